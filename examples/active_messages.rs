@@ -0,0 +1,69 @@
+#![deny(warnings)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use mpi::{
+    active_messages::{ActiveMessages, HandlerId},
+    traits::*,
+    MpiError, Threading,
+};
+
+const ADD: HandlerId = 0;
+const GREET: HandlerId = 1;
+const FAIL: HandlerId = 2;
+
+static GREETINGS: AtomicUsize = AtomicUsize::new(0);
+
+fn main() {
+    let (universe, threading) = mpi::initialize_with_threading(Threading::Multiple).unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+    let next_rank = (rank + 1) % size;
+
+    // A panicking handler fails its request without aborting all processes.
+    mpi::environment::abort_on_panic(101);
+
+    // All processes register the same handlers.
+    let messages = ActiveMessages::builder()
+        .handler(ADD, |(a, b): (i32, i32)| a + b)
+        .handler(GREET, |greeting: String| {
+            assert!(greeting.starts_with("Hello from "));
+            GREETINGS.fetch_add(1, Ordering::Relaxed);
+        })
+        .handler(FAIL, |fail: bool| assert!(!fail, "Failing on purpose"))
+        .start(&world);
+    assert_eq!(
+        threading == Threading::Multiple,
+        messages.has_progress_thread()
+    );
+
+    let sum: i32 = messages.call(next_rank, ADD, &(rank, 1)).unwrap();
+    assert_eq!(rank + 1, sum);
+
+    messages
+        .send(next_rank, GREET, &format!("Hello from {}", rank))
+        .unwrap();
+
+    match messages.call::<_, ()>(next_rank, 99, &()) {
+        Err(MpiError::ActiveMessage {
+            rank, handler: 99, ..
+        }) => assert_eq!(next_rank, rank),
+        result => panic!("Expected an unknown handler, got {:?}", result),
+    }
+
+    // A panicking handler fails the call instead of the process serving it.
+    match messages.call::<_, ()>(next_rank, FAIL, &true) {
+        Err(MpiError::ActiveMessage {
+            handler: FAIL,
+            reason,
+            ..
+        }) => assert!(reason.contains("Failing on purpose")),
+        result => panic!("Expected a failed handler, got {:?}", result),
+    }
+
+    // Without a progress thread, requests are served while waiting or polling.
+    messages.poll();
+    messages.finish().unwrap();
+    assert_eq!(1, GREETINGS.load(Ordering::Relaxed));
+}
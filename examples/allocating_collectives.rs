@@ -0,0 +1,65 @@
+#![deny(warnings)]
+
+use mpi::{traits::*, Count};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+
+    let root_rank = 0;
+    let root_process = world.process_at_rank(root_rank);
+
+    // Every process contributes the same number of elements
+    let all = world.all_gather_vec(&[rank, 2 * rank]);
+    assert_eq!((0..size).flat_map(|r| [r, 2 * r]).collect::<Vec<_>>(), all);
+
+    // Process `r` contributes `r` elements
+    let msg: Vec<_> = (0..rank).collect();
+    let (all, counts) = world.all_gather_varcount_vec(&msg[..]);
+    assert_eq!((0..size).collect::<Vec<Count>>(), counts);
+    assert_eq!((0..size).flat_map(|r| 0..r).collect::<Vec<_>>(), all);
+
+    match root_process.gather_vec(&msg[..]) {
+        Some(gathered) => {
+            assert_eq!(root_rank, rank);
+            assert_eq!((0..size).flat_map(|r| 0..r).collect::<Vec<_>>(), gathered);
+        }
+        None => assert_ne!(root_rank, rank),
+    }
+
+    let mut values = if rank == root_rank {
+        vec![1.5f64, 2.5, 3.5]
+    } else {
+        Vec::new()
+    };
+    root_process.broadcast_vec(&mut values);
+    assert_eq!(vec![1.5, 2.5, 3.5], values);
+
+    // The root sends `r + 1` copies of `r` to process `r`
+    let piece = if rank == root_rank {
+        let counts: Vec<Count> = (1..=size).collect();
+        let sendbuf: Vec<_> = (0..size)
+            .flat_map(|r| std::iter::repeat(r).take(r as usize + 1))
+            .collect();
+        root_process.scatter_varcount_vec_root(&sendbuf[..], &counts[..])
+    } else {
+        root_process.scatter_varcount_vec()
+    };
+    assert_eq!(vec![rank; rank as usize + 1], piece);
+
+    // Process `r` sends `d + 1` copies of `r` to process `d`
+    let sendcounts: Vec<Count> = (1..=size).collect();
+    let sendbuf: Vec<_> = (0..size)
+        .flat_map(|d| std::iter::repeat(rank).take(d as usize + 1))
+        .collect();
+    let (received, recvcounts) = world.all_to_all_varcount_vec(&sendbuf[..], &sendcounts[..]);
+    assert_eq!(vec![rank + 1; size as usize], recvcounts);
+    assert_eq!(
+        (0..size)
+            .flat_map(|r| std::iter::repeat(r).take(rank as usize + 1))
+            .collect::<Vec<_>>(),
+        received
+    );
+}
@@ -0,0 +1,67 @@
+#![deny(warnings)]
+#![allow(clippy::float_cmp)]
+
+use std::{
+    sync::mpsc::{RecvTimeoutError, TryRecvError},
+    time::Duration,
+};
+
+use mpi::{
+    channel::{channel, Receiver, Select, Sender},
+    tag::TagSpace,
+    traits::*,
+};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let mut world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+    let next_rank = (rank + 1) % size;
+    let previous_rank = (rank + size - 1) % size;
+
+    TagSpace::attach(&mut world);
+    let space = world.get_attr::<TagSpace>().unwrap();
+    let numbers = space.tag::<[i32]>().unwrap();
+    let halves = space.tag::<[f64]>().unwrap();
+    let nothing = space.tag::<[u8]>().unwrap();
+
+    // A channel around the ring of processes
+    let tx = Sender::new(world.process_at_rank(next_rank), numbers);
+    let rx = Receiver::new(world.process_at_rank(previous_rank), numbers);
+    tx.send(rank);
+    tx.send_all(&[10 * rank, 100 * rank]);
+    tx.close();
+    let received: Vec<i32> = rx.iter().collect();
+    assert_eq!(
+        vec![previous_rank, 10 * previous_rank, 100 * previous_rank],
+        received
+    );
+    assert_eq!(Err(TryRecvError::Disconnected), rx.try_recv());
+
+    // Nothing is sent on this channel until the end.
+    let (quiet_tx, quiet_rx) = channel::<u8, _>(&world, rank, nothing);
+    assert_eq!(Err(TryRecvError::Empty), quiet_rx.try_recv());
+    assert_eq!(
+        Err(RecvTimeoutError::Timeout),
+        quiet_rx.recv_timeout(Duration::from_millis(10))
+    );
+
+    let halves_tx = Sender::new(world.process_at_rank(next_rank), halves);
+    let halves_rx = Receiver::new(world.process_at_rank(previous_rank), halves);
+    halves_tx.send(f64::from(rank) + 0.5);
+
+    let mut select = Select::new();
+    let quiet_index = select.recv(&quiet_rx);
+    let halves_index = select.recv(&halves_rx);
+    assert_eq!(halves_index, select.ready());
+    assert_eq!(Ok(f64::from(previous_rank) + 0.5), halves_rx.try_recv());
+    assert_eq!(None, select.try_ready());
+
+    quiet_tx.close();
+    assert_eq!(quiet_index, select.ready());
+    assert_eq!(Err(TryRecvError::Disconnected), quiet_rx.try_recv());
+
+    drop(halves_tx);
+    assert!(halves_rx.recv().is_err());
+}
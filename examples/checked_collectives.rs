@@ -0,0 +1,62 @@
+#![deny(warnings)]
+
+use mpi::{traits::*, MpiError};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+    let root_process = world.process_at_rank(0);
+
+    // Matching arguments pass the verification and the call is made.
+    world.checked().barrier().unwrap();
+    let mut buf = vec![0u32; 10];
+    if rank == 0 {
+        buf.iter_mut().enumerate().for_each(|(i, x)| *x = i as u32);
+    }
+    root_process.checked().broadcast_into(&mut buf[..]).unwrap();
+    assert_eq!((0..10).collect::<Vec<u32>>(), buf);
+
+    let mut sum = 0;
+    world
+        .checked()
+        .all_reduce_into(&rank, &mut sum, SystemOperation::sum())
+        .unwrap();
+    assert_eq!((0..size).sum::<i32>(), sum);
+
+    if size < 2 {
+        return;
+    }
+
+    // The last rank broadcasts more elements than the others.
+    let len = if rank == size - 1 { 12 } else { 10 };
+    let mut buf = vec![0u32; len];
+    match root_process.checked().broadcast_into(&mut buf[..]) {
+        Err(MpiError::CollectiveMismatch { operation, ranks }) => {
+            assert_eq!("MPI_Bcast", operation);
+            assert_eq!(vec![size - 1], ranks);
+        }
+        result => panic!("Expected a mismatch, got {:?}", result),
+    }
+
+    // The last rank names a different root.
+    let root = if rank == size - 1 { 1 } else { 0 };
+    let mut value = rank;
+    match world
+        .process_at_rank(root)
+        .checked()
+        .broadcast_into(&mut value)
+    {
+        Err(MpiError::CollectiveMismatch { ranks, .. }) => assert_eq!(vec![size - 1], ranks),
+        result => panic!("Expected a mismatch, got {:?}", result),
+    }
+
+    // Different collectives do not match either.
+    let result = if rank == size - 1 {
+        world.checked().barrier()
+    } else {
+        root_process.checked().broadcast_into(&mut value)
+    };
+    assert!(matches!(result, Err(MpiError::CollectiveMismatch { .. })));
+}
@@ -0,0 +1,42 @@
+#![deny(warnings)]
+
+use mpi::{traits::*, Config, InitError, Threading};
+
+fn main() {
+    let mut universe = Config::new()
+        .threading(Threading::Single)
+        .bsend_buffer(1 << 16)
+        .panic_abort(true)
+        .init()
+        .unwrap();
+    assert_eq!(1 << 16, universe.buffer_size());
+
+    let world = universe.world();
+    let rank = world.rank();
+    world.this_process().buffered_send(&rank);
+    let (received, _) = world.this_process().receive::<i32>();
+    assert_eq!(rank, received);
+
+    assert_eq!(
+        InitError::AlreadyInitialized,
+        Config::new().init().err().unwrap()
+    );
+    assert!(mpi::initialize().is_none());
+    assert_eq!(
+        InitError::AlreadyInitialized,
+        mpi::environment::Universe::from_existing().err().unwrap()
+    );
+
+    universe.detach_buffer();
+    drop(world);
+    universe.finalize().unwrap();
+
+    assert_eq!(
+        InitError::AlreadyFinalized,
+        Config::new().init().err().unwrap()
+    );
+    assert_eq!(
+        InitError::AlreadyFinalized,
+        mpi::environment::Universe::from_existing().err().unwrap()
+    );
+}
@@ -0,0 +1,92 @@
+#![deny(warnings)]
+
+use std::{thread, time::Duration};
+
+use mpi::{
+    debug_checks::{self, Operation, OperationKind, Report},
+    topology::SimpleCommunicator,
+    traits::*,
+    Threading,
+};
+
+fn operation(rank: i32, routine: &str, kind: OperationKind, peer: Option<i32>) -> Operation {
+    Operation {
+        rank,
+        routine: routine.to_string(),
+        kind,
+        peer,
+        tag: None,
+        communicator: Some("MPI_COMM_WORLD".to_string()),
+        members: if kind == OperationKind::Collective {
+            vec![0, 1, 2, 3]
+        } else {
+            Vec::new()
+        },
+        elapsed: Duration::from_secs(90),
+    }
+}
+
+fn main() {
+    let (universe, threading) = mpi::initialize_with_threading(Threading::Multiple).unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+    let next = world.process_at_rank((rank + 1) % size);
+    let previous = world.process_at_rank((rank + size - 1) % size);
+
+    debug_checks::set_timeout(Duration::from_secs(600));
+    assert_eq!(Duration::from_secs(600), debug_checks::timeout());
+    assert!(debug_checks::outstanding().is_empty());
+
+    if threading == Threading::Multiple {
+        // A receive blocked in another thread shows up as outstanding until it completes.
+        thread::scope(|scope| {
+            let source = previous.rank();
+            let receiver = scope.spawn(move || {
+                let world = SimpleCommunicator::world();
+                world.process_at_rank(source).receive_with_tag::<i32>(5).0
+            });
+            let outstanding = loop {
+                let outstanding = debug_checks::outstanding();
+                if !outstanding.is_empty() {
+                    break outstanding;
+                }
+                thread::yield_now();
+            };
+            assert_eq!(1, outstanding.len());
+            assert_eq!("MPI_Recv", outstanding[0].routine);
+            assert_eq!(OperationKind::PointToPoint, outstanding[0].kind);
+            assert_eq!(rank, outstanding[0].rank);
+            assert_eq!(Some(previous.rank()), outstanding[0].peer);
+            assert_eq!(Some(5), outstanding[0].tag);
+            assert_eq!(
+                Some("MPI_COMM_WORLD"),
+                outstanding[0].communicator.as_deref()
+            );
+
+            next.send_with_tag(&rank, 5);
+            assert_eq!(previous.rank(), receiver.join().unwrap());
+        });
+        assert!(debug_checks::outstanding().is_empty());
+    }
+
+    if rank == 0 {
+        // Ranks 0 and 1 receive from each other while ranks 2 and 3 broadcast from different
+        // roots.
+        let report = Report::new(vec![
+            operation(0, "MPI_Recv", OperationKind::PointToPoint, Some(1)),
+            operation(1, "MPI_Recv", OperationKind::PointToPoint, Some(0)),
+            operation(2, "MPI_Bcast", OperationKind::Collective, Some(2)),
+            operation(3, "MPI_Bcast", OperationKind::Collective, Some(3)),
+        ]);
+        assert_eq!(4, report.operations().len());
+        assert_eq!(&[vec![0, 1]], report.cycles());
+        assert!(report.edges().any(|edge| edge == (2, 0)));
+        assert_eq!(1, report.mismatches().len());
+        assert_eq!(2, report.mismatches()[0].calls.len());
+
+        let text = report.to_string();
+        assert!(text.contains("rsmpi: deadlock 0 -> 1 -> 0"));
+        assert!(text.contains("rank 3: MPI_Bcast root 3 on MPI_COMM_WORLD for 90.0 s"));
+    }
+}
@@ -11,4 +11,18 @@ fn main() {
     moon.barrier();
 
     assert_eq!(CommunicatorRelation::Congruent, world.compare(&moon));
+
+    #[cfg(not(msmpi))]
+    {
+        let future = world.immediate_duplicate();
+        // Other work can proceed while the duplication completes.
+        moon.barrier();
+        let sun = future.get();
+        sun.barrier();
+        assert_eq!(CommunicatorRelation::Congruent, world.compare(&sun));
+
+        let info = mpi::info::Info::from_pairs([("mpi_assert_no_any_tag", "true")]).unwrap();
+        let star = world.duplicate_with_info(&info);
+        assert_eq!(CommunicatorRelation::Congruent, world.compare(&star));
+    }
 }
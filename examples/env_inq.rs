@@ -1,12 +1,32 @@
 #![deny(warnings)]
 
+use mpi::{environment::IoProcess, traits::*};
+
 fn main() {
     let (version, subversion) = mpi::environment::version();
     println!("This is MPI-{}.{}.", version, subversion);
     println!("{}", mpi::environment::library_version().unwrap());
-    let _universe = mpi::initialize().unwrap();
+    let universe = mpi::initialize().unwrap();
     println!("{}", mpi::environment::processor_name().unwrap());
 
+    assert!(universe.tag_upper_bound() >= 32767);
+    if let Some(host) = universe.host() {
+        assert!(0 <= host && host < universe.world().size());
+    }
+    if let IoProcess::Rank(rank) = universe.io_rank() {
+        assert!(0 <= rank && rank < universe.world().size());
+    }
+    assert!(universe.last_used_code() >= 0);
+    println!(
+        "Tags up to {}, clocks are {}synchronized.",
+        universe.tag_upper_bound(),
+        if universe.wtime_is_global() {
+            ""
+        } else {
+            "not "
+        }
+    );
+
     #[cfg(not(msmpi))]
     assert!(
         version >= 3,
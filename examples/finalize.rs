@@ -0,0 +1,41 @@
+#![deny(warnings)]
+
+use std::sync::{Arc, Mutex};
+
+use mpi::{environment, topology::SimpleCommunicator, traits::*};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    // A panic on any rank brings down all of them instead of leaving them waiting.
+    environment::abort_on_panic(3);
+
+    let world = universe.world();
+    let rank = world.rank();
+
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    for name in ["first", "second"] {
+        let calls = Arc::clone(&calls);
+        environment::on_finalize(move || calls.lock().unwrap().push(name));
+    }
+    {
+        let calls = Arc::clone(&calls);
+        environment::on_finalize(move || {
+            // MPI can still be used while the callbacks run.
+            let sum = SimpleCommunicator::world().all_reduce(1, SystemOperation::sum());
+            assert_eq!(SimpleCommunicator::world().size(), sum);
+            calls.lock().unwrap().push("communicate");
+        });
+    }
+    world.barrier();
+    assert!(calls.lock().unwrap().is_empty());
+
+    drop(world);
+    universe.finalize().unwrap();
+    assert_eq!(
+        vec!["communicate", "second", "first"],
+        *calls.lock().unwrap()
+    );
+    if rank == 0 {
+        println!("Finalized");
+    }
+}
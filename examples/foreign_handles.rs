@@ -0,0 +1,77 @@
+#![deny(warnings)]
+
+use mpi::{
+    collective::BorrowedOperation,
+    datatype::{DatatypeRef, MutView},
+    ffi,
+    request::{Request, StaticScope},
+    topology::{BorrowedCommunicator, BorrowedGroup, CommunicatorRelation},
+    traits::*,
+};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+
+    // A C library creates a communicator and lends it to us.
+    let mut raw_comm = unsafe { ffi::RSMPI_COMM_NULL };
+    unsafe {
+        ffi::MPI_Comm_dup(ffi::RSMPI_COMM_WORLD, &mut raw_comm);
+    }
+    {
+        let comm = unsafe { BorrowedCommunicator::from_raw_borrowed(raw_comm) }.unwrap();
+        let sum = comm.all_reduce(rank, SystemOperation::sum());
+        assert_eq!((0..size).sum::<i32>(), sum);
+    }
+    // Dropping the borrowed communicator left it to the library to free.
+    unsafe {
+        ffi::MPI_Comm_free(&mut raw_comm);
+    }
+    assert!(unsafe { BorrowedCommunicator::from_raw_borrowed(raw_comm) }.is_none());
+
+    // Handles travel to and from Fortran as integers.
+    let comm = unsafe { BorrowedCommunicator::from_fortran(world.to_fortran()) }.unwrap();
+    assert!(matches!(
+        world.compare(&comm),
+        CommunicatorRelation::Identical
+    ));
+
+    let group = world.group();
+    let borrowed_group = unsafe { BorrowedGroup::from_fortran(group.to_fortran()) }.unwrap();
+    assert_eq!(size, borrowed_group.size());
+
+    let op =
+        unsafe { BorrowedOperation::from_fortran(SystemOperation::max().to_fortran()) }.unwrap();
+    assert_eq!(size - 1, comm.all_reduce(rank, op));
+
+    // A datatype committed by the Fortran solver
+    let mut raw_type = unsafe { ffi::RSMPI_DATATYPE_NULL };
+    let fortran_type = unsafe {
+        ffi::MPI_Type_contiguous(2, ffi::RSMPI_INT32_T, &mut raw_type);
+        ffi::MPI_Type_commit(&mut raw_type);
+        ffi::RSMPI_Type_c2f(raw_type)
+    };
+    {
+        let pair = unsafe { DatatypeRef::from_fortran(fortran_type) }.unwrap();
+        assert_eq!(fortran_type, pair.to_fortran());
+        let mut buf = if rank == 0 { [1i32, 2, 3, 4] } else { [0; 4] };
+        let mut view = unsafe { MutView::with_count_and_datatype(&mut buf[..], 2, &pair) };
+        world.process_at_rank(0).broadcast_into(&mut view);
+        assert_eq!([1, 2, 3, 4], buf);
+    }
+    unsafe {
+        ffi::MPI_Type_free(&mut raw_type);
+    }
+
+    // A request started by Fortran code is completed from Rust.
+    let fortran_request = unsafe {
+        let mut request = ffi::RSMPI_REQUEST_NULL;
+        ffi::MPI_Ibarrier(ffi::RSMPI_COMM_WORLD, &mut request);
+        ffi::RSMPI_Request_c2f(request)
+    };
+    let request = unsafe { Request::from_fortran(fortran_request, &(), StaticScope) };
+    assert_eq!(fortran_request, request.to_fortran());
+    request.wait();
+}
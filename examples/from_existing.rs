@@ -0,0 +1,41 @@
+#![deny(warnings)]
+
+use std::ptr;
+
+use mpi::{environment::Universe, ffi, traits::*, Config, InitError};
+
+fn main() {
+    // Some other library initializes MPI.
+    unsafe {
+        ffi::MPI_Init(ptr::null_mut(), ptr::null_mut());
+    }
+    assert_eq!(
+        InitError::AlreadyInitialized,
+        Config::new().init().err().unwrap()
+    );
+
+    let universe = Universe::from_existing().unwrap();
+    let world = universe.world();
+    let sum = world.all_reduce(1, SystemOperation::sum());
+    assert_eq!(world.size(), sum);
+    assert_eq!(
+        InitError::AlreadyInitialized,
+        Universe::from_existing().err().unwrap()
+    );
+    drop(world);
+    universe.finalize().unwrap();
+
+    // MPI is still initialized, so it can be picked up again.
+    let universe = Universe::from_existing().unwrap();
+    universe.world().barrier();
+    drop(universe);
+
+    // The other library finalizes MPI.
+    unsafe {
+        ffi::MPI_Finalize();
+    }
+    assert_eq!(
+        InitError::AlreadyFinalized,
+        Universe::from_existing().err().unwrap()
+    );
+}
@@ -2,7 +2,7 @@
 #![allow(clippy::many_single_char_names)]
 
 use mpi::{
-    topology::{GroupRelation, Rank, SystemGroup},
+    topology::{GroupRelation, Rank, RankRange, SystemGroup},
     traits::*,
 };
 
@@ -79,4 +79,35 @@ fn main() {
         Some(rev[g.rank().unwrap() as usize]),
         r.translate_rank(g.rank().unwrap(), &g)
     );
+
+    // a descending range reverses the group as well
+    let r_ = g.include_ranges(&[RankRange::with_stride(g.size() - 1..=0, -1)]);
+    assert_eq!(GroupRelation::Identical, r.compare(&r_));
+    assert_eq!(rev, r_.ranks());
+
+    // even and odd ranks as strided ranges and as predicates
+    let evens = RankRange::with_stride(0..=g.size() - 1, 2);
+    assert_eq!(
+        (0, g.size() - 1, 2),
+        (evens.first(), evens.last(), evens.stride())
+    );
+    let even = g.include_ranges(&[evens]);
+    let odd = g.exclude_ranges(&[evens]);
+    assert_eq!(
+        GroupRelation::Identical,
+        even.compare(&g.filter(|r| r % 2 == 0))
+    );
+    assert_eq!(
+        GroupRelation::Identical,
+        odd.compare(&g.filter(|r| r % 2 != 0))
+    );
+    assert!(even.ranks().iter().all(|r| r % 2 == 0));
+    assert_eq!(g.size(), even.size() + odd.size());
+
+    // ranges without a stride cover the first half
+    if !first_half.is_empty() {
+        let f_range = g.include_ranges(&[(0..=g.size() / 2 - 1).into()]);
+        assert_eq!(GroupRelation::Identical, f.compare(&f_range));
+        assert_eq!(first_half, f_range.ranks());
+    }
 }
@@ -0,0 +1,66 @@
+#![deny(warnings)]
+
+use mpi::{
+    topology::{dims_create, HaloLayout},
+    traits::*,
+    Count,
+};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    let mut dims = [0, 0];
+    dims_create(world.size(), &mut dims);
+    assert_eq!(world.size(), dims.iter().product::<Count>());
+    assert!(dims[0] >= dims[1]);
+
+    let grid = world
+        .create_cartesian_communicator(&dims, &[true, true], false)
+        .expect("Every process is part of the grid");
+
+    let faces: Vec<_> = grid.neighbors().collect();
+    assert_eq!(4, faces.len());
+    assert!(faces
+        .iter()
+        .all(|n| n.rank.is_some() && n.offset.iter().filter(|&&o| o != 0).count() == 1));
+    assert_eq!(8, grid.neighbors_with_diagonals().count());
+
+    let coords = grid.rank_to_coordinates(grid.rank());
+    let row_comm = grid.axis_subgroup(1);
+    assert_eq!(1, row_comm.num_dimensions());
+    assert_eq!(dims[1], row_comm.size());
+    assert_eq!(coords[1], row_comm.rank());
+
+    // a 4 x 3 interior surrounded by one layer of ghost cells
+    let layout = HaloLayout::new(vec![6, 5], vec![1, 1]);
+    let (rows, cols) = (6, 5);
+    let rank = grid.rank();
+    let mut buf = vec![-1; layout.len()];
+    for row in 1..rows - 1 {
+        for col in 1..cols - 1 {
+            buf[row * cols + col] = rank;
+        }
+    }
+
+    // Tags below 4 stay free for other messages on the grid
+    grid.halo_exchange(&mut buf[..], &layout, 4);
+
+    for neighbor in faces {
+        let expected = neighbor.rank.unwrap();
+        match neighbor.offset[..] {
+            [-1, 0] => assert!((1..cols - 1).all(|col| buf[col] == expected)),
+            [1, 0] => assert!((1..cols - 1).all(|col| buf[(rows - 1) * cols + col] == expected)),
+            [0, -1] => assert!((1..rows - 1).all(|row| buf[row * cols] == expected)),
+            [0, 1] => assert!((1..rows - 1).all(|row| buf[row * cols + cols - 1] == expected)),
+            _ => unreachable!(),
+        }
+    }
+
+    // the corners are not part of any face exchange
+    for corner in [0, cols - 1, (rows - 1) * cols, rows * cols - 1] {
+        assert_eq!(-1, buf[corner]);
+    }
+    // the interior is unchanged
+    assert!((1..rows - 1).all(|row| (1..cols - 1).all(|col| buf[row * cols + col] == rank)));
+}
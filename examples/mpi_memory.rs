@@ -0,0 +1,54 @@
+#![deny(warnings)]
+
+use mpi::{
+    memory::{MpiBox, MpiVec},
+    request::WaitGuard,
+    traits::*,
+    Rank,
+};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+
+    let mut send: MpiVec<Rank> = MpiVec::from_iter_in(&universe, (0..4).map(|i| rank * 4 + i));
+    send.push(rank);
+    assert_eq!(5, send.len());
+    assert_eq!(Some(rank), send.pop());
+
+    let mut recv = MpiVec::new(&universe);
+    recv.resize(send.len() * size as usize, -1);
+    world.all_gather_into(&send, &mut recv[..]);
+    let expected: Vec<Rank> = (0..4 * size).collect();
+    assert_eq!(&expected[..], &recv[..]);
+
+    let mut total = MpiBox::new(&universe, 0);
+    world.all_reduce_into(&rank, &mut total, SystemOperation::sum());
+    assert_eq!((0..size).sum::<Rank>(), *total);
+
+    let next = (rank + 1) % size;
+    let previous = (rank - 1 + size) % size;
+    let outgoing = MpiVec::from_slice(&universe, &[rank; 3]);
+    let mut received = MpiVec::from_slice(&universe, &[-1; 3]);
+    mpi::request::scope(|scope| {
+        let _sreq = WaitGuard::from(world.process_at_rank(next).immediate_send(scope, &outgoing));
+        world
+            .process_at_rank(previous)
+            .immediate_receive_into(scope, &mut received)
+            .wait();
+    });
+    assert_eq!(&[previous; 3][..], &received[..]);
+
+    #[cfg(feature = "allocator-api2")]
+    {
+        use mpi::memory::MpiAllocator;
+
+        let mut buf = allocator_api2::vec::Vec::new_in(MpiAllocator::new(&universe));
+        buf.extend_from_slice(&[rank; 16]);
+        let mut max = MpiVec::from_slice(&universe, &[-1; 16]);
+        world.all_reduce_into(&buf[..], &mut max, SystemOperation::max());
+        assert!(max.iter().all(|&r| r == size - 1));
+    }
+}
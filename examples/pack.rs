@@ -1,6 +1,9 @@
 #![deny(warnings)]
 
-use mpi::traits::*;
+use mpi::{
+    datatype::{self, MutView, UserDatatype, View},
+    traits::*,
+};
 
 fn main() {
     let universe = mpi::initialize().unwrap();
@@ -15,4 +18,48 @@ fn main() {
     }
 
     assert_eq!([3, 2, 1], new_ints);
+
+    let mut checked_ints = [0, 0, 0];
+    let position = world
+        .unpack_from(&packed, &mut checked_ints[..], 0)
+        .unwrap();
+    assert_eq!([3, 2, 1], checked_ints);
+    assert_eq!(packed.len(), position as usize);
+
+    let (vec_ints, _) = world.unpack_vec::<i32>(&packed, 3, 0).unwrap();
+    assert_eq!(vec![3, 2, 1], vec_ints);
+
+    // Asking for more than was packed is an error rather than an out of bounds read
+    let mut too_many = [0i32; 4];
+    assert!(world.unpack_from(&packed, &mut too_many[..], 0).is_err());
+    assert!(world.unpack_vec::<i32>(&packed, 3, position).is_err());
+
+    // The last value of the buffer has a derived datatype
+    let strided = UserDatatype::vector(2, 1, 2, &i32::equivalent_datatype());
+    let spread = [5i32, -1, 6, -1];
+    let mut derived = world.pack(&ints[..]);
+    derived.extend(world.pack(unsafe { &View::with_count_and_datatype(&spread[..], 1, &strided) }));
+    let mut gathered = [0i32; 4];
+    let end = world
+        .unpack_from(
+            &derived,
+            unsafe { &mut MutView::with_count_and_datatype(&mut gathered[..], 1, &strided) },
+            position,
+        )
+        .unwrap();
+    assert_eq!([5, 0, 6, 0], gathered);
+    assert_eq!(derived.len(), end as usize);
+
+    let external = datatype::pack_external(&ints[..]);
+    assert_eq!(
+        datatype::pack_external_size(3, &i32::equivalent_datatype()) as usize,
+        external.len()
+    );
+
+    let mut external_ints = [0, 0, 0];
+    datatype::unpack_external_from(&external, &mut external_ints[..], 0).unwrap();
+    assert_eq!([3, 2, 1], external_ints);
+
+    let (external_vec, _) = datatype::unpack_external_vec::<i32>(&external, 3, 0).unwrap();
+    assert_eq!(vec![3, 2, 1], external_vec);
 }
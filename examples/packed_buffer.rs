@@ -0,0 +1,79 @@
+#![deny(warnings)]
+
+use mpi::{
+    datatype::{MutView, PackedBuffer, UserDatatype, View},
+    request::WaitGuard,
+    traits::*,
+};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let next_rank = (rank + 1) % size;
+    let previous_rank = (rank - 1 + size) % size;
+
+    let mut outgoing = PackedBuffer::new(&world);
+    outgoing
+        .append(&rank)
+        .append(&[1.5f64 * rank as f64, 2.5][..])
+        .append(&[true, false, true][..]);
+
+    mpi::request::scope(|scope| {
+        let _sreq = WaitGuard::from(
+            world
+                .process_at_rank(next_rank)
+                .immediate_send(scope, outgoing.as_bytes()),
+        );
+
+        let (bytes, _) = world.process_at_rank(previous_rank).receive_vec::<u8>();
+        let mut incoming = PackedBuffer::from_bytes(&world, bytes);
+
+        let mut sender = 0;
+        incoming.read_into(&mut sender).unwrap();
+        assert_eq!(previous_rank, sender);
+
+        let floats = incoming.read_vec::<f64>(2).unwrap();
+        assert_eq!(vec![1.5 * previous_rank as f64, 2.5], floats);
+
+        let bools = incoming.read_vec::<bool>(3).unwrap();
+        assert_eq!(vec![true, false, true], bools);
+
+        assert_eq!(0, incoming.remaining());
+        assert!(incoming.read_vec::<i32>(1).is_err());
+    });
+
+    // A derived datatype as the last value, which may pack into fewer bytes than
+    // `MPI_Pack_size()` reserves for it
+    let strided = UserDatatype::vector(2, 2, 3, &i32::equivalent_datatype());
+    let grid = [1, 2, -1, 3, 4, -1];
+    let mut derived = PackedBuffer::new(&world);
+    derived
+        .append(&rank)
+        .append(unsafe { &View::with_count_and_datatype(&grid[..], 1, &strided) });
+
+    let mut derived = PackedBuffer::from_bytes(&world, derived.into_bytes());
+    let mut packed_rank = -1;
+    derived.read_into(&mut packed_rank).unwrap();
+    assert_eq!(rank, packed_rank);
+    let mut unpacked = [0; 6];
+    derived
+        .read_into(unsafe { &mut MutView::with_count_and_datatype(&mut unpacked[..], 1, &strided) })
+        .unwrap();
+    assert_eq!([1, 2, 0, 3, 4, 0], unpacked);
+    assert_eq!(0, derived.remaining());
+
+    let mut portable = PackedBuffer::external32();
+    portable.append(&[7u16, 8, 9][..]).append(&-1i64);
+
+    let mut portable = PackedBuffer::external32_from_bytes(portable.into_bytes());
+    assert_eq!(vec![7u16, 8, 9], portable.read_vec::<u16>(3).unwrap());
+    let mut last = 0i64;
+    portable.read_into(&mut last).unwrap();
+    assert_eq!(-1, last);
+
+    portable.rewind();
+    assert_eq!(vec![7u16], portable.read_vec::<u16>(1).unwrap());
+}
@@ -0,0 +1,67 @@
+#![deny(warnings)]
+
+use mpi::{
+    profiling::{self, TraceFormat},
+    traits::*,
+};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+    let next = world.process_at_rank((rank + 1) % size);
+    let previous = world.process_at_rank((rank + size - 1) % size);
+
+    // Nothing is recorded until profiling is enabled.
+    world.barrier();
+    assert!(profiling::events().is_empty());
+
+    profiling::enable();
+    let msg = [rank as u64; 4];
+    let mut received = [0u64; 4];
+    mpi::request::scope(|scope| {
+        let sreq = next.immediate_send_with_tag(scope, &msg[..], 7);
+        previous.receive_into_with_tag(&mut received[..], 7);
+        sreq.wait();
+    });
+    let sum = world.all_reduce(rank, SystemOperation::sum());
+    profiling::disable();
+    world.barrier();
+
+    assert_eq!((0..size).sum::<i32>(), sum);
+    let events = profiling::events();
+    let names: Vec<_> = events.iter().map(|e| e.name).collect();
+    assert_eq!(
+        vec!["MPI_Isend", "MPI_Recv", "MPI_Wait", "MPI_Allreduce"],
+        names
+    );
+
+    let send = &events[0];
+    assert_eq!(Some(next.rank()), send.peer);
+    assert_eq!(Some(7), send.tag);
+    assert_eq!(std::mem::size_of_val(&msg), send.bytes);
+    assert_eq!(Some("MPI_COMM_WORLD"), send.communicator.as_deref());
+    assert_eq!(Some(previous.rank()), events[1].peer);
+    assert_eq!("collective", events[3].category);
+    assert!(events.iter().all(|e| e.duration() >= 0.0));
+
+    let mut trace = Vec::new();
+    profiling::write_trace(&mut trace, &events, rank, TraceFormat::Text).unwrap();
+    assert_eq!(
+        events.len(),
+        String::from_utf8(trace).unwrap().lines().count()
+    );
+
+    // The trace of each rank is written when the universe is dropped.
+    let prefix = std::env::temp_dir().join(format!("rsmpi-profiling-{}", std::process::id()));
+    profiling::set_output(Some((prefix.clone(), TraceFormat::Chrome)));
+    drop(universe);
+
+    let mut path = prefix.into_os_string();
+    path.push(format!(".{}.json", rank));
+    let json = std::fs::read_to_string(&path).unwrap();
+    assert!(json.starts_with("{\"traceEvents\":["));
+    assert_eq!(events.len(), json.matches("\"ph\":\"X\"").count());
+    std::fs::remove_file(&path).unwrap();
+}
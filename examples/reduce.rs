@@ -8,6 +8,7 @@ use std::os::raw::{c_int, c_void};
 use mpi::collective::UserOperation;
 use mpi::{
     collective::{self, SystemOperation, UnsafeUserOperation},
+    datatype::ValueIndex,
     ffi::MPI_Datatype,
     topology::Rank,
     traits::*,
@@ -30,6 +31,34 @@ fn test_user_operations<C: Communicator>(comm: C) {
         }),
     );
     assert_eq!(h, size * (size + 1) / 2);
+
+    let mut sum = 0;
+    comm.all_reduce_into(
+        &(rank + 1),
+        &mut sum,
+        &UserOperation::commutative_for(|x: &Rank, y: &mut Rank| *y += x),
+    );
+    assert_eq!(sum, size * (size + 1) / 2);
+
+    // Element-wise minimum of `[rank, 1]` over all processes
+    let mut pair = [0; 2];
+    comm.all_reduce_into(
+        &[rank, 1][..],
+        &mut pair[..],
+        &UserOperation::elementwise(false, |x: &Rank, y: &mut Rank| *y = (*x).min(*y)),
+    );
+    assert_eq!(pair, [0, 1]);
+
+    let max = UserOperation::elementwise_builder(true)
+        .with_type(|x: &Rank, y: &mut Rank| *y = (*x).max(*y))
+        .with_type(|x: &f64, y: &mut f64| *y = x.max(*y))
+        .build();
+    let mut max_rank = 0;
+    comm.all_reduce_into(&rank, &mut max_rank, &max);
+    assert_eq!(max_rank, size - 1);
+    let mut max_float = 0.0;
+    comm.all_reduce_into(&(rank as f64 / 2.0), &mut max_float, &max);
+    assert_eq!(max_float, (size - 1) as f64 / 2.0);
 }
 
 #[cfg(not(feature = "user-operations"))]
@@ -91,6 +120,30 @@ fn main() {
     world.all_reduce_into(&rank, &mut max, SystemOperation::max());
     assert_eq!(max, size - 1);
 
+    assert_eq!(world.all_reduce(rank, SystemOperation::max()), size - 1);
+    assert_eq!(
+        world.all_reduce(rank as f64, SystemOperation::sum()),
+        (size * (size - 1) / 2) as f64
+    );
+
+    // Every process but the last holds the same minimum, so the lowest rank wins
+    let value = if rank == size - 1 { 5.0f64 } else { -1.0 };
+    assert_eq!(world.all_min_loc(value), (-1.0, 0));
+    assert_eq!(world.all_max_loc(value), (5.0, size - 1));
+
+    let mut pair = ValueIndex::default();
+    world.all_reduce_into(
+        &ValueIndex::from((-rank, rank)),
+        &mut pair,
+        SystemOperation::min_loc(),
+    );
+    assert_eq!(<(i32, Rank)>::from(pair), (1 - size, size - 1));
+
+    assert!(world.any(rank == 0));
+    assert!(!world.any(rank == size));
+    assert!(world.all(rank < size));
+    assert!(!world.all(rank == 0));
+
     let a: u16 = 0b0000_1111_1111_0000;
     let b: u16 = 0b0011_1100_0011_1100;
 
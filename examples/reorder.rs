@@ -0,0 +1,70 @@
+#![deny(warnings)]
+
+use mpi::{
+    topology::{dims_create, CommGraph, NodeMap, Reordering, Topology},
+    traits::*,
+};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    // Two pairs of heavily communicating vertices placed round-robin on two nodes
+    let nodes = NodeMap::from_nodes(vec![0, 1, 0, 1]);
+    let mut graph = CommGraph::new(4);
+    graph
+        .add_edge(0, 1, 10)
+        .add_edge(2, 3, 10)
+        .add_edge(1, 2, 1);
+    let reordering = Reordering::new(&nodes, &graph);
+    assert_eq!(21, reordering.initial_edge_cut());
+    assert_eq!(1, reordering.edge_cut());
+    assert_eq!(
+        vec![0, 2, 1, 3],
+        (0..4).map(|p| reordering.vertex_of(p)).collect::<Vec<_>>()
+    );
+    assert_eq!(2, reordering.process_of(1));
+    assert_eq!(vec![(2, 10)], reordering.neighbor_processes(&graph, 0));
+    assert_eq!(
+        vec![(0, 10), (1, 1)],
+        reordering.neighbor_processes(&graph, 2)
+    );
+
+    // Reorder a periodic 2-d grid for the actual placement of the processes
+    let nodes = NodeMap::gather(&world);
+    assert_eq!(world.size() as usize, nodes.num_ranks());
+    let node_size = world.split_shared(world.rank()).size();
+    assert_eq!(
+        node_size as usize,
+        nodes.ranks_on(nodes.node_of(world.rank())).len()
+    );
+
+    let mut dims = [0, 0];
+    dims_create(world.size(), &mut dims);
+    let periods = [true, true];
+    let graph = CommGraph::cartesian(&dims, &periods, 1);
+    let reordering = Reordering::new(&nodes, &graph);
+    assert!(reordering.edge_cut() <= reordering.initial_edge_cut());
+
+    let reordered = reordering.apply(&world);
+    assert_eq!(reordering.vertex_of(world.rank()), reordered.rank());
+    let grid = reordered
+        .create_cartesian_communicator(&dims, &periods, false)
+        .expect("Every process is part of the grid");
+    assert_eq!(reordered.rank(), grid.rank());
+
+    // The same placement as a distributed graph on the original ranks
+    let dist_graph = reordering.create_dist_graph(&world, &graph);
+    assert_eq!(Topology::DistributedGraph, dist_graph.topology());
+    assert_eq!(world.rank(), dist_graph.rank());
+
+    if world.rank() == 0 {
+        println!(
+            "{} nodes, first is {}, edge-cut {} -> {}",
+            nodes.num_nodes(),
+            nodes.name(0),
+            reordering.initial_edge_cut(),
+            reordering.edge_cut()
+        );
+    }
+}
@@ -0,0 +1,73 @@
+#![deny(warnings)]
+
+use std::collections::HashMap;
+
+use mpi::traits::*;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+
+    let next_rank = (rank + 1) % size;
+    let previous_rank = (rank - 1 + size) % size;
+
+    let mut config: HashMap<String, Vec<u32>> = HashMap::new();
+    config.insert(format!("rank {}", rank), (0..rank as u32).collect());
+    config.insert("common".to_string(), vec![1, 2, 3]);
+
+    if rank % 2 == 0 {
+        world
+            .process_at_rank(next_rank)
+            .send_serialized(&config)
+            .unwrap();
+        let (received, _): (HashMap<String, Vec<u32>>, _) = world
+            .process_at_rank(previous_rank)
+            .receive_deserialized()
+            .unwrap();
+        check(&received, previous_rank);
+    } else {
+        let (received, status): (HashMap<String, Vec<u32>>, _) = world
+            .process_at_rank(previous_rank)
+            .receive_deserialized()
+            .unwrap();
+        assert_eq!(previous_rank, status.source_rank());
+        check(&received, previous_rank);
+        world
+            .process_at_rank(next_rank)
+            .send_serialized(&config)
+            .unwrap();
+    }
+
+    let root_process = world.process_at_rank(0);
+
+    let mut greeting = if rank == 0 {
+        String::from("hello from the root")
+    } else {
+        String::new()
+    };
+    root_process.broadcast_serialized(&mut greeting).unwrap();
+    assert_eq!("hello from the root", greeting);
+
+    let names = root_process
+        .gather_serialized(&format!("process {}", rank))
+        .unwrap();
+    if rank == 0 {
+        let names = names.unwrap();
+        assert_eq!(size as usize, names.len());
+        for (r, name) in names.iter().enumerate() {
+            assert_eq!(&format!("process {}", r), name);
+        }
+    } else {
+        assert!(names.is_none());
+    }
+}
+
+fn check(config: &HashMap<String, Vec<u32>>, sender: mpi::Rank) {
+    assert_eq!(Some(&vec![1, 2, 3]), config.get("common"));
+    assert_eq!(
+        Some(&(0..sender as u32).collect::<Vec<_>>()),
+        config.get(&format!("rank {}", sender))
+    );
+}
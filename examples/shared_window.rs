@@ -0,0 +1,42 @@
+#![deny(warnings)]
+
+use mpi::{traits::*, window::SharedWindow};
+
+const TABLE_LEN: usize = 64;
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    let node = world.split_shared(world.rank());
+    let node_rank = node.rank();
+
+    // Only the first process on each node holds the lookup table
+    let local_len = if node_rank == 0 { TABLE_LEN } else { 0 };
+    let mut table = SharedWindow::<f64>::allocate(&node, local_len);
+    // The slice is dropped before anybody writes to the window
+    assert_eq!(local_len, unsafe { table.local() }.len());
+
+    if node_rank == 0 {
+        for (i, x) in table.local_mut().iter_mut().enumerate() {
+            *x = (i * i) as f64;
+        }
+    }
+    table.barrier();
+
+    // The table is only written before the barrier above
+    let shared = unsafe { table.shared_query(0) };
+    assert_eq!(TABLE_LEN, shared.len());
+    for (i, &x) in shared.iter().enumerate() {
+        assert_eq!((i * i) as f64, x);
+    }
+    table.barrier();
+
+    // Every process owns one slot of a second window and reads its neighbor's
+    let mut slots = SharedWindow::<i32>::allocate(&node, 1);
+    slots.local_mut()[0] = node_rank;
+    slots.barrier();
+
+    let neighbor = (node_rank + 1) % node.size();
+    assert_eq!(&[neighbor], unsafe { slots.shared_query(neighbor) });
+}
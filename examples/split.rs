@@ -61,6 +61,15 @@ fn main() {
             let no_comm = world.split_by_subgroup(&odd_group);
             assert!(no_comm.is_none());
         }
+
+        // Only the odd processes take part in creating their communicator.
+        if odd_group.rank().is_some() {
+            let odd_comm = world.split_by_subgroup_with_tag(&odd_group, 7).unwrap();
+            assert_eq!(
+                GroupRelation::Identical,
+                odd_comm.group().compare(&odd_group)
+            );
+        }
     }
 
     let oddness_comm = world.split_by_color(Color::with_value(world.rank() % 2));
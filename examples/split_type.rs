@@ -0,0 +1,58 @@
+#![deny(warnings)]
+
+use mpi::{
+    info::Info,
+    topology::{OpenMpiSplitType, SplitType},
+    traits::*,
+};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+
+    let mut info = Info::new();
+    assert!(info.is_empty());
+    info.set("rsmpi_hint", "value").unwrap();
+    assert_eq!(Some("value".to_string()), info.get("rsmpi_hint").unwrap());
+    assert_eq!(vec!["rsmpi_hint".to_string()], info.keys());
+    assert!(info.clone().remove("rsmpi_hint").unwrap());
+    assert_eq!(1, info.len());
+    assert!(!info.remove("rsmpi_missing").unwrap());
+
+    let node = world
+        .split_by_type(SplitType::Shared, world.rank(), &Info::new())
+        .unwrap()
+        .expect("Every process is part of a shared memory communicator");
+    assert_eq!(node.size(), world.split_shared(world.rank()).size());
+
+    let numa = SplitType::HwGuided("NUMANode".to_string());
+    if numa.is_available() {
+        if let Some(numa_comm) = node.split_by_type(numa, node.rank(), &info).unwrap() {
+            assert!(numa_comm.size() <= node.size());
+        }
+    }
+
+    if SplitType::HwUnguided.is_available() {
+        if let Some(subset) = node
+            .split_by_type(SplitType::HwUnguided, node.rank(), &Info::new())
+            .unwrap()
+        {
+            assert!(subset.size() < node.size());
+        }
+    }
+
+    let socket = SplitType::OpenMpi(OpenMpiSplitType::Socket);
+    let socket_comm = world
+        .split_by_type(socket.clone(), world.rank(), &Info::new())
+        .unwrap();
+    if !socket.is_available() {
+        assert!(socket_comm.is_none());
+    }
+
+    println!(
+        "Rank {} of {} shares a node with {} processes.",
+        world.rank(),
+        world.size(),
+        node.size()
+    );
+}
@@ -0,0 +1,63 @@
+#![deny(warnings)]
+
+use std::thread;
+
+use mpi::{
+    threading::{Funneled, Multiple, Serialized, Single},
+    topology::SimpleCommunicator,
+    traits::*,
+    Threading,
+};
+
+fn main() {
+    let (universe, threading) = mpi::initialize_with_threading(Threading::Multiple).unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+    let next_rank = (rank + 1) % size;
+    let previous_rank = (rank + size - 1) % size;
+
+    assert!(universe.token::<Single>().is_some());
+    assert_eq!(
+        threading >= Threading::Funneled,
+        universe.token::<Funneled>().is_some()
+    );
+    assert_eq!(
+        threading == Threading::Multiple,
+        universe.token::<Multiple>().is_some()
+    );
+
+    if let Some(token) = universe.token::<Multiple>() {
+        // Any thread may use a shared communicator.
+        let shared = token.share(universe.world());
+        let received = thread::scope(|scope| {
+            let receiver = scope.spawn(|| {
+                shared
+                    .process_at_rank(previous_rank)
+                    .receive_with_tag::<i32>(1)
+                    .0
+            });
+            shared.process_at_rank(next_rank).send_with_tag(&rank, 1);
+            receiver.join().unwrap()
+        });
+        assert_eq!(previous_rank, received);
+    }
+
+    if let Some(token) = universe.token::<Serialized>() {
+        // One thread at a time may use a serialized communicator.
+        let serialized = token.serialize(universe.world());
+        let sum = thread::scope(|scope| {
+            scope
+                .spawn(|| serialized.lock().all_reduce(rank, SystemOperation::sum()))
+                .join()
+                .unwrap()
+        });
+        assert_eq!((0..size).sum::<i32>(), sum);
+        serialized.into_inner().barrier();
+    } else if cfg!(debug_assertions) {
+        // Only the thread that initialized MPI may make calls.
+        let result = thread::spawn(|| SimpleCommunicator::world().barrier()).join();
+        assert!(result.is_err());
+        world.barrier();
+    }
+}
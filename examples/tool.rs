@@ -0,0 +1,103 @@
+#![deny(warnings)]
+
+#[cfg(msmpi)]
+fn main() {
+    // MS-MPI does not implement the tool information interface.
+}
+
+#[cfg(not(msmpi))]
+fn main() {
+    use mpi::{
+        tool::{Binding, PerformanceClass, Scope, ToolError},
+        traits::*,
+        MpiError,
+    };
+
+    // The tool information interface can be used before MPI is initialized.
+    let tool = mpi::tool::initialize().unwrap();
+
+    let cvars = tool.control_variables().unwrap();
+    assert_eq!(tool.num_control_variables(), cvars.len());
+    for info in &cvars {
+        if info.binding() != Binding::NoObject {
+            continue;
+        }
+        if info.holds::<i32>() {
+            let mut cvar = tool.control_variable::<i32>(info).unwrap();
+            assert!(tool.control_variable::<f64>(info).is_err());
+            let value = cvar.read_all().unwrap();
+            assert_eq!(cvar.count(), value.len());
+            if info.scope() == Scope::Local {
+                // Writing back the current value must not change anything, but the library may
+                // refuse to set the variable at this point.
+                match cvar.write(&value) {
+                    Ok(())
+                    | Err(MpiError::Tool(ToolError::SetNotNow))
+                    | Err(MpiError::Tool(ToolError::SetNever)) => {}
+                    Err(e) => panic!("Failed to write {}: {}", info.name(), e),
+                }
+                assert_eq!(value, cvar.read_all().unwrap());
+            }
+        } else if info.holds::<u8>() {
+            let cvar = tool.control_variable::<u8>(info).unwrap();
+            cvar.read_string().unwrap();
+        }
+    }
+    if let Some(first) = cvars.first() {
+        let found = tool.find_control_variable(first.name()).unwrap().unwrap();
+        assert_eq!(first.index(), found.index());
+    }
+
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+
+    // Variables can only be bound to the kind of object they are defined for.
+    if let Some(info) = cvars
+        .iter()
+        .find(|info| info.binding() == Binding::NoObject)
+    {
+        assert!(matches!(
+            tool.control_variable_for::<i32, _>(info, &world),
+            Err(MpiError::Tool(ToolError::InvalidHandle))
+        ));
+    }
+
+    let pvars = tool.performance_variables().unwrap();
+    let session = tool.session().unwrap();
+    let mut counters: Vec<_> = pvars
+        .iter()
+        .filter(|info| {
+            info.binding() == Binding::NoObject
+                && info.class() == PerformanceClass::Counter
+                && info.holds::<u64>()
+        })
+        .filter_map(|info| session.variable::<u64>(info).ok())
+        .collect();
+    for counter in &mut counters {
+        counter.start().unwrap();
+    }
+
+    let msg = vec![rank; 64];
+    let mut received = vec![0; 64];
+    world.process_at_rank((rank + 1) % size).send(&msg[..]);
+    world
+        .process_at_rank((rank + size - 1) % size)
+        .receive_into(&mut received[..]);
+    assert_eq!(vec![(rank + size - 1) % size; 64], received);
+
+    for counter in &mut counters {
+        counter.stop().unwrap();
+        assert_eq!(counter.count(), counter.read_all().unwrap().len());
+    }
+
+    if rank == 0 {
+        println!(
+            "{} control variables, {} performance variables, {} counters read",
+            cvars.len(),
+            pvars.len(),
+            counters.len()
+        );
+    }
+}
@@ -0,0 +1,126 @@
+#![deny(warnings)]
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use mpi::traits::*;
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Metadata, Subscriber,
+};
+
+/// The fields recorded on a span with target `mpi`
+#[derive(Default, Debug, Clone)]
+struct Fields {
+    routine: String,
+    rank: Option<i64>,
+    peer: Option<i64>,
+    tag: Option<i64>,
+    datatype: Option<String>,
+    bytes: Option<u64>,
+}
+
+impl Visit for Fields {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        match field.name() {
+            "rank" => self.rank = Some(value),
+            "peer" => self.peer = Some(value),
+            "tag" => self.tag = Some(value),
+            _ => {}
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "bytes" {
+            self.bytes = Some(value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "routine" => self.routine = value.to_string(),
+            "datatype" => self.datatype = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+}
+
+/// Collects the spans emitted by the `mpi` crate
+#[derive(Default, Clone)]
+struct Collector {
+    spans: Arc<Mutex<Vec<Fields>>>,
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target() == "mpi"
+    }
+
+    fn new_span(&self, attributes: &span::Attributes<'_>) -> span::Id {
+        let mut fields = Fields::default();
+        attributes.record(&mut fields);
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(fields);
+        span::Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &span::Id, values: &span::Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        values.record(&mut spans[span.into_u64() as usize - 1]);
+    }
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+}
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let world = universe.world();
+    let rank = world.rank();
+    let size = world.size();
+    let next = world.process_at_rank((rank + 1) % size);
+    let previous = world.process_at_rank((rank + size - 1) % size);
+
+    let collector = Collector::default();
+    let msg = [rank as u64; 4];
+    let mut received = [0u64; 4];
+    let sum = tracing::subscriber::with_default(collector.clone(), || {
+        mpi::request::scope(|scope| {
+            let sreq = next.immediate_send_with_tag(scope, &msg[..], 7);
+            previous.receive_into_with_tag(&mut received[..], 7);
+            sreq.wait();
+        });
+        world.all_reduce(rank, SystemOperation::sum())
+    });
+    assert_eq!((0..size).sum::<i32>(), sum);
+    assert_eq!([previous.rank() as u64; 4], received);
+
+    let spans = collector.spans.lock().unwrap().clone();
+    let routines: Vec<_> = spans.iter().map(|s| s.routine.as_str()).collect();
+    assert_eq!(
+        vec!["MPI_Isend", "MPI_Recv", "MPI_Wait", "MPI_Allreduce"],
+        routines
+    );
+
+    let send = &spans[0];
+    assert_eq!(Some(rank as i64), send.rank);
+    assert_eq!(Some(next.rank() as i64), send.peer);
+    assert_eq!(Some(7), send.tag);
+    assert_eq!(Some(std::mem::size_of_val(&msg) as u64), send.bytes);
+    assert!(send.datatype.is_some());
+    assert_eq!(Some(previous.rank() as i64), spans[1].peer);
+    assert_eq!(None, spans[2].bytes);
+
+    // Without an interested subscriber, no spans are recorded.
+    world.barrier();
+    assert_eq!(spans.len(), collector.spans.lock().unwrap().len());
+}
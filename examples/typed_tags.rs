@@ -0,0 +1,60 @@
+#![deny(warnings)]
+
+use mpi::{
+    request::WaitGuard,
+    tag::{TagSpace, TypedTag},
+    traits::*,
+};
+
+fn main() {
+    let universe = mpi::initialize().unwrap();
+    let mut world = universe.world();
+    let size = world.size();
+    let rank = world.rank();
+    let next_rank = (rank + 1) % size;
+    let previous_rank = (rank + size - 1) % size;
+
+    // Every process allocates in the same order, so they agree on the tags.
+    TagSpace::attach(&mut world);
+    let space = world.get_attr::<TagSpace>().unwrap();
+    let before = space.remaining();
+    let ranks: TypedTag<i32> = space.tag().unwrap();
+    let values = space.tags::<[f64]>(3).unwrap();
+    assert_eq!(before - 4, space.remaining());
+    assert!(ranks.tag() <= universe.tag_upper_bound());
+    assert!(values.iter().all(|tag| tag.tag() != ranks.tag()));
+    assert!(space.tags::<u8>(space.remaining() + 1).is_none());
+    // The default tag 0 and other small tags are never handed out.
+    assert!(values
+        .iter()
+        .all(|tag| tag.tag() >= TagSpace::DEFAULT_FLOOR));
+
+    // Attaching again keeps the tags handed out so far.
+    TagSpace::attach(&mut world);
+    assert_eq!(
+        before - 4,
+        world.get_attr::<TagSpace>().unwrap().remaining()
+    );
+
+    let next_process = world.process_at_rank(next_rank);
+    let previous_process = world.process_at_rank(previous_rank);
+
+    mpi::request::scope(|scope| {
+        let _sreq =
+            WaitGuard::from(next_process.immediate_send_with_tag(scope, &rank, ranks.tag()));
+        // The type of the message follows from the tag.
+        let (received, status) = previous_process.receive_with_tag(ranks);
+        assert_eq!(previous_rank, received);
+        assert_eq!(ranks.tag(), status.tag());
+    });
+
+    for (i, tag) in values.iter().enumerate() {
+        let data = vec![f64::from(rank); i + 1];
+        mpi::request::scope(|scope| {
+            let _sreq =
+                WaitGuard::from(next_process.immediate_send_with_tag(scope, &data[..], tag.tag()));
+            let (received, _) = previous_process.receive_vec_with_tag(tag);
+            assert_eq!(vec![f64::from(previous_rank); i + 1], received);
+        });
+    }
+}
@@ -18,14 +18,78 @@ const MPI_Datatype RSMPI_UINT64_T = MPI_UINT64_T;
 const MPI_Datatype RSMPI_FLOAT_COMPLEX = MPI_C_FLOAT_COMPLEX;
 const MPI_Datatype RSMPI_DOUBLE_COMPLEX = MPI_C_DOUBLE_COMPLEX;
 
+const MPI_Datatype RSMPI_FLOAT_INT = MPI_FLOAT_INT;
+const MPI_Datatype RSMPI_DOUBLE_INT = MPI_DOUBLE_INT;
+const MPI_Datatype RSMPI_SHORT_INT = MPI_SHORT_INT;
+const MPI_Datatype RSMPI_2INT = MPI_2INT;
+
+const MPI_Datatype RSMPI_CHAR = MPI_CHAR;
+const MPI_Datatype RSMPI_INT = MPI_INT;
+const MPI_Datatype RSMPI_UNSIGNED = MPI_UNSIGNED;
+const MPI_Datatype RSMPI_UNSIGNED_LONG = MPI_UNSIGNED_LONG;
+const MPI_Datatype RSMPI_UNSIGNED_LONG_LONG = MPI_UNSIGNED_LONG_LONG;
+#if MPI_VERSION >= 3
+const MPI_Datatype RSMPI_COUNT = MPI_COUNT;
+#else
+const MPI_Datatype RSMPI_COUNT = MPI_DATATYPE_NULL;
+#endif
+
 const MPI_Datatype RSMPI_DATATYPE_NULL = MPI_DATATYPE_NULL;
 
+const int RSMPI_ORDER_C = MPI_ORDER_C;
+const int RSMPI_ORDER_FORTRAN = MPI_ORDER_FORTRAN;
+
 const MPI_Comm RSMPI_COMM_WORLD = MPI_COMM_WORLD;
 const MPI_Comm RSMPI_COMM_NULL = MPI_COMM_NULL;
 const MPI_Comm RSMPI_COMM_SELF = MPI_COMM_SELF;
 
 const int RSMPI_COMM_TYPE_SHARED = MPI_COMM_TYPE_SHARED;
 
+/* Split types introduced in MPI 4.0 and 4.1 map to MPI_UNDEFINED where the library predates them,
+   which makes MPI_Comm_split_type() return MPI_COMM_NULL. Open MPI 5 provides the MPI 4.0 types
+   while still reporting MPI 3.1. */
+#if MPI_VERSION >= 4 || defined(MPI_COMM_TYPE_HW_GUIDED) || \
+    (defined(OPEN_MPI) && OMPI_MAJOR_VERSION >= 5)
+const int RSMPI_COMM_TYPE_HW_GUIDED = MPI_COMM_TYPE_HW_GUIDED;
+const int RSMPI_COMM_TYPE_HW_UNGUIDED = MPI_COMM_TYPE_HW_UNGUIDED;
+#else
+const int RSMPI_COMM_TYPE_HW_GUIDED = MPI_UNDEFINED;
+const int RSMPI_COMM_TYPE_HW_UNGUIDED = MPI_UNDEFINED;
+#endif
+
+#if MPI_VERSION > 4 || (MPI_VERSION == 4 && MPI_SUBVERSION >= 1) || \
+    defined(MPI_COMM_TYPE_RESOURCE_GUIDED)
+const int RSMPI_COMM_TYPE_RESOURCE_GUIDED = MPI_COMM_TYPE_RESOURCE_GUIDED;
+#else
+const int RSMPI_COMM_TYPE_RESOURCE_GUIDED = MPI_UNDEFINED;
+#endif
+
+#ifdef OPEN_MPI
+const int RSMPI_OMPI_COMM_TYPE_HWTHREAD = OMPI_COMM_TYPE_HWTHREAD;
+const int RSMPI_OMPI_COMM_TYPE_CORE = OMPI_COMM_TYPE_CORE;
+const int RSMPI_OMPI_COMM_TYPE_L1CACHE = OMPI_COMM_TYPE_L1CACHE;
+const int RSMPI_OMPI_COMM_TYPE_L2CACHE = OMPI_COMM_TYPE_L2CACHE;
+const int RSMPI_OMPI_COMM_TYPE_L3CACHE = OMPI_COMM_TYPE_L3CACHE;
+const int RSMPI_OMPI_COMM_TYPE_SOCKET = OMPI_COMM_TYPE_SOCKET;
+const int RSMPI_OMPI_COMM_TYPE_NUMA = OMPI_COMM_TYPE_NUMA;
+const int RSMPI_OMPI_COMM_TYPE_BOARD = OMPI_COMM_TYPE_BOARD;
+const int RSMPI_OMPI_COMM_TYPE_HOST = OMPI_COMM_TYPE_HOST;
+const int RSMPI_OMPI_COMM_TYPE_CU = OMPI_COMM_TYPE_CU;
+const int RSMPI_OMPI_COMM_TYPE_CLUSTER = OMPI_COMM_TYPE_CLUSTER;
+#else
+const int RSMPI_OMPI_COMM_TYPE_HWTHREAD = MPI_UNDEFINED;
+const int RSMPI_OMPI_COMM_TYPE_CORE = MPI_UNDEFINED;
+const int RSMPI_OMPI_COMM_TYPE_L1CACHE = MPI_UNDEFINED;
+const int RSMPI_OMPI_COMM_TYPE_L2CACHE = MPI_UNDEFINED;
+const int RSMPI_OMPI_COMM_TYPE_L3CACHE = MPI_UNDEFINED;
+const int RSMPI_OMPI_COMM_TYPE_SOCKET = MPI_UNDEFINED;
+const int RSMPI_OMPI_COMM_TYPE_NUMA = MPI_UNDEFINED;
+const int RSMPI_OMPI_COMM_TYPE_BOARD = MPI_UNDEFINED;
+const int RSMPI_OMPI_COMM_TYPE_HOST = MPI_UNDEFINED;
+const int RSMPI_OMPI_COMM_TYPE_CU = MPI_UNDEFINED;
+const int RSMPI_OMPI_COMM_TYPE_CLUSTER = MPI_UNDEFINED;
+#endif
+
 const MPI_Group RSMPI_GROUP_EMPTY = MPI_GROUP_EMPTY;
 const MPI_Group RSMPI_GROUP_NULL = MPI_GROUP_NULL;
 const int RSMPI_UNDEFINED = MPI_UNDEFINED;
@@ -58,6 +122,7 @@ const int RSMPI_DIST_GRAPH = MPI_DIST_GRAPH;
 
 const int RSMPI_MAX_LIBRARY_VERSION_STRING = MPI_MAX_LIBRARY_VERSION_STRING;
 const int RSMPI_MAX_PROCESSOR_NAME = MPI_MAX_PROCESSOR_NAME;
+const int RSMPI_MAX_INFO_KEY = MPI_MAX_INFO_KEY;
 
 const MPI_Op RSMPI_MAX = MPI_MAX;
 const MPI_Op RSMPI_MIN = MPI_MIN;
@@ -69,6 +134,11 @@ const MPI_Op RSMPI_LOR = MPI_LOR;
 const MPI_Op RSMPI_BOR = MPI_BOR;
 const MPI_Op RSMPI_LXOR = MPI_LXOR;
 const MPI_Op RSMPI_BXOR = MPI_BXOR;
+const MPI_Op RSMPI_MINLOC = MPI_MINLOC;
+const MPI_Op RSMPI_MAXLOC = MPI_MAXLOC;
+const MPI_Op RSMPI_REPLACE = MPI_REPLACE;
+const MPI_Op RSMPI_NO_OP = MPI_NO_OP;
+const MPI_Op RSMPI_OP_NULL = MPI_OP_NULL;
 
 const MPI_Errhandler RSMPI_ERRORS_ARE_FATAL = MPI_ERRORS_ARE_FATAL;
 const MPI_Errhandler RSMPI_ERRORS_RETURN = MPI_ERRORS_RETURN;
@@ -79,6 +149,115 @@ const MPI_Info RSMPI_INFO_NULL = MPI_INFO_NULL;
 
 const MPI_Win RSMPI_WIN_NULL = MPI_WIN_NULL;
 
+/* The tool information interface (MPI_T) was introduced in MPI 3.0 and is not part of MS-MPI. */
+#if MPI_VERSION >= 3 && !defined(MSMPI_VER)
+const int RSMPI_T_VERBOSITY_USER_BASIC = MPI_T_VERBOSITY_USER_BASIC;
+const int RSMPI_T_VERBOSITY_USER_DETAIL = MPI_T_VERBOSITY_USER_DETAIL;
+const int RSMPI_T_VERBOSITY_USER_ALL = MPI_T_VERBOSITY_USER_ALL;
+const int RSMPI_T_VERBOSITY_TUNER_BASIC = MPI_T_VERBOSITY_TUNER_BASIC;
+const int RSMPI_T_VERBOSITY_TUNER_DETAIL = MPI_T_VERBOSITY_TUNER_DETAIL;
+const int RSMPI_T_VERBOSITY_TUNER_ALL = MPI_T_VERBOSITY_TUNER_ALL;
+const int RSMPI_T_VERBOSITY_MPIDEV_BASIC = MPI_T_VERBOSITY_MPIDEV_BASIC;
+const int RSMPI_T_VERBOSITY_MPIDEV_DETAIL = MPI_T_VERBOSITY_MPIDEV_DETAIL;
+const int RSMPI_T_VERBOSITY_MPIDEV_ALL = MPI_T_VERBOSITY_MPIDEV_ALL;
+
+const int RSMPI_T_BIND_NO_OBJECT = MPI_T_BIND_NO_OBJECT;
+const int RSMPI_T_BIND_MPI_COMM = MPI_T_BIND_MPI_COMM;
+const int RSMPI_T_BIND_MPI_DATATYPE = MPI_T_BIND_MPI_DATATYPE;
+const int RSMPI_T_BIND_MPI_ERRHANDLER = MPI_T_BIND_MPI_ERRHANDLER;
+const int RSMPI_T_BIND_MPI_FILE = MPI_T_BIND_MPI_FILE;
+const int RSMPI_T_BIND_MPI_GROUP = MPI_T_BIND_MPI_GROUP;
+const int RSMPI_T_BIND_MPI_OP = MPI_T_BIND_MPI_OP;
+const int RSMPI_T_BIND_MPI_REQUEST = MPI_T_BIND_MPI_REQUEST;
+const int RSMPI_T_BIND_MPI_WIN = MPI_T_BIND_MPI_WIN;
+const int RSMPI_T_BIND_MPI_MESSAGE = MPI_T_BIND_MPI_MESSAGE;
+const int RSMPI_T_BIND_MPI_INFO = MPI_T_BIND_MPI_INFO;
+
+const int RSMPI_T_SCOPE_CONSTANT = MPI_T_SCOPE_CONSTANT;
+const int RSMPI_T_SCOPE_READONLY = MPI_T_SCOPE_READONLY;
+const int RSMPI_T_SCOPE_LOCAL = MPI_T_SCOPE_LOCAL;
+const int RSMPI_T_SCOPE_GROUP = MPI_T_SCOPE_GROUP;
+const int RSMPI_T_SCOPE_GROUP_EQ = MPI_T_SCOPE_GROUP_EQ;
+const int RSMPI_T_SCOPE_ALL = MPI_T_SCOPE_ALL;
+const int RSMPI_T_SCOPE_ALL_EQ = MPI_T_SCOPE_ALL_EQ;
+
+const int RSMPI_T_PVAR_CLASS_STATE = MPI_T_PVAR_CLASS_STATE;
+const int RSMPI_T_PVAR_CLASS_LEVEL = MPI_T_PVAR_CLASS_LEVEL;
+const int RSMPI_T_PVAR_CLASS_SIZE = MPI_T_PVAR_CLASS_SIZE;
+const int RSMPI_T_PVAR_CLASS_PERCENTAGE = MPI_T_PVAR_CLASS_PERCENTAGE;
+const int RSMPI_T_PVAR_CLASS_HIGHWATERMARK = MPI_T_PVAR_CLASS_HIGHWATERMARK;
+const int RSMPI_T_PVAR_CLASS_LOWWATERMARK = MPI_T_PVAR_CLASS_LOWWATERMARK;
+const int RSMPI_T_PVAR_CLASS_COUNTER = MPI_T_PVAR_CLASS_COUNTER;
+const int RSMPI_T_PVAR_CLASS_AGGREGATE = MPI_T_PVAR_CLASS_AGGREGATE;
+const int RSMPI_T_PVAR_CLASS_TIMER = MPI_T_PVAR_CLASS_TIMER;
+const int RSMPI_T_PVAR_CLASS_GENERIC = MPI_T_PVAR_CLASS_GENERIC;
+
+const int RSMPI_T_ERR_MEMORY = MPI_T_ERR_MEMORY;
+const int RSMPI_T_ERR_NOT_INITIALIZED = MPI_T_ERR_NOT_INITIALIZED;
+const int RSMPI_T_ERR_CANNOT_INIT = MPI_T_ERR_CANNOT_INIT;
+const int RSMPI_T_ERR_INVALID_INDEX = MPI_T_ERR_INVALID_INDEX;
+const int RSMPI_T_ERR_INVALID_HANDLE = MPI_T_ERR_INVALID_HANDLE;
+const int RSMPI_T_ERR_OUT_OF_HANDLES = MPI_T_ERR_OUT_OF_HANDLES;
+const int RSMPI_T_ERR_OUT_OF_SESSIONS = MPI_T_ERR_OUT_OF_SESSIONS;
+const int RSMPI_T_ERR_INVALID_SESSION = MPI_T_ERR_INVALID_SESSION;
+const int RSMPI_T_ERR_CVAR_SET_NOT_NOW = MPI_T_ERR_CVAR_SET_NOT_NOW;
+const int RSMPI_T_ERR_CVAR_SET_NEVER = MPI_T_ERR_CVAR_SET_NEVER;
+const int RSMPI_T_ERR_PVAR_NO_STARTSTOP = MPI_T_ERR_PVAR_NO_STARTSTOP;
+const int RSMPI_T_ERR_PVAR_NO_WRITE = MPI_T_ERR_PVAR_NO_WRITE;
+const int RSMPI_T_ERR_PVAR_NO_ATOMIC = MPI_T_ERR_PVAR_NO_ATOMIC;
+#else
+const int RSMPI_T_VERBOSITY_USER_BASIC = MPI_UNDEFINED;
+const int RSMPI_T_VERBOSITY_USER_DETAIL = MPI_UNDEFINED;
+const int RSMPI_T_VERBOSITY_USER_ALL = MPI_UNDEFINED;
+const int RSMPI_T_VERBOSITY_TUNER_BASIC = MPI_UNDEFINED;
+const int RSMPI_T_VERBOSITY_TUNER_DETAIL = MPI_UNDEFINED;
+const int RSMPI_T_VERBOSITY_TUNER_ALL = MPI_UNDEFINED;
+const int RSMPI_T_VERBOSITY_MPIDEV_BASIC = MPI_UNDEFINED;
+const int RSMPI_T_VERBOSITY_MPIDEV_DETAIL = MPI_UNDEFINED;
+const int RSMPI_T_VERBOSITY_MPIDEV_ALL = MPI_UNDEFINED;
+const int RSMPI_T_BIND_NO_OBJECT = MPI_UNDEFINED;
+const int RSMPI_T_BIND_MPI_COMM = MPI_UNDEFINED;
+const int RSMPI_T_BIND_MPI_DATATYPE = MPI_UNDEFINED;
+const int RSMPI_T_BIND_MPI_ERRHANDLER = MPI_UNDEFINED;
+const int RSMPI_T_BIND_MPI_FILE = MPI_UNDEFINED;
+const int RSMPI_T_BIND_MPI_GROUP = MPI_UNDEFINED;
+const int RSMPI_T_BIND_MPI_OP = MPI_UNDEFINED;
+const int RSMPI_T_BIND_MPI_REQUEST = MPI_UNDEFINED;
+const int RSMPI_T_BIND_MPI_WIN = MPI_UNDEFINED;
+const int RSMPI_T_BIND_MPI_MESSAGE = MPI_UNDEFINED;
+const int RSMPI_T_BIND_MPI_INFO = MPI_UNDEFINED;
+const int RSMPI_T_SCOPE_CONSTANT = MPI_UNDEFINED;
+const int RSMPI_T_SCOPE_READONLY = MPI_UNDEFINED;
+const int RSMPI_T_SCOPE_LOCAL = MPI_UNDEFINED;
+const int RSMPI_T_SCOPE_GROUP = MPI_UNDEFINED;
+const int RSMPI_T_SCOPE_GROUP_EQ = MPI_UNDEFINED;
+const int RSMPI_T_SCOPE_ALL = MPI_UNDEFINED;
+const int RSMPI_T_SCOPE_ALL_EQ = MPI_UNDEFINED;
+const int RSMPI_T_PVAR_CLASS_STATE = MPI_UNDEFINED;
+const int RSMPI_T_PVAR_CLASS_LEVEL = MPI_UNDEFINED;
+const int RSMPI_T_PVAR_CLASS_SIZE = MPI_UNDEFINED;
+const int RSMPI_T_PVAR_CLASS_PERCENTAGE = MPI_UNDEFINED;
+const int RSMPI_T_PVAR_CLASS_HIGHWATERMARK = MPI_UNDEFINED;
+const int RSMPI_T_PVAR_CLASS_LOWWATERMARK = MPI_UNDEFINED;
+const int RSMPI_T_PVAR_CLASS_COUNTER = MPI_UNDEFINED;
+const int RSMPI_T_PVAR_CLASS_AGGREGATE = MPI_UNDEFINED;
+const int RSMPI_T_PVAR_CLASS_TIMER = MPI_UNDEFINED;
+const int RSMPI_T_PVAR_CLASS_GENERIC = MPI_UNDEFINED;
+const int RSMPI_T_ERR_MEMORY = MPI_UNDEFINED;
+const int RSMPI_T_ERR_NOT_INITIALIZED = MPI_UNDEFINED;
+const int RSMPI_T_ERR_CANNOT_INIT = MPI_UNDEFINED;
+const int RSMPI_T_ERR_INVALID_INDEX = MPI_UNDEFINED;
+const int RSMPI_T_ERR_INVALID_HANDLE = MPI_UNDEFINED;
+const int RSMPI_T_ERR_OUT_OF_HANDLES = MPI_UNDEFINED;
+const int RSMPI_T_ERR_OUT_OF_SESSIONS = MPI_UNDEFINED;
+const int RSMPI_T_ERR_INVALID_SESSION = MPI_UNDEFINED;
+const int RSMPI_T_ERR_CVAR_SET_NOT_NOW = MPI_UNDEFINED;
+const int RSMPI_T_ERR_CVAR_SET_NEVER = MPI_UNDEFINED;
+const int RSMPI_T_ERR_PVAR_NO_STARTSTOP = MPI_UNDEFINED;
+const int RSMPI_T_ERR_PVAR_NO_WRITE = MPI_UNDEFINED;
+const int RSMPI_T_ERR_PVAR_NO_ATOMIC = MPI_UNDEFINED;
+#endif
+
 int* RSMPI_UNWEIGHTED() {
     return MPI_UNWEIGHTED;
 }
@@ -24,13 +24,43 @@ extern const MPI_Datatype RSMPI_UINT64_T;
 extern const MPI_Datatype RSMPI_FLOAT_COMPLEX;
 extern const MPI_Datatype RSMPI_DOUBLE_COMPLEX;
 
+extern const MPI_Datatype RSMPI_FLOAT_INT;
+extern const MPI_Datatype RSMPI_DOUBLE_INT;
+extern const MPI_Datatype RSMPI_SHORT_INT;
+extern const MPI_Datatype RSMPI_2INT;
+
+extern const MPI_Datatype RSMPI_CHAR;
+extern const MPI_Datatype RSMPI_INT;
+extern const MPI_Datatype RSMPI_UNSIGNED;
+extern const MPI_Datatype RSMPI_UNSIGNED_LONG;
+extern const MPI_Datatype RSMPI_UNSIGNED_LONG_LONG;
+extern const MPI_Datatype RSMPI_COUNT;
+
 extern const MPI_Datatype RSMPI_DATATYPE_NULL;
 
+extern const int RSMPI_ORDER_C;
+extern const int RSMPI_ORDER_FORTRAN;
+
 extern const MPI_Comm RSMPI_COMM_WORLD;
 extern const MPI_Comm RSMPI_COMM_NULL;
 extern const MPI_Comm RSMPI_COMM_SELF;
 
 extern const int RSMPI_COMM_TYPE_SHARED;
+extern const int RSMPI_COMM_TYPE_HW_GUIDED;
+extern const int RSMPI_COMM_TYPE_HW_UNGUIDED;
+extern const int RSMPI_COMM_TYPE_RESOURCE_GUIDED;
+
+extern const int RSMPI_OMPI_COMM_TYPE_HWTHREAD;
+extern const int RSMPI_OMPI_COMM_TYPE_CORE;
+extern const int RSMPI_OMPI_COMM_TYPE_L1CACHE;
+extern const int RSMPI_OMPI_COMM_TYPE_L2CACHE;
+extern const int RSMPI_OMPI_COMM_TYPE_L3CACHE;
+extern const int RSMPI_OMPI_COMM_TYPE_SOCKET;
+extern const int RSMPI_OMPI_COMM_TYPE_NUMA;
+extern const int RSMPI_OMPI_COMM_TYPE_BOARD;
+extern const int RSMPI_OMPI_COMM_TYPE_HOST;
+extern const int RSMPI_OMPI_COMM_TYPE_CU;
+extern const int RSMPI_OMPI_COMM_TYPE_CLUSTER;
 
 extern const MPI_Group RSMPI_GROUP_EMPTY;
 extern const MPI_Group RSMPI_GROUP_NULL;
@@ -64,6 +94,7 @@ extern const int RSMPI_DIST_GRAPH;
 
 extern const int RSMPI_MAX_LIBRARY_VERSION_STRING;
 extern const int RSMPI_MAX_PROCESSOR_NAME;
+extern const int RSMPI_MAX_INFO_KEY;
 
 extern const MPI_Op RSMPI_MAX;
 extern const MPI_Op RSMPI_MIN;
@@ -75,6 +106,11 @@ extern const MPI_Op RSMPI_LOR;
 extern const MPI_Op RSMPI_BOR;
 extern const MPI_Op RSMPI_LXOR;
 extern const MPI_Op RSMPI_BXOR;
+extern const MPI_Op RSMPI_MINLOC;
+extern const MPI_Op RSMPI_MAXLOC;
+extern const MPI_Op RSMPI_REPLACE;
+extern const MPI_Op RSMPI_NO_OP;
+extern const MPI_Op RSMPI_OP_NULL;
 
 extern const MPI_Errhandler RSMPI_ERRORS_ARE_FATAL;
 extern const MPI_Errhandler RSMPI_ERRORS_RETURN;
@@ -85,6 +121,61 @@ extern const MPI_Info RSMPI_INFO_NULL;
 
 extern const MPI_Win RSMPI_WIN_NULL;
 
+extern const int RSMPI_T_VERBOSITY_USER_BASIC;
+extern const int RSMPI_T_VERBOSITY_USER_DETAIL;
+extern const int RSMPI_T_VERBOSITY_USER_ALL;
+extern const int RSMPI_T_VERBOSITY_TUNER_BASIC;
+extern const int RSMPI_T_VERBOSITY_TUNER_DETAIL;
+extern const int RSMPI_T_VERBOSITY_TUNER_ALL;
+extern const int RSMPI_T_VERBOSITY_MPIDEV_BASIC;
+extern const int RSMPI_T_VERBOSITY_MPIDEV_DETAIL;
+extern const int RSMPI_T_VERBOSITY_MPIDEV_ALL;
+
+extern const int RSMPI_T_BIND_NO_OBJECT;
+extern const int RSMPI_T_BIND_MPI_COMM;
+extern const int RSMPI_T_BIND_MPI_DATATYPE;
+extern const int RSMPI_T_BIND_MPI_ERRHANDLER;
+extern const int RSMPI_T_BIND_MPI_FILE;
+extern const int RSMPI_T_BIND_MPI_GROUP;
+extern const int RSMPI_T_BIND_MPI_OP;
+extern const int RSMPI_T_BIND_MPI_REQUEST;
+extern const int RSMPI_T_BIND_MPI_WIN;
+extern const int RSMPI_T_BIND_MPI_MESSAGE;
+extern const int RSMPI_T_BIND_MPI_INFO;
+
+extern const int RSMPI_T_SCOPE_CONSTANT;
+extern const int RSMPI_T_SCOPE_READONLY;
+extern const int RSMPI_T_SCOPE_LOCAL;
+extern const int RSMPI_T_SCOPE_GROUP;
+extern const int RSMPI_T_SCOPE_GROUP_EQ;
+extern const int RSMPI_T_SCOPE_ALL;
+extern const int RSMPI_T_SCOPE_ALL_EQ;
+
+extern const int RSMPI_T_PVAR_CLASS_STATE;
+extern const int RSMPI_T_PVAR_CLASS_LEVEL;
+extern const int RSMPI_T_PVAR_CLASS_SIZE;
+extern const int RSMPI_T_PVAR_CLASS_PERCENTAGE;
+extern const int RSMPI_T_PVAR_CLASS_HIGHWATERMARK;
+extern const int RSMPI_T_PVAR_CLASS_LOWWATERMARK;
+extern const int RSMPI_T_PVAR_CLASS_COUNTER;
+extern const int RSMPI_T_PVAR_CLASS_AGGREGATE;
+extern const int RSMPI_T_PVAR_CLASS_TIMER;
+extern const int RSMPI_T_PVAR_CLASS_GENERIC;
+
+extern const int RSMPI_T_ERR_MEMORY;
+extern const int RSMPI_T_ERR_NOT_INITIALIZED;
+extern const int RSMPI_T_ERR_CANNOT_INIT;
+extern const int RSMPI_T_ERR_INVALID_INDEX;
+extern const int RSMPI_T_ERR_INVALID_HANDLE;
+extern const int RSMPI_T_ERR_OUT_OF_HANDLES;
+extern const int RSMPI_T_ERR_OUT_OF_SESSIONS;
+extern const int RSMPI_T_ERR_INVALID_SESSION;
+extern const int RSMPI_T_ERR_CVAR_SET_NOT_NOW;
+extern const int RSMPI_T_ERR_CVAR_SET_NEVER;
+extern const int RSMPI_T_ERR_PVAR_NO_STARTSTOP;
+extern const int RSMPI_T_ERR_PVAR_NO_WRITE;
+extern const int RSMPI_T_ERR_PVAR_NO_ATOMIC;
+
 int* RSMPI_UNWEIGHTED();
 
 double RSMPI_Wtime();
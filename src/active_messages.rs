@@ -0,0 +1,410 @@
+//! Active messages: remote calls of handlers registered by ID
+//!
+//! Every process registers the same handlers under the same `HandlerId`s and starts an
+//! `ActiveMessages` on a communicator. A process can then `send()` arguments to a handler on
+//! another process, or `call()` it and wait for the value the handler returns. Arguments and
+//! return values are encoded with the `DefaultEncoding`.
+//!
+//! Requests are served by a background thread if MPI provides `Threading::Multiple`. Otherwise,
+//! they are served whenever the process sends, waits for a reply or calls `poll()`, so a process
+//! that computes for a long time should poll now and then.
+//!
+//! `finish()` detects when all processes are done. Each request is sent in synchronous mode and
+//! the process only enters a nonblocking barrier once its requests have been received, so when
+//! the barrier completes, no request is in flight anymore. Until then, the process keeps serving
+//! requests of the others.
+//!
+//! This module is only available with the `serde` feature.
+//!
+//! # Examples
+//!
+//! See `examples/active_messages.rs`
+
+use std::{
+    any::Any,
+    cell::Cell,
+    collections::HashMap,
+    fmt,
+    panic::AssertUnwindSafe,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    thread::{self, JoinHandle},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    attribute::TagUpperBound,
+    collective::traits::*,
+    environment::catch_panics,
+    point_to_point::traits::*,
+    request,
+    serialize::{DefaultEncoding, Encoding},
+    threading::{Multiple, SharedCommunicator, Token},
+    topology::{traits::*, Rank, SimpleCommunicator},
+    MpiError, Tag,
+};
+
+/// Identifies a handler on all processes
+pub type HandlerId = u32;
+
+/// The tag of requests. Replies are tagged with a positive ID of the call.
+const REQUEST: Tag = 0;
+
+/// A handler decoding its arguments and encoding its return value
+type Handler = Box<dyn Fn(&[u8]) -> Result<Vec<u8>, MpiError> + Send + Sync>;
+
+/// The handler, the tag of the reply if one is expected, and the encoded arguments
+type Envelope = (HandlerId, Option<Tag>, Vec<u8>);
+
+/// The encoded return value of a handler, or why the call failed
+type Reply = Result<Vec<u8>, String>;
+
+/// Collects the handlers of an `ActiveMessages`
+///
+/// Created by `ActiveMessages::builder()`.
+#[derive(Default)]
+pub struct ActiveMessagesBuilder {
+    handlers: HashMap<HandlerId, Handler>,
+}
+
+impl ActiveMessagesBuilder {
+    /// Registers `handler` under `id`.
+    ///
+    /// # Panics
+    ///
+    /// If a handler has already been registered under `id`
+    pub fn handler<A, R, F>(mut self, id: HandlerId, handler: F) -> Self
+    where
+        A: DeserializeOwned,
+        R: Serialize,
+        F: Fn(A) -> R + Send + Sync + 'static,
+    {
+        let handler: Handler = Box::new(move |args| {
+            let args = DefaultEncoding::default().decode(args)?;
+            DefaultEncoding::default().encode(&handler(args))
+        });
+        let previous = self.handlers.insert(id, handler);
+        assert!(
+            previous.is_none(),
+            "Active message handler {} registered twice",
+            id
+        );
+        self
+    }
+
+    /// Starts serving requests on a duplicate of `comm`.
+    ///
+    /// This is a collective operation on `comm`.
+    pub fn start<C: ?Sized + Communicator>(self, comm: &C) -> ActiveMessages {
+        let comm = comm.duplicate();
+        let server = Server {
+            handlers: self.handlers,
+            error: Mutex::new(None),
+        };
+        let progress = match Token::<Multiple>::new() {
+            Some(token) => {
+                let shared = Arc::new((token.share(comm), server));
+                let stop = Arc::new(AtomicBool::new(false));
+                let thread = {
+                    let shared = Arc::clone(&shared);
+                    let stop = Arc::clone(&stop);
+                    thread::spawn(move || {
+                        let (comm, server) = &*shared;
+                        while !stop.load(Ordering::Acquire) {
+                            if !server.serve(comm) {
+                                thread::yield_now();
+                            }
+                        }
+                    })
+                };
+                Progress::Thread {
+                    shared,
+                    stop,
+                    thread: Some(thread),
+                }
+            }
+            None => Progress::Polling { comm, server },
+        };
+        let reply_tag_bound = SimpleCommunicator::world()
+            .get_attr::<TagUpperBound>()
+            .map(Tag::from)
+            .expect("MPI_TAG_UB is not attached to MPI_COMM_WORLD");
+        ActiveMessages {
+            progress,
+            last_reply_tag: Cell::new(REQUEST),
+            reply_tag_bound,
+        }
+    }
+}
+
+/// Serves requests to the registered handlers
+struct Server {
+    handlers: HashMap<HandlerId, Handler>,
+    /// The first error of a request that did not expect a reply
+    error: Mutex<Option<MpiError>>,
+}
+
+impl Server {
+    /// Serves a request if one has arrived and returns whether it did.
+    fn serve(&self, comm: &SimpleCommunicator) -> bool {
+        let Some(message) = comm.any_process().immediate_matched_probe_with_tag(REQUEST) else {
+            return false;
+        };
+        let (bytes, status) = message.matched_receive_vec::<u8>();
+        let envelope = DefaultEncoding::default().decode::<Envelope>(&bytes);
+        let (id, reply_tag, args) = match envelope {
+            Ok(envelope) => envelope,
+            Err(error) => {
+                self.record(error);
+                return true;
+            }
+        };
+        // A panicking handler must not take down the progress thread or leave the caller
+        // waiting for a reply, so the panic is reported like any other failure.
+        let reply: Reply = match self.handlers.get(&id) {
+            Some(handler) => match catch_panics(AssertUnwindSafe(|| handler(&args))) {
+                Ok(result) => result.map_err(|error| error.to_string()),
+                Err(payload) => Err(panic_reason(&*payload)),
+            },
+            None => Err(format!("No handler with ID {}", id)),
+        };
+        match reply_tag {
+            Some(tag) => match DefaultEncoding::default().encode(&reply) {
+                Ok(bytes) => comm
+                    .process_at_rank(status.source_rank())
+                    .send_with_tag(&bytes[..], tag),
+                Err(error) => self.record(error),
+            },
+            None => {
+                if let Err(reason) = reply {
+                    self.record(MpiError::ActiveMessage {
+                        rank: comm.rank(),
+                        handler: id,
+                        reason,
+                    });
+                }
+            }
+        }
+        true
+    }
+
+    fn record(&self, error: MpiError) {
+        self.error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_or_insert(error);
+    }
+}
+
+/// Describes the panic of a handler from its payload.
+fn panic_reason(payload: &(dyn Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+    match message {
+        Some(message) => format!("Handler panicked: {}", message),
+        None => "Handler panicked".to_owned(),
+    }
+}
+
+/// Where requests are served
+enum Progress {
+    /// By a background thread
+    Thread {
+        shared: Arc<(SharedCommunicator<SimpleCommunicator>, Server)>,
+        stop: Arc<AtomicBool>,
+        thread: Option<JoinHandle<()>>,
+    },
+    /// By the calling thread, whenever it waits
+    Polling {
+        comm: SimpleCommunicator,
+        server: Server,
+    },
+}
+
+/// Sends requests to handlers on other processes and serves theirs
+///
+/// A handler that panics fails the request: `call()` returns an `MpiError::ActiveMessage` with
+/// the panic message, and for `send()` the error is reported by `finish()` on the process that ran
+/// the handler. Such a panic does not abort all processes even with `abort_on_panic()`; the
+/// panic hook only prints its message.
+///
+/// Dropping an `ActiveMessages` stops serving requests without waiting for the other processes,
+/// use `finish()` to shut down cleanly.
+pub struct ActiveMessages {
+    progress: Progress,
+    last_reply_tag: Cell<Tag>,
+    reply_tag_bound: Tag,
+}
+
+impl ActiveMessages {
+    /// Starts registering handlers.
+    pub fn builder() -> ActiveMessagesBuilder {
+        ActiveMessagesBuilder::default()
+    }
+
+    /// Whether requests are served by a background thread
+    pub fn has_progress_thread(&self) -> bool {
+        matches!(self.progress, Progress::Thread { .. })
+    }
+
+    /// Calls the handler `handler` on the process `rank` with `args`, without waiting for it to
+    /// return.
+    ///
+    /// Returns once the request has been received. Failures of the handler are reported by
+    /// `finish()` on the process `rank`.
+    pub fn send<A>(&self, rank: Rank, handler: HandlerId, args: &A) -> Result<(), MpiError>
+    where
+        A: ?Sized + Serialize,
+    {
+        self.request(rank, handler, None, args)
+    }
+
+    /// Calls the handler `handler` on the process `rank` with `args` and returns its return
+    /// value.
+    pub fn call<A, R>(&self, rank: Rank, handler: HandlerId, args: &A) -> Result<R, MpiError>
+    where
+        A: ?Sized + Serialize,
+        R: DeserializeOwned,
+    {
+        let tag = self.next_reply_tag();
+        self.request(rank, handler, Some(tag), args)?;
+        let process = self.comm().process_at_rank(rank);
+        let bytes = loop {
+            if let Some(message) = process.immediate_matched_probe_with_tag(tag) {
+                break message.matched_receive_vec::<u8>().0;
+            }
+            self.progress();
+        };
+        let reply: Reply = DefaultEncoding::default().decode(&bytes)?;
+        match reply {
+            Ok(value) => DefaultEncoding::default().decode(&value),
+            Err(reason) => Err(MpiError::ActiveMessage {
+                rank,
+                handler,
+                reason,
+            }),
+        }
+    }
+
+    /// Serves the requests that have arrived and returns how many there were.
+    ///
+    /// With a progress thread, requests are served in the background and this returns 0.
+    pub fn poll(&self) -> usize {
+        match &self.progress {
+            Progress::Thread { .. } => 0,
+            Progress::Polling { comm, server } => {
+                let mut served = 0;
+                while server.serve(comm) {
+                    served += 1;
+                }
+                served
+            }
+        }
+    }
+
+    /// Waits until all processes have finished and stops serving requests.
+    ///
+    /// This is a collective operation. Returns the first error of a request to this process that
+    /// did not expect a reply.
+    pub fn finish(mut self) -> Result<(), MpiError> {
+        let mut barrier = self.comm().immediate_barrier();
+        loop {
+            match barrier.test() {
+                Ok(_) => break,
+                Err(pending) => barrier = pending,
+            }
+            self.progress();
+        }
+        self.stop();
+        let error = match &self.progress {
+            Progress::Thread { shared, .. } => &shared.1.error,
+            Progress::Polling { server, .. } => &server.error,
+        };
+        match error.lock().unwrap_or_else(PoisonError::into_inner).take() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    fn comm(&self) -> &SimpleCommunicator {
+        match &self.progress {
+            Progress::Thread { shared, .. } => &*shared.0,
+            Progress::Polling { comm, .. } => comm,
+        }
+    }
+
+    fn request<A>(
+        &self,
+        rank: Rank,
+        handler: HandlerId,
+        reply_tag: Option<Tag>,
+        args: &A,
+    ) -> Result<(), MpiError>
+    where
+        A: ?Sized + Serialize,
+    {
+        let args = DefaultEncoding::default().encode(args)?;
+        let envelope: Envelope = (handler, reply_tag, args);
+        let bytes = DefaultEncoding::default().encode(&envelope)?;
+        let process = self.comm().process_at_rank(rank);
+        request::scope(|scope| {
+            let mut request =
+                process.immediate_synchronous_send_with_tag(scope, &bytes[..], REQUEST);
+            loop {
+                match request.test() {
+                    Ok(_) => break,
+                    Err(pending) => request = pending,
+                }
+                self.progress();
+            }
+        });
+        Ok(())
+    }
+
+    /// Serves requests while the calling thread waits.
+    fn progress(&self) {
+        self.poll();
+        thread::yield_now();
+    }
+
+    fn next_reply_tag(&self) -> Tag {
+        let last = self.last_reply_tag.get();
+        let tag = if last >= self.reply_tag_bound {
+            REQUEST + 1
+        } else {
+            last + 1
+        };
+        self.last_reply_tag.set(tag);
+        tag
+    }
+
+    fn stop(&mut self) {
+        if let Progress::Thread { stop, thread, .. } = &mut self.progress {
+            stop.store(true, Ordering::Release);
+            if let Some(thread) = thread.take() {
+                if thread.join().is_err() && !thread::panicking() {
+                    panic!("Active message progress thread panicked");
+                }
+            }
+        }
+    }
+}
+
+impl Drop for ActiveMessages {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl fmt::Debug for ActiveMessages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ActiveMessages")
+            .field("progress_thread", &self.has_progress_thread())
+            .finish()
+    }
+}
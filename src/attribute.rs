@@ -4,7 +4,7 @@ use std::{any::TypeId, collections::HashMap, ffi::c_void, os::raw::c_int, ptr, s
 
 use once_cell::sync::Lazy;
 
-use crate::{ffi, traits::AsRaw};
+use crate::{environment::IoProcess, ffi, traits::AsRaw, Error, Rank, Tag};
 
 /// Topology traits
 pub mod traits {
@@ -191,3 +191,99 @@ impl From<&AppNum> for isize {
         an.0 as isize
     }
 }
+
+/// For obtaining the largest usable tag value
+#[repr(C)]
+#[derive(Clone)]
+pub(crate) struct TagUpperBound(c_int);
+
+impl CommAttribute for TagUpperBound {
+    fn get_key() -> AttributeKey {
+        unsafe { AttributeKey::new_unchecked(ffi::MPI_TAG_UB as i32) }
+    }
+}
+
+impl From<&TagUpperBound> for Tag {
+    fn from(ub: &TagUpperBound) -> Self {
+        ub.0
+    }
+}
+
+/// For obtaining the rank of the host process, if any
+#[repr(C)]
+#[derive(Clone)]
+pub(crate) struct Host(c_int);
+
+impl CommAttribute for Host {
+    fn get_key() -> AttributeKey {
+        unsafe { AttributeKey::new_unchecked(ffi::MPI_HOST as i32) }
+    }
+}
+
+impl From<&Host> for Option<Rank> {
+    fn from(host: &Host) -> Self {
+        if host.0 == unsafe { ffi::RSMPI_PROC_NULL } {
+            None
+        } else {
+            Some(host.0)
+        }
+    }
+}
+
+/// For obtaining the rank of a process that can perform I/O
+#[repr(C)]
+#[derive(Clone)]
+pub(crate) struct IoRank(c_int);
+
+impl CommAttribute for IoRank {
+    fn get_key() -> AttributeKey {
+        unsafe { AttributeKey::new_unchecked(ffi::MPI_IO as i32) }
+    }
+}
+
+impl From<&IoRank> for IoProcess {
+    fn from(io: &IoRank) -> Self {
+        if io.0 == unsafe { ffi::RSMPI_PROC_NULL } {
+            IoProcess::None
+        } else if io.0 == unsafe { ffi::RSMPI_ANY_SOURCE } {
+            IoProcess::Any
+        } else {
+            IoProcess::Rank(io.0)
+        }
+    }
+}
+
+/// For obtaining whether clocks are synchronized across processes
+#[repr(C)]
+#[derive(Clone)]
+pub(crate) struct WtimeIsGlobal(c_int);
+
+impl CommAttribute for WtimeIsGlobal {
+    fn get_key() -> AttributeKey {
+        unsafe { AttributeKey::new_unchecked(ffi::MPI_WTIME_IS_GLOBAL as i32) }
+    }
+}
+
+impl From<&WtimeIsGlobal> for bool {
+    fn from(global: &WtimeIsGlobal) -> Self {
+        global.0 != 0
+    }
+}
+
+/// For obtaining the largest error code in use, including those added with
+/// `MPI_Add_error_code()`
+#[repr(C)]
+#[derive(Clone)]
+pub(crate) struct LastUsedCode(c_int);
+
+impl CommAttribute for LastUsedCode {
+    fn get_key() -> AttributeKey {
+        unsafe { AttributeKey::new_unchecked(ffi::MPI_LASTUSEDCODE as i32) }
+    }
+}
+
+impl From<&LastUsedCode> for Error {
+    fn from(code: &LastUsedCode) -> Self {
+        code.0
+    }
+}
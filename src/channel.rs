@@ -0,0 +1,389 @@
+//! Typed channels between two processes
+//!
+//! A `Sender<T>` and a `Receiver<T>` are bound to a process of a communicator and a tag. They
+//! mirror `std::sync::mpsc`: the receiving side returns values one at a time and reports the same
+//! error types, so code written against `mpsc` carries over with few changes.
+//!
+//! Every message carries one or more values of `T`, so a receiver learns the length of a message
+//! through a matched probe. A message without values closes the channel, it is sent by
+//! `Sender::close()` or, on a best-effort basis, when the `Sender` is dropped. `Select` waits for
+//! the first of several receivers that has a value.
+//!
+//! All processes that use a channel have to use the same tag for it, e.g. one allocated from a
+//! `TagSpace`.
+//!
+//! # Examples
+//!
+//! See `examples/channel.rs`
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    fmt,
+    marker::PhantomData,
+    slice,
+    sync::mpsc::{RecvError, RecvTimeoutError, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    datatype::traits::*,
+    environment::is_finalized,
+    point_to_point::{traits::*, Message, Status},
+    tag::AsTag,
+    topology::{traits::*, Process, Rank},
+    Tag,
+};
+
+/// Creates the sending and the receiving end of a channel to and from the process `peer` of
+/// `comm`.
+///
+/// The `peer` has to create its channel with the rank of this process and the same `tag`.
+pub fn channel<'a, T, C>(
+    comm: &'a C,
+    peer: Rank,
+    tag: impl AsTag<[T]>,
+) -> (Sender<'a, T>, Receiver<'a, T>)
+where
+    T: Equivalence,
+    C: ?Sized + Communicator,
+{
+    let process = comm.process_at_rank(peer);
+    let tag = tag.as_tag();
+    (Sender::new(process, tag), Receiver::new(process, tag))
+}
+
+/// The sending end of a channel
+///
+/// Values are sent with a standard mode send, so for large values `send()` may block until the
+/// receiver starts to receive them.
+///
+/// The channel is closed with `close()`. Dropping the sender closes it as well, except while the
+/// thread is panicking or after MPI has been finalized, when no message is sent and the receiver
+/// keeps waiting.
+pub struct Sender<'a, T: Equivalence> {
+    destination: Process<'a>,
+    tag: Tag,
+    closed: bool,
+    _value: PhantomData<fn(T)>,
+}
+
+impl<'a, T: Equivalence> Sender<'a, T> {
+    /// Sends values to `destination`, tagged `tag`.
+    pub fn new(destination: Process<'a>, tag: impl AsTag<[T]>) -> Self {
+        Sender {
+            destination,
+            tag: tag.as_tag(),
+            closed: false,
+            _value: PhantomData,
+        }
+    }
+
+    /// Sends `value`.
+    pub fn send(&self, value: T) {
+        self.send_all(slice::from_ref(&value));
+    }
+
+    /// Sends all of `values` in a single message. The receiver returns them one at a time.
+    pub fn send_all(&self, values: &[T]) {
+        if !values.is_empty() {
+            self.destination.send_with_tag(values, self.tag);
+        }
+    }
+
+    /// Closes the channel, the receiver returns the values sent so far and then reports that the
+    /// channel is closed.
+    pub fn close(mut self) {
+        self.send_close();
+    }
+
+    fn send_close(&mut self) {
+        let closed: &[T] = &[];
+        self.destination.send_with_tag(closed, self.tag);
+        self.closed = true;
+    }
+}
+
+impl<'a, T: Equivalence> Drop for Sender<'a, T> {
+    fn drop(&mut self) {
+        if !self.closed && !thread::panicking() && !is_finalized() {
+            self.send_close();
+        }
+    }
+}
+
+impl<'a, T: Equivalence> fmt::Debug for Sender<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender")
+            .field("destination", &self.destination.rank())
+            .field("tag", &self.tag)
+            .finish()
+    }
+}
+
+/// The receiving end of a channel
+pub struct Receiver<'a, T: Equivalence> {
+    source: Process<'a>,
+    tag: Tag,
+    /// Values received but not yet returned
+    pending: RefCell<VecDeque<T>>,
+    closed: Cell<bool>,
+}
+
+impl<'a, T: Equivalence> Receiver<'a, T> {
+    /// Receives values from `source`, tagged `tag`.
+    pub fn new(source: Process<'a>, tag: impl AsTag<[T]>) -> Self {
+        Receiver {
+            source,
+            tag: tag.as_tag(),
+            pending: RefCell::new(VecDeque::new()),
+            closed: Cell::new(false),
+        }
+    }
+
+    /// Blocks until a value arrives, returns `RecvError` once the channel has been closed and all
+    /// values have been returned.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        loop {
+            match self.take() {
+                Ok(value) => return Ok(value),
+                Err(TryRecvError::Disconnected) => return Err(RecvError),
+                Err(TryRecvError::Empty) => {
+                    self.fill(self.source.matched_probe_with_tag(self.tag));
+                }
+            }
+        }
+    }
+
+    /// Returns a value if one has arrived, without blocking.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        match self.take() {
+            Err(TryRecvError::Empty) if self.poll() => self.take(),
+            result => result,
+        }
+    }
+
+    /// Waits at most `timeout` for a value to arrive.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.try_recv() {
+                Ok(value) => return Ok(value),
+                Err(TryRecvError::Disconnected) => return Err(RecvTimeoutError::Disconnected),
+                Err(TryRecvError::Empty) if Instant::now() >= deadline => {
+                    return Err(RecvTimeoutError::Timeout)
+                }
+                Err(TryRecvError::Empty) => thread::yield_now(),
+            }
+        }
+    }
+
+    /// Returns an iterator that blocks for values until the channel is closed.
+    pub fn iter(&self) -> Iter<'_, 'a, T> {
+        Iter { receiver: self }
+    }
+
+    /// Returns an iterator over the values that have already arrived.
+    pub fn try_iter(&self) -> TryIter<'_, 'a, T> {
+        TryIter { receiver: self }
+    }
+
+    fn take(&self) -> Result<T, TryRecvError> {
+        match self.pending.borrow_mut().pop_front() {
+            Some(value) => Ok(value),
+            None if self.closed.get() => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    fn fill(&self, message: (Message, Status)) {
+        let (values, _) = message.matched_receive_vec::<T>();
+        if values.is_empty() {
+            self.closed.set(true);
+        }
+        self.pending.borrow_mut().extend(values);
+    }
+
+    /// Receives a message if one has arrived. Returns whether a value or the end of the channel
+    /// is available.
+    fn poll(&self) -> bool {
+        if !self.pending.borrow().is_empty() || self.closed.get() {
+            return true;
+        }
+        match self.source.immediate_matched_probe_with_tag(self.tag) {
+            Some(message) => {
+                self.fill(message);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<'a, T: Equivalence> fmt::Debug for Receiver<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("source", &self.source.rank())
+            .field("tag", &self.tag)
+            .field("pending", &self.pending.borrow().len())
+            .field("closed", &self.closed.get())
+            .finish()
+    }
+}
+
+impl<'r, 'a, T: Equivalence> IntoIterator for &'r Receiver<'a, T> {
+    type Item = T;
+    type IntoIter = Iter<'r, 'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Equivalence> IntoIterator for Receiver<'a, T> {
+    type Item = T;
+    type IntoIter = IntoIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { receiver: self }
+    }
+}
+
+/// Iterator returned by `Receiver::iter()`
+#[derive(Debug)]
+pub struct Iter<'r, 'a, T: Equivalence> {
+    receiver: &'r Receiver<'a, T>,
+}
+
+impl<'r, 'a, T: Equivalence> Iterator for Iter<'r, 'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.receiver.recv().ok()
+    }
+}
+
+/// Iterator returned by `Receiver::try_iter()`
+#[derive(Debug)]
+pub struct TryIter<'r, 'a, T: Equivalence> {
+    receiver: &'r Receiver<'a, T>,
+}
+
+impl<'r, 'a, T: Equivalence> Iterator for TryIter<'r, 'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+/// Iterator returned by `Receiver::into_iter()`
+#[derive(Debug)]
+pub struct IntoIter<'a, T: Equivalence> {
+    receiver: Receiver<'a, T>,
+}
+
+impl<'a, T: Equivalence> Iterator for IntoIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.receiver.recv().ok()
+    }
+}
+
+/// A receiver of any type, as seen by `Select`
+trait Poll {
+    fn poll(&self) -> bool;
+}
+
+impl<'a, T: Equivalence> Poll for Receiver<'a, T> {
+    fn poll(&self) -> bool {
+        Receiver::poll(self)
+    }
+}
+
+/// Waits for one of several receivers to have a value
+///
+/// Receivers are added with `recv()`, which returns their index. The `ready` methods return the
+/// index of a receiver on which `try_recv()` returns a value or reports the end of the channel.
+/// Receivers are polled with `MPI_Improbe()`, starting after the one returned last, so a busy
+/// receiver does not starve the others.
+#[derive(Default)]
+pub struct Select<'r> {
+    receivers: Vec<&'r dyn Poll>,
+    next: Cell<usize>,
+}
+
+impl<'r> Select<'r> {
+    /// An empty selection
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `receiver` to the selection and returns its index.
+    pub fn recv<'a: 'r, T: Equivalence + 'r>(&mut self, receiver: &'r Receiver<'a, T>) -> usize {
+        self.receivers.push(receiver);
+        self.receivers.len() - 1
+    }
+
+    /// Blocks until one of the receivers is ready and returns its index.
+    ///
+    /// # Panics
+    ///
+    /// If no receivers have been added
+    pub fn ready(&self) -> usize {
+        loop {
+            if let Some(index) = self.try_ready() {
+                return index;
+            }
+            thread::yield_now();
+        }
+    }
+
+    /// Returns the index of a ready receiver, without blocking.
+    ///
+    /// # Panics
+    ///
+    /// If no receivers have been added
+    pub fn try_ready(&self) -> Option<usize> {
+        assert!(
+            !self.receivers.is_empty(),
+            "Cannot select from zero receivers"
+        );
+        let start = self.next.get();
+        (0..self.receivers.len())
+            .map(|offset| (start + offset) % self.receivers.len())
+            .find(|&index| self.receivers[index].poll())
+            .map(|index| {
+                self.next.set(index + 1);
+                index
+            })
+    }
+
+    /// Waits at most `timeout` for one of the receivers to be ready and returns its index.
+    ///
+    /// # Panics
+    ///
+    /// If no receivers have been added
+    pub fn ready_timeout(&self, timeout: Duration) -> Option<usize> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(index) = self.try_ready() {
+                return Some(index);
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            thread::yield_now();
+        }
+    }
+}
+
+impl<'r> fmt::Debug for Select<'r> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Select")
+            .field("receivers", &self.receivers.len())
+            .finish()
+    }
+}
@@ -14,6 +14,7 @@ use std::mem;
 use std::{
     ffi::{CString, NulError},
     fmt,
+    marker::PhantomData,
     os::raw::{c_char, c_int, c_void},
     process::Command,
     ptr,
@@ -22,17 +23,22 @@ use std::{
 use conv::ConvUtil;
 #[cfg(feature = "user-operations")]
 use libffi::middle::{Cif, Closure, Type};
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Serialize};
 
 #[cfg(feature = "user-operations")]
 use crate::datatype::{DatatypeRef, DynBuffer, DynBufferMut};
+#[cfg(feature = "serde")]
+use crate::serialize::{DefaultEncoding, Encoding};
 use crate::{
-    datatype::traits::*,
+    datatype::{assume_init_vec, displacements, traits::*, uninit_vec, Partition, PartitionMut},
     ffi,
     ffi::MPI_Op,
+    instrument::{self, Call},
     raw::traits::*,
     request::{Request, Scope, StaticScope},
     topology::{traits::*, InterCommunicator, Process, Rank},
-    with_uninitialized, MpiError,
+    with_uninitialized, Count, Fint, MpiError,
 };
 
 /// Collective communication traits
@@ -57,6 +63,7 @@ pub trait CommunicatorCollectives: Communicator {
     ///
     /// 5.3
     fn barrier(&self) {
+        let _call = instrument::enter(|| Call::collective("MPI_Barrier").on(self));
         unsafe {
             ffi::MPI_Barrier(self.as_raw());
         }
@@ -81,6 +88,8 @@ pub trait CommunicatorCollectives: Communicator {
         S: Buffer,
         R: BufferMut,
     {
+        let _call =
+            instrument::enter(|| Call::collective("MPI_Allgather").on(self).buffer(sendbuf));
         unsafe {
             ffi::MPI_Allgather(
                 sendbuf.pointer(),
@@ -94,6 +103,33 @@ pub trait CommunicatorCollectives: Communicator {
         }
     }
 
+    /// Gather contents of slices on all participating processes into a newly allocated `Vec`.
+    ///
+    /// After the call completes, the returned `Vec` on all processes holds the concatenation of
+    /// `sendbuf` on all processes, ordered by rank.
+    ///
+    /// All `sendbuf`s must have the same length. Use `all_gather_varcount_vec` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/allocating_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.7
+    fn all_gather_vec<T>(&self, sendbuf: &[T]) -> Vec<T>
+    where
+        T: Equivalence,
+    {
+        let size: usize = self
+            .target_size()
+            .value_as()
+            .expect("Communicator size cannot be expressed as a usize.");
+        let mut recvbuf = uninit_vec::<T>(sendbuf.len() * size);
+        self.all_gather_into(sendbuf, &mut recvbuf[..]);
+        unsafe { assume_init_vec(recvbuf) }
+    }
+
     /// Gather contents of buffers on all participating processes.
     ///
     /// After the call completes, the contents of the send `Buffer`s on all processes will be
@@ -114,6 +150,8 @@ pub trait CommunicatorCollectives: Communicator {
         S: Buffer,
         R: PartitionedBufferMut,
     {
+        let _call =
+            instrument::enter(|| Call::collective("MPI_Allgatherv").on(self).buffer(sendbuf));
         unsafe {
             ffi::MPI_Allgatherv(
                 sendbuf.pointer(),
@@ -128,6 +166,40 @@ pub trait CommunicatorCollectives: Communicator {
         }
     }
 
+    /// Gather contents of slices of varying length on all participating processes into a newly
+    /// allocated `Vec`.
+    ///
+    /// The lengths of all `sendbuf`s are exchanged first. Returns the concatenation of `sendbuf`
+    /// on all processes, ordered by rank, together with the number of elements contributed by each
+    /// process.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/allocating_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.7
+    fn all_gather_varcount_vec<T>(&self, sendbuf: &[T]) -> (Vec<T>, Vec<Count>)
+    where
+        T: Equivalence,
+    {
+        let counts = self.all_gather_vec(&[sendbuf.count()]);
+        let displs = displacements(&counts);
+        let total: Count = counts.iter().sum();
+
+        let mut recvbuf = uninit_vec::<T>(
+            total
+                .value_as()
+                .expect("Gathered element count cannot be expressed as a usize."),
+        );
+        {
+            let mut partition = PartitionMut::new(&mut recvbuf[..], &counts[..], &displs[..]);
+            self.all_gather_varcount_into(sendbuf, &mut partition);
+        }
+        (unsafe { assume_init_vec(recvbuf) }, counts)
+    }
+
     /// Distribute the send `Buffer`s from all processes to the receive `Buffer`s on all processes.
     ///
     /// Each process sends and receives the same count of elements to and from each process.
@@ -144,6 +216,7 @@ pub trait CommunicatorCollectives: Communicator {
         S: Buffer,
         R: BufferMut,
     {
+        let _call = instrument::enter(|| Call::collective("MPI_Alltoall").on(self).buffer(sendbuf));
         let c_size = self.target_size();
         unsafe {
             ffi::MPI_Alltoall(
@@ -171,6 +244,11 @@ pub trait CommunicatorCollectives: Communicator {
         S: PartitionedBuffer,
         R: PartitionedBufferMut,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Alltoallv")
+                .on(self)
+                .partitioned(sendbuf)
+        });
         unsafe {
             ffi::MPI_Alltoallv(
                 sendbuf.pointer(),
@@ -186,6 +264,57 @@ pub trait CommunicatorCollectives: Communicator {
         }
     }
 
+    /// Distribute slices of varying length from all processes to all processes, receiving into a
+    /// newly allocated `Vec`.
+    ///
+    /// `sendbuf` is split into consecutive pieces of `sendcounts[i]` elements, the `i`th of which
+    /// is sent to the process of rank `i`. The counts are exchanged first. Returns the pieces
+    /// received from all processes, ordered by rank, together with the number of elements received
+    /// from each process.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/allocating_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.8
+    fn all_to_all_varcount_vec<T>(
+        &self,
+        sendbuf: &[T],
+        sendcounts: &[Count],
+    ) -> (Vec<T>, Vec<Count>)
+    where
+        T: Equivalence,
+    {
+        assert_eq!(
+            sendcounts.count(),
+            self.target_size(),
+            "There must be one send count per process."
+        );
+        let recvcounts = {
+            let mut recvcounts = uninit_vec::<Count>(sendcounts.len());
+            self.all_to_all_into(sendcounts, &mut recvcounts[..]);
+            unsafe { assume_init_vec(recvcounts) }
+        };
+        let senddispls = displacements(sendcounts);
+        let recvdispls = displacements(&recvcounts);
+        let total: Count = recvcounts.iter().sum();
+
+        let mut recvbuf = uninit_vec::<T>(
+            total
+                .value_as()
+                .expect("Received element count cannot be expressed as a usize."),
+        );
+        {
+            let partition = Partition::new(sendbuf, sendcounts, &senddispls[..]);
+            let mut recv_partition =
+                PartitionMut::new(&mut recvbuf[..], &recvcounts[..], &recvdispls[..]);
+            self.all_to_all_varcount_into(&partition, &mut recv_partition);
+        }
+        (unsafe { assume_init_vec(recvbuf) }, recvcounts)
+    }
+
     /// Performs a global reduction under the operation `op` of the input data in `sendbuf` and
     /// stores the result in `recvbuf` on all processes.
     ///
@@ -202,6 +331,8 @@ pub trait CommunicatorCollectives: Communicator {
         R: BufferMut,
         O: Operation,
     {
+        let _call =
+            instrument::enter(|| Call::collective("MPI_Allreduce").on(self).buffer(sendbuf));
         unsafe {
             ffi::MPI_Allreduce(
                 sendbuf.pointer(),
@@ -214,6 +345,94 @@ pub trait CommunicatorCollectives: Communicator {
         }
     }
 
+    /// Performs a global reduction under the operation `op` of `value` and returns the result on
+    /// all processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/reduce.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.6
+    fn all_reduce<T, O>(&self, value: T, op: O) -> T
+    where
+        T: Equivalence,
+        O: Operation,
+    {
+        let mut result = UninitMsg::<T>::uninit();
+        self.all_reduce_into(&value, &mut result, op);
+        unsafe { result.assume_init() }
+    }
+
+    /// Finds the global minimum of `value` and returns it on all processes together with the
+    /// lowest rank that holds it.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/reduce.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.4
+    fn all_min_loc<T>(&self, value: T) -> (T, Rank)
+    where
+        ValueIndex<T>: Equivalence,
+    {
+        let pair = ValueIndex {
+            value,
+            index: self.rank(),
+        };
+        self.all_reduce(pair, SystemOperation::min_loc()).into()
+    }
+
+    /// Finds the global maximum of `value` and returns it on all processes together with the
+    /// lowest rank that holds it.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/reduce.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.4
+    fn all_max_loc<T>(&self, value: T) -> (T, Rank)
+    where
+        ValueIndex<T>: Equivalence,
+    {
+        let pair = ValueIndex {
+            value,
+            index: self.rank(),
+        };
+        self.all_reduce(pair, SystemOperation::max_loc()).into()
+    }
+
+    /// Returns `true` on all processes if `value` is `true` on any process.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/reduce.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.6
+    fn any(&self, value: bool) -> bool {
+        self.all_reduce(value, SystemOperation::logical_or())
+    }
+
+    /// Returns `true` on all processes if `value` is `true` on every process.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/reduce.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.6
+    fn all(&self, value: bool) -> bool {
+        self.all_reduce(value, SystemOperation::logical_and())
+    }
+
     /// Performs an element-wise global reduction under the operation `op` of the input data in
     /// `sendbuf` and scatters the result into equal sized blocks in the receive buffers on all
     /// processes.
@@ -235,6 +454,11 @@ pub trait CommunicatorCollectives: Communicator {
         R: BufferMut,
         O: Operation,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Reduce_scatter_block")
+                .on(self)
+                .buffer(sendbuf)
+        });
         assert_eq!(recvbuf.count() * self.target_size(), sendbuf.count());
         unsafe {
             ffi::MPI_Reduce_scatter_block(
@@ -264,6 +488,7 @@ pub trait CommunicatorCollectives: Communicator {
         R: BufferMut,
         O: Operation,
     {
+        let _call = instrument::enter(|| Call::collective("MPI_Scan").on(self).buffer(sendbuf));
         unsafe {
             ffi::MPI_Scan(
                 sendbuf.pointer(),
@@ -292,6 +517,7 @@ pub trait CommunicatorCollectives: Communicator {
         R: BufferMut,
         O: Operation,
     {
+        let _call = instrument::enter(|| Call::collective("MPI_Exscan").on(self).buffer(sendbuf));
         unsafe {
             ffi::MPI_Exscan(
                 sendbuf.pointer(),
@@ -317,6 +543,7 @@ pub trait CommunicatorCollectives: Communicator {
     ///
     /// 5.12.1
     fn immediate_barrier(&self) -> Request<'static, ()> {
+        let _call = instrument::enter(|| Call::collective("MPI_Ibarrier").on(self));
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| ffi::MPI_Ibarrier(self.as_raw(), request)).1,
@@ -347,6 +574,8 @@ pub trait CommunicatorCollectives: Communicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
+        let _call =
+            instrument::enter(|| Call::collective("MPI_Iallgather").on(self).buffer(sendbuf));
         unsafe {
             let recvcount = recvbuf.count() / self.target_size();
             Request::from_raw(
@@ -390,6 +619,8 @@ pub trait CommunicatorCollectives: Communicator {
         R: 'a + PartitionedBufferMut,
         Sc: Scope<'a>,
     {
+        let _call =
+            instrument::enter(|| Call::collective("MPI_Iallgatherv").on(self).buffer(sendbuf));
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -432,6 +663,8 @@ pub trait CommunicatorCollectives: Communicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
+        let _call =
+            instrument::enter(|| Call::collective("MPI_Ialltoall").on(self).buffer(sendbuf));
         let c_size = self.target_size();
         unsafe {
             Request::from_raw(
@@ -470,6 +703,11 @@ pub trait CommunicatorCollectives: Communicator {
         R: 'a + PartitionedBufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Ialltoallv")
+                .on(self)
+                .partitioned(sendbuf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -516,6 +754,8 @@ pub trait CommunicatorCollectives: Communicator {
         O: 'a + Operation,
         Sc: Scope<'a>,
     {
+        let _call =
+            instrument::enter(|| Call::collective("MPI_Iallreduce").on(self).buffer(sendbuf));
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -560,6 +800,11 @@ pub trait CommunicatorCollectives: Communicator {
         O: 'a + Operation,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Ireduce_scatter_block")
+                .on(self)
+                .buffer(sendbuf)
+        });
         assert_eq!(recvbuf.count() * self.target_size(), sendbuf.count());
         unsafe {
             Request::from_raw(
@@ -604,6 +849,7 @@ pub trait CommunicatorCollectives: Communicator {
         O: 'a + Operation,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| Call::collective("MPI_Iscan").on(self).buffer(sendbuf));
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -647,6 +893,7 @@ pub trait CommunicatorCollectives: Communicator {
         O: 'a + Operation,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| Call::collective("MPI_Iexscan").on(self).buffer(sendbuf));
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -666,6 +913,17 @@ pub trait CommunicatorCollectives: Communicator {
             )
         }
     }
+
+    /// Collective calls that verify their arguments across processes first
+    ///
+    /// See `CheckedCommunicator`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/checked_collectives.rs`
+    fn checked(&self) -> CheckedCommunicator<'_, Self> {
+        CheckedCommunicator { comm: self }
+    }
 }
 
 impl<C: Communicator + ?Sized> CommunicatorCollectives for C {}
@@ -694,6 +952,12 @@ pub trait Root: AsCommunicator {
     where
         Buf: BufferMut,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Bcast")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(&*buffer)
+        });
         unsafe {
             ffi::MPI_Bcast(
                 buffer.pointer_mut(),
@@ -705,6 +969,39 @@ pub trait Root: AsCommunicator {
         }
     }
 
+    /// Broadcast of the contents of a `Vec`
+    ///
+    /// After the call completes, `buffer` on all processes in the `Communicator` of the `Root`
+    /// `&self` will be equal to `buffer` on the `Root`. The length is broadcast first, so `buffer`
+    /// is resized as needed on the other processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/allocating_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.4
+    fn broadcast_vec<T>(&self, buffer: &mut Vec<T>)
+    where
+        T: Equivalence,
+    {
+        if self.as_communicator().rank() == self.root_rank() {
+            let mut len = buffer.count();
+            self.broadcast_into(&mut len);
+            self.broadcast_into(&mut buffer[..]);
+        } else {
+            let mut len: Count = 0;
+            self.broadcast_into(&mut len);
+            let mut recvbuf = uninit_vec::<T>(
+                len.value_as()
+                    .expect("Broadcast length cannot be expressed as a usize."),
+            );
+            self.broadcast_into(&mut recvbuf[..]);
+            *buffer = unsafe { assume_init_vec(recvbuf) };
+        }
+    }
+
     /// Gather contents of buffers on `Root`.
     ///
     /// After the call completes, the contents of the `Buffer`s on all ranks will be
@@ -725,6 +1022,12 @@ pub trait Root: AsCommunicator {
     where
         S: Buffer,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Gather")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_ne!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             ffi::MPI_Gather(
@@ -761,6 +1064,12 @@ pub trait Root: AsCommunicator {
         S: Buffer,
         R: BufferMut,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Gather")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_eq!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             let recvcount = recvbuf.count() / self.as_communicator().target_size();
@@ -798,6 +1107,12 @@ pub trait Root: AsCommunicator {
     where
         S: Buffer,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Gatherv")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_ne!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             ffi::MPI_Gatherv(
@@ -836,6 +1151,12 @@ pub trait Root: AsCommunicator {
         S: Buffer,
         R: PartitionedBufferMut,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Gatherv")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_eq!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             ffi::MPI_Gatherv(
@@ -852,6 +1173,56 @@ pub trait Root: AsCommunicator {
         }
     }
 
+    /// Gather contents of slices of varying length on `Root` into a newly allocated `Vec`.
+    ///
+    /// The lengths of all `sendbuf`s are gathered first. Returns the concatenation of `sendbuf` on
+    /// all processes, ordered by rank, on the `Root` and `None` on all other processes.
+    ///
+    /// This function must be called on all processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/allocating_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.5
+    fn gather_vec<T>(&self, sendbuf: &[T]) -> Option<Vec<T>>
+    where
+        T: Equivalence,
+    {
+        let count = sendbuf.count();
+        if self.as_communicator().rank() != self.root_rank() {
+            self.gather_into(&count);
+            self.gather_varcount_into(sendbuf);
+            return None;
+        }
+
+        let size: usize = self
+            .as_communicator()
+            .target_size()
+            .value_as()
+            .expect("Communicator size cannot be expressed as a usize.");
+        let counts = {
+            let mut counts = uninit_vec::<Count>(size);
+            self.gather_into_root(&count, &mut counts[..]);
+            unsafe { assume_init_vec(counts) }
+        };
+        let displs = displacements(&counts);
+        let total: Count = counts.iter().sum();
+
+        let mut recvbuf = uninit_vec::<T>(
+            total
+                .value_as()
+                .expect("Gathered element count cannot be expressed as a usize."),
+        );
+        {
+            let mut partition = PartitionMut::new(&mut recvbuf[..], &counts[..], &displs[..]);
+            self.gather_varcount_into_root(sendbuf, &mut partition);
+        }
+        Some(unsafe { assume_init_vec(recvbuf) })
+    }
+
     /// Scatter contents of a buffer on the root process to all processes.
     ///
     /// After the call completes each participating process will have received a part of the send
@@ -872,6 +1243,12 @@ pub trait Root: AsCommunicator {
     where
         R: BufferMut,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Scatter")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(&*recvbuf)
+        });
         assert_ne!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             ffi::MPI_Scatter(
@@ -908,6 +1285,12 @@ pub trait Root: AsCommunicator {
         S: Buffer,
         R: BufferMut,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Scatter")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_eq!(self.as_communicator().rank(), self.root_rank());
         let sendcount = sendbuf.count() / self.as_communicator().target_size();
         unsafe {
@@ -945,6 +1328,12 @@ pub trait Root: AsCommunicator {
     where
         R: BufferMut,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Scatterv")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(&*recvbuf)
+        });
         assert_ne!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             ffi::MPI_Scatterv(
@@ -983,6 +1372,12 @@ pub trait Root: AsCommunicator {
         S: PartitionedBuffer,
         R: BufferMut,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Scatterv")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .partitioned(sendbuf)
+        });
         assert_eq!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             ffi::MPI_Scatterv(
@@ -999,6 +1394,74 @@ pub trait Root: AsCommunicator {
         }
     }
 
+    /// Scatter pieces of varying length of a slice on the root process into newly allocated
+    /// `Vec`s on all processes.
+    ///
+    /// The counts are scattered first, so only the root needs to know them.
+    ///
+    /// This function must be called on all non-root processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/allocating_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.6
+    fn scatter_varcount_vec<T>(&self) -> Vec<T>
+    where
+        T: Equivalence,
+    {
+        let mut count: Count = 0;
+        self.scatter_into(&mut count);
+        let mut recvbuf = uninit_vec::<T>(
+            count
+                .value_as()
+                .expect("Scattered element count cannot be expressed as a usize."),
+        );
+        self.scatter_varcount_into(&mut recvbuf[..]);
+        unsafe { assume_init_vec(recvbuf) }
+    }
+
+    /// Scatter pieces of varying length of a slice on the root process into newly allocated
+    /// `Vec`s on all processes.
+    ///
+    /// `sendbuf` is split into consecutive pieces of `counts[i]` elements, the `i`th of which is
+    /// returned on the process of rank `i`. The counts are scattered first, so only the root needs
+    /// to know them.
+    ///
+    /// This function must be called on the root process.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/allocating_collectives.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.6
+    fn scatter_varcount_vec_root<T>(&self, sendbuf: &[T], counts: &[Count]) -> Vec<T>
+    where
+        T: Equivalence,
+    {
+        assert_eq!(
+            counts.count(),
+            self.as_communicator().target_size(),
+            "There must be one count per process."
+        );
+        let mut count: Count = 0;
+        self.scatter_into_root(counts, &mut count);
+
+        let displs = displacements(counts);
+        let partition = Partition::new(sendbuf, counts, &displs[..]);
+        let mut recvbuf = uninit_vec::<T>(
+            count
+                .value_as()
+                .expect("Scattered element count cannot be expressed as a usize."),
+        );
+        self.scatter_varcount_into_root(&partition, &mut recvbuf[..]);
+        unsafe { assume_init_vec(recvbuf) }
+    }
+
     /// Performs a global reduction under the operation `op` of the input data in `sendbuf` and
     /// stores the result on the `Root` process.
     ///
@@ -1016,6 +1479,12 @@ pub trait Root: AsCommunicator {
         S: Buffer,
         O: Operation,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Reduce")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_ne!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             ffi::MPI_Reduce(
@@ -1048,6 +1517,12 @@ pub trait Root: AsCommunicator {
         R: BufferMut,
         O: Operation,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Reduce")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_eq!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             ffi::MPI_Reduce(
@@ -1080,6 +1555,12 @@ pub trait Root: AsCommunicator {
         Buf: 'a + BufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Ibcast")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(&*buf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -1119,6 +1600,12 @@ pub trait Root: AsCommunicator {
         S: 'a + Buffer,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Igather")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_ne!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             Request::from_raw(
@@ -1164,6 +1651,12 @@ pub trait Root: AsCommunicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Igather")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_eq!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             let recvcount = recvbuf.count() / self.as_communicator().target_size();
@@ -1208,6 +1701,12 @@ pub trait Root: AsCommunicator {
         S: 'a + Buffer,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Igatherv")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_ne!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             Request::from_raw(
@@ -1254,6 +1753,12 @@ pub trait Root: AsCommunicator {
         R: 'a + PartitionedBufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Igatherv")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_eq!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             Request::from_raw(
@@ -1298,6 +1803,12 @@ pub trait Root: AsCommunicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Iscatter")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(&*recvbuf)
+        });
         assert_ne!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             Request::from_raw(
@@ -1343,6 +1854,12 @@ pub trait Root: AsCommunicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Iscatter")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_eq!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             let sendcount = sendbuf.count() / self.as_communicator().target_size();
@@ -1387,6 +1904,12 @@ pub trait Root: AsCommunicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Iscatterv")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(&*recvbuf)
+        });
         assert_ne!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             Request::from_raw(
@@ -1433,6 +1956,12 @@ pub trait Root: AsCommunicator {
         R: 'a + BufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Iscatterv")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .partitioned(sendbuf)
+        });
         assert_eq!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             Request::from_raw(
@@ -1480,6 +2009,12 @@ pub trait Root: AsCommunicator {
         O: 'a + Operation,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Ireduce")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_ne!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             Request::from_raw(
@@ -1527,6 +2062,12 @@ pub trait Root: AsCommunicator {
         O: 'a + Operation,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::collective("MPI_Ireduce")
+                .on(self.as_communicator())
+                .peer(self.root_rank())
+                .buffer(sendbuf)
+        });
         assert_eq!(self.as_communicator().rank(), self.root_rank());
         unsafe {
             Request::from_raw(
@@ -1549,6 +2090,180 @@ pub trait Root: AsCommunicator {
         }
     }
 
+    /// Broadcast a value of a `serde` type, encoded with `encoding`.
+    ///
+    /// After the call completes, `value` on all processes in the `Communicator` of the `Root`
+    /// `&self` will be equal to `value` on the `Root`. The encoded length is broadcast first, so
+    /// the size of the value does not need to be known in advance.
+    ///
+    /// If encoding fails on the root, all processes return an error.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.4
+    #[cfg(feature = "serde")]
+    fn broadcast_serialized_with_encoding<T, E>(
+        &self,
+        value: &mut T,
+        encoding: E,
+    ) -> Result<(), MpiError>
+    where
+        T: Serialize + DeserializeOwned,
+        E: Encoding,
+    {
+        let is_root = self.as_communicator().rank() == self.root_rank();
+        let encoded = if is_root {
+            Some(encoding.encode(value))
+        } else {
+            None
+        };
+
+        // A negative length tells the other processes that the root failed to encode its value.
+        let mut len: Count = match encoded {
+            Some(Ok(ref bytes)) => bytes
+                .len()
+                .value_as()
+                .expect("Encoded value length cannot be expressed as an MPI Count."),
+            Some(Err(_)) => -1,
+            None => 0,
+        };
+        self.broadcast_into(&mut len);
+
+        let mut bytes = match encoded {
+            Some(result) => result?,
+            None => match len.value_as::<usize>() {
+                Ok(len) => vec![0u8; len],
+                Err(_) => {
+                    return Err(MpiError::Serialize(
+                        "the root process failed to encode the broadcast value".into(),
+                    ))
+                }
+            },
+        };
+        self.broadcast_into(&mut bytes[..]);
+
+        if !is_root {
+            *value = encoding.decode(&bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Broadcast a value of a `serde` type.
+    ///
+    /// After the call completes, `value` on all processes in the `Communicator` of the `Root`
+    /// `&self` will be equal to `value` on the `Root`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/serialized.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.4
+    #[cfg(feature = "serde")]
+    fn broadcast_serialized<T>(&self, value: &mut T) -> Result<(), MpiError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        self.broadcast_serialized_with_encoding(value, DefaultEncoding::default())
+    }
+
+    /// Gather values of a `serde` type on `Root`, encoded with `encoding`.
+    ///
+    /// Returns the values of all processes ordered by rank on the `Root` and `None` on all other
+    /// processes. The encoded lengths are gathered first, so values may differ in size.
+    ///
+    /// This function must be called on all processes. If encoding fails on any process, that
+    /// process and the `Root` return an error.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.5
+    #[cfg(feature = "serde")]
+    fn gather_serialized_with_encoding<T, E>(
+        &self,
+        value: &T,
+        encoding: E,
+    ) -> Result<Option<Vec<T>>, MpiError>
+    where
+        T: Serialize + DeserializeOwned,
+        E: Encoding,
+    {
+        let encoded = encoding.encode(value);
+
+        // A negative length tells the root that this process failed to encode its value.
+        let (sendbuf, len): (&[u8], Count) = match encoded {
+            Ok(ref bytes) => (
+                &bytes[..],
+                bytes
+                    .len()
+                    .value_as()
+                    .expect("Encoded value length cannot be expressed as an MPI Count."),
+            ),
+            Err(_) => (&[][..], -1),
+        };
+
+        if self.as_communicator().rank() != self.root_rank() {
+            self.gather_into(&len);
+            self.gather_varcount_into(sendbuf);
+            return encoded.map(|_| None);
+        }
+
+        let size = self.as_communicator().target_size();
+        let mut lens: Vec<Count> = vec![0; size.value_as().expect("Negative communicator size")];
+        self.gather_into_root(&len, &mut lens[..]);
+
+        let counts: Vec<Count> = lens.iter().map(|&len| len.max(0)).collect();
+        let displs = displacements(&counts);
+        let total: Count = counts.iter().sum();
+
+        let mut bytes = vec![0u8; total.value_as().expect("Negative gathered length")];
+        {
+            let mut partition = PartitionMut::new(&mut bytes[..], &counts[..], &displs[..]);
+            self.gather_varcount_into_root(sendbuf, &mut partition);
+        }
+
+        encoded?;
+        if let Some(rank) = lens.iter().position(|&len| len < 0) {
+            return Err(MpiError::Serialize(
+                format!("process {} failed to encode its gathered value", rank).into(),
+            ));
+        }
+
+        counts
+            .iter()
+            .zip(displs.iter())
+            .map(|(&count, &displ)| {
+                let start: usize = displ.value_as().expect("Negative displacement");
+                let end = start + count.value_as::<usize>().expect("Negative count");
+                encoding.decode(&bytes[start..end])
+            })
+            .collect::<Result<Vec<T>, MpiError>>()
+            .map(Some)
+    }
+
+    /// Gather values of a `serde` type on `Root`.
+    ///
+    /// Returns the values of all processes ordered by rank on the `Root` and `None` on all other
+    /// processes.
+    ///
+    /// This function must be called on all processes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/serialized.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.5
+    #[cfg(feature = "serde")]
+    fn gather_serialized<T>(&self, value: &T) -> Result<Option<Vec<T>>, MpiError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        self.gather_serialized_with_encoding(value, DefaultEncoding::default())
+    }
+
     /// Spawns child processes
     ///
     /// # Standard sections
@@ -1685,6 +2400,17 @@ pub trait Root: AsCommunicator {
             Ok(unsafe { InterCommunicator::from_raw(result) })
         }
     }
+
+    /// Rooted collective calls that verify their arguments across processes first
+    ///
+    /// See `CheckedRoot`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/checked_collectives.rs`
+    fn checked(&self) -> CheckedRoot<'_, Self> {
+        CheckedRoot { root: self }
+    }
 }
 
 impl<'a> Root for Process<'a> {
@@ -1693,6 +2419,298 @@ impl<'a> Root for Process<'a> {
     }
 }
 
+/// Collective calls on a `Communicator` that verify their arguments across processes first
+///
+/// Before each call, the processes all-reduce a hash of the routine and of the count and
+/// datatype size of the buffer that has to match across processes. If the hashes differ, no
+/// process makes the call and all of them return `MpiError::CollectiveMismatch`, naming the
+/// processes that disagree with the majority. Verification costs an additional all-reduce, and
+/// an all-gather on mismatch. It is meant for intracommunicators.
+///
+/// Created by `CommunicatorCollectives::checked()`.
+#[derive(Copy, Clone, Debug)]
+pub struct CheckedCommunicator<'a, C: ?Sized> {
+    comm: &'a C,
+}
+
+impl<'a, C: Communicator + ?Sized> CheckedCommunicator<'a, C> {
+    /// Checked `CommunicatorCollectives::barrier()`
+    pub fn barrier(&self) -> Result<(), MpiError> {
+        verify(self.comm, "MPI_Barrier", None, (0, 0))?;
+        self.comm.barrier();
+        Ok(())
+    }
+
+    /// Checked `CommunicatorCollectives::all_gather_into()`
+    pub fn all_gather_into<S: ?Sized, R: ?Sized>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+    ) -> Result<(), MpiError>
+    where
+        S: Buffer,
+        R: BufferMut,
+    {
+        verify(self.comm, "MPI_Allgather", None, shape(sendbuf))?;
+        self.comm.all_gather_into(sendbuf, recvbuf);
+        Ok(())
+    }
+
+    /// Checked `CommunicatorCollectives::all_to_all_into()`
+    pub fn all_to_all_into<S: ?Sized, R: ?Sized>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+    ) -> Result<(), MpiError>
+    where
+        S: Buffer,
+        R: BufferMut,
+    {
+        verify(self.comm, "MPI_Alltoall", None, shape(sendbuf))?;
+        self.comm.all_to_all_into(sendbuf, recvbuf);
+        Ok(())
+    }
+
+    /// Checked `CommunicatorCollectives::all_reduce_into()`
+    pub fn all_reduce_into<S: ?Sized, R: ?Sized, O>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+        op: O,
+    ) -> Result<(), MpiError>
+    where
+        S: Buffer,
+        R: BufferMut,
+        O: Operation,
+    {
+        verify(self.comm, "MPI_Allreduce", None, shape(sendbuf))?;
+        self.comm.all_reduce_into(sendbuf, recvbuf, op);
+        Ok(())
+    }
+
+    /// Checked `CommunicatorCollectives::reduce_scatter_block_into()`
+    pub fn reduce_scatter_block_into<S: ?Sized, R: ?Sized, O>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+        op: O,
+    ) -> Result<(), MpiError>
+    where
+        S: Buffer,
+        R: BufferMut,
+        O: Operation,
+    {
+        verify(
+            self.comm,
+            "MPI_Reduce_scatter_block",
+            None,
+            shape(&*recvbuf),
+        )?;
+        self.comm.reduce_scatter_block_into(sendbuf, recvbuf, op);
+        Ok(())
+    }
+
+    /// Checked `CommunicatorCollectives::scan_into()`
+    pub fn scan_into<S: ?Sized, R: ?Sized, O>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+        op: O,
+    ) -> Result<(), MpiError>
+    where
+        S: Buffer,
+        R: BufferMut,
+        O: Operation,
+    {
+        verify(self.comm, "MPI_Scan", None, shape(sendbuf))?;
+        self.comm.scan_into(sendbuf, recvbuf, op);
+        Ok(())
+    }
+
+    /// Checked `CommunicatorCollectives::exclusive_scan_into()`
+    pub fn exclusive_scan_into<S: ?Sized, R: ?Sized, O>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+        op: O,
+    ) -> Result<(), MpiError>
+    where
+        S: Buffer,
+        R: BufferMut,
+        O: Operation,
+    {
+        verify(self.comm, "MPI_Exscan", None, shape(sendbuf))?;
+        self.comm.exclusive_scan_into(sendbuf, recvbuf, op);
+        Ok(())
+    }
+}
+
+/// Rooted collective calls that verify their arguments across processes first
+///
+/// Works like `CheckedCommunicator`, with the rank of the root as part of the hash.
+///
+/// Created by `Root::checked()`.
+#[derive(Copy, Clone, Debug)]
+pub struct CheckedRoot<'a, R: ?Sized> {
+    root: &'a R,
+}
+
+impl<'a, T: Root + ?Sized> CheckedRoot<'a, T> {
+    fn verify(&self, operation: &'static str, shape: (Count, Count)) -> Result<(), MpiError> {
+        verify(
+            self.root.as_communicator(),
+            operation,
+            Some(self.root.root_rank()),
+            shape,
+        )
+    }
+
+    /// Checked `Root::broadcast_into()`
+    pub fn broadcast_into<Buf: ?Sized>(&self, buffer: &mut Buf) -> Result<(), MpiError>
+    where
+        Buf: BufferMut,
+    {
+        self.verify("MPI_Bcast", shape(&*buffer))?;
+        self.root.broadcast_into(buffer);
+        Ok(())
+    }
+
+    /// Checked `Root::gather_into()`
+    pub fn gather_into<S: ?Sized>(&self, sendbuf: &S) -> Result<(), MpiError>
+    where
+        S: Buffer,
+    {
+        self.verify("MPI_Gather", shape(sendbuf))?;
+        self.root.gather_into(sendbuf);
+        Ok(())
+    }
+
+    /// Checked `Root::gather_into_root()`
+    pub fn gather_into_root<S: ?Sized, R: ?Sized>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+    ) -> Result<(), MpiError>
+    where
+        S: Buffer,
+        R: BufferMut,
+    {
+        self.verify("MPI_Gather", shape(sendbuf))?;
+        self.root.gather_into_root(sendbuf, recvbuf);
+        Ok(())
+    }
+
+    /// Checked `Root::scatter_into()`
+    pub fn scatter_into<R: ?Sized>(&self, recvbuf: &mut R) -> Result<(), MpiError>
+    where
+        R: BufferMut,
+    {
+        self.verify("MPI_Scatter", shape(&*recvbuf))?;
+        self.root.scatter_into(recvbuf);
+        Ok(())
+    }
+
+    /// Checked `Root::scatter_into_root()`
+    pub fn scatter_into_root<S: ?Sized, R: ?Sized>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+    ) -> Result<(), MpiError>
+    where
+        S: Buffer,
+        R: BufferMut,
+    {
+        self.verify("MPI_Scatter", shape(&*recvbuf))?;
+        self.root.scatter_into_root(sendbuf, recvbuf);
+        Ok(())
+    }
+
+    /// Checked `Root::reduce_into()`
+    pub fn reduce_into<S: ?Sized, O>(&self, sendbuf: &S, op: O) -> Result<(), MpiError>
+    where
+        S: Buffer,
+        O: Operation,
+    {
+        self.verify("MPI_Reduce", shape(sendbuf))?;
+        self.root.reduce_into(sendbuf, op);
+        Ok(())
+    }
+
+    /// Checked `Root::reduce_into_root()`
+    pub fn reduce_into_root<S: ?Sized, R: ?Sized, O>(
+        &self,
+        sendbuf: &S,
+        recvbuf: &mut R,
+        op: O,
+    ) -> Result<(), MpiError>
+    where
+        S: Buffer,
+        R: BufferMut,
+        O: Operation,
+    {
+        self.verify("MPI_Reduce", shape(sendbuf))?;
+        self.root.reduce_into_root(sendbuf, recvbuf, op);
+        Ok(())
+    }
+}
+
+/// The count and the size in bytes of the datatype of `buf`
+fn shape<B>(buf: &B) -> (Count, Count)
+where
+    B: AsDatatype + Collection + ?Sized,
+{
+    let size = unsafe {
+        with_uninitialized(|size| ffi::MPI_Type_size(buf.as_datatype().as_raw(), size)).1
+    };
+    (buf.count(), size)
+}
+
+/// Compares the signature of a checked collective call across the processes in `comm`.
+fn verify<C>(
+    comm: &C,
+    operation: &'static str,
+    root: Option<Rank>,
+    (count, size): (Count, Count),
+) -> Result<(), MpiError>
+where
+    C: Communicator + ?Sized,
+{
+    // 64 bit FNV-1a, which unlike the hashers of the standard library is stable across builds
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    let root = root.unwrap_or(-1);
+    let fields = [root.to_le_bytes(), count.to_le_bytes(), size.to_le_bytes()];
+    for &byte in operation.as_bytes().iter().chain(fields.iter().flatten()) {
+        hash = (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+    }
+
+    // The maximum of the complement is the complement of the minimum.
+    let mut extremes = [0u64; 2];
+    comm.all_reduce_into(
+        &[hash, !hash][..],
+        &mut extremes[..],
+        SystemOperation::max(),
+    );
+    if extremes[0] == !extremes[1] {
+        return Ok(());
+    }
+
+    let hashes = comm.all_gather_vec(&[hash]);
+    let mut votes = std::collections::BTreeMap::new();
+    for &hash in &hashes {
+        *votes.entry(hash).or_insert(0) += 1;
+    }
+    // Ties are decided in favour of the lowest rank.
+    let expected = hashes.iter().rev().max_by_key(|hash| votes[*hash]).copied();
+    Err(MpiError::CollectiveMismatch {
+        operation,
+        ranks: (0..)
+            .zip(&hashes)
+            .filter(|&(_, hash)| Some(*hash) != expected)
+            .map(|(rank, _)| rank)
+            .collect(),
+    })
+}
+
 /// An operation to be used in a reduction or scan type operation, e.g. `MPI_SUM`
 pub trait Operation: AsRaw<Raw = MPI_Op> {
     /// Returns whether the operation is commutative.
@@ -1707,6 +2725,15 @@ pub trait Operation: AsRaw<Raw = MPI_Op> {
             commute != 0
         }
     }
+
+    /// The handle of the operation in the Fortran bindings of MPI
+    ///
+    /// # Standard section(s)
+    ///
+    /// 17.2.4
+    fn to_fortran(&self) -> Fint {
+        unsafe { ffi::RSMPI_Op_c2f(self.as_raw()) }
+    }
 }
 impl<'a, T: 'a + Operation> Operation for &'a T {}
 
@@ -1744,6 +2771,43 @@ impl SystemOperation {
         logical_xor => ffi::RSMPI_LXOR,
         bitwise_xor => ffi::RSMPI_BXOR
     }
+
+    /// The minimum of `ValueIndex` pairs, keeping the smallest index among equal minima
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.4
+    pub fn min_loc() -> SystemOperation {
+        SystemOperation(unsafe { ffi::RSMPI_MINLOC })
+    }
+
+    /// The maximum of `ValueIndex` pairs, keeping the smallest index among equal maxima
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.4
+    pub fn max_loc() -> SystemOperation {
+        SystemOperation(unsafe { ffi::RSMPI_MAXLOC })
+    }
+
+    /// Replaces the target value with the origin value, only valid in one-sided accumulate
+    /// operations
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.3.4
+    pub fn replace() -> SystemOperation {
+        SystemOperation(unsafe { ffi::RSMPI_REPLACE })
+    }
+
+    /// Leaves the target value unchanged, only valid in one-sided accumulate operations
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.3.4
+    pub fn no_op() -> SystemOperation {
+        SystemOperation(unsafe { ffi::RSMPI_NO_OP })
+    }
 }
 
 unsafe impl AsRaw for SystemOperation {
@@ -1755,6 +2819,46 @@ unsafe impl AsRaw for SystemOperation {
 
 impl Operation for SystemOperation {}
 
+/// An operation owned by someone else, e.g. a C or Fortran library, that is valid for `'a`
+///
+/// The operation is not freed when the `BorrowedOperation` is dropped.
+#[derive(Copy, Clone)]
+pub struct BorrowedOperation<'a>(MPI_Op, PhantomData<&'a ()>);
+
+impl<'a> BorrowedOperation<'a> {
+    /// Borrows the operation `raw`, returns `None` if `raw` is `MPI_OP_NULL`.
+    ///
+    /// # Safety
+    /// - `raw` must be a live operation handle or `MPI_OP_NULL`
+    /// - `raw` must not be freed during `'a`
+    pub unsafe fn from_raw_borrowed(raw: MPI_Op) -> Option<Self> {
+        (raw != ffi::RSMPI_OP_NULL).then_some(BorrowedOperation(raw, PhantomData))
+    }
+
+    /// Borrows the operation with the Fortran handle `op`, returns `None` if `op` is
+    /// `MPI_OP_NULL`.
+    ///
+    /// # Safety
+    /// - `op` must be the handle of a live operation or of `MPI_OP_NULL`
+    /// - the operation must not be freed during `'a`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 17.2.4
+    pub unsafe fn from_fortran(op: Fint) -> Option<Self> {
+        Self::from_raw_borrowed(ffi::RSMPI_Op_f2c(op))
+    }
+}
+
+unsafe impl<'a> AsRaw for BorrowedOperation<'a> {
+    type Raw = MPI_Op;
+    fn as_raw(&self) -> Self::Raw {
+        self.0
+    }
+}
+
+impl<'a> Operation for BorrowedOperation<'a> {}
+
 #[cfg(feature = "user-operations")]
 trait Erased {}
 
@@ -1921,6 +3025,74 @@ impl<'a> UserOperation<'a> {
         }
     }
 
+    /// Define an associative operation that combines buffers of `T` element by element.
+    ///
+    /// This is a more readable shorthand for the `elementwise` method.  Refer to
+    /// [`elementwise`](#method.elementwise) for more information.
+    pub fn associative_for<T, F>(function: F) -> Self
+    where
+        T: Equivalence + 'a,
+        F: Fn(&T, &mut T) + Sync + 'a,
+    {
+        Self::elementwise(false, function)
+    }
+
+    /// Define an associative and commutative operation that combines buffers of `T` element by
+    /// element.
+    ///
+    /// This is a more readable shorthand for the `elementwise` method.  Refer to
+    /// [`elementwise`](#method.elementwise) for more information.
+    pub fn commutative_for<T, F>(function: F) -> Self
+    where
+        T: Equivalence + 'a,
+        F: Fn(&T, &mut T) + Sync + 'a,
+    {
+        Self::elementwise(true, function)
+    }
+
+    /// Creates an associative and possibly commutative operation on buffers of `T` from a closure
+    /// that combines a single pair of elements.
+    ///
+    /// The closure receives an element `x` of `invec` and the corresponding element `y` of
+    /// `inoutvec` and shall set `y` to the value of `f(x, y)`, where `f` is a binary associative
+    /// operation.  The datatype of the buffers is checked against `T` once per invocation, the
+    /// loop over the elements is done by the operation.
+    ///
+    /// Use `elementwise_builder` to define an operation for several types at once.
+    ///
+    /// **Note:** If the closure panics or the operation is used with a datatype other than the one
+    /// equivalent to `T`, the entire program will abort.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/reduce.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 5.9.5
+    pub fn elementwise<T, F>(commute: bool, function: F) -> Self
+    where
+        T: Equivalence + 'a,
+        F: Fn(&T, &mut T) + Sync + 'a,
+    {
+        Self::elementwise_builder(commute)
+            .with_type(function)
+            .build()
+    }
+
+    /// Starts defining an associative and possibly commutative operation that combines buffers
+    /// element by element and supports several element types.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/reduce.rs`
+    pub fn elementwise_builder(commute: bool) -> ElementwiseOperationBuilder<'a> {
+        ElementwiseOperationBuilder {
+            commute,
+            functions: Vec::new(),
+        }
+    }
+
     /// Creates a `UserOperation` from raw parts.
     ///
     /// Here, `anchor` is an arbitrary object that is stored alongside the `MPI_Op`.
@@ -1936,6 +3108,94 @@ impl<'a> UserOperation<'a> {
     }
 }
 
+/// A type-erased element-wise function that returns `false` if the buffers do not match its type
+#[cfg(feature = "user-operations")]
+type ElementwiseFunction<'a> = Box<dyn Fn(DynBuffer, DynBufferMut) -> bool + Sync + 'a>;
+
+/// Defines a `UserOperation` that combines buffers element by element, with a separate closure for
+/// each supported element type.
+///
+/// When the operation is invoked, the closure registered for the datatype of the buffers is used.
+/// Types are told apart by their equivalent MPI datatype, so each registered type should have a
+/// different one.
+///
+/// # Examples
+///
+/// ```no_run
+/// use mpi::{collective::UserOperation, traits::*};
+///
+/// let universe = mpi::initialize().unwrap();
+/// let world = universe.world();
+///
+/// let op = UserOperation::elementwise_builder(true)
+///     .with_type(|x: &i32, y: &mut i32| *y = (*y).max(*x))
+///     .with_type(|x: &f64, y: &mut f64| *y = y.max(*x))
+///     .build();
+///
+/// let mut max = 0.0;
+/// world.all_reduce_into(&1.5f64, &mut max, &op);
+/// ```
+///
+/// # Standard section(s)
+///
+/// 5.9.5
+#[cfg(feature = "user-operations")]
+pub struct ElementwiseOperationBuilder<'a> {
+    commute: bool,
+    functions: Vec<ElementwiseFunction<'a>>,
+}
+
+#[cfg(feature = "user-operations")]
+impl<'a> fmt::Debug for ElementwiseOperationBuilder<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ElementwiseOperationBuilder")
+            .field("commute", &self.commute)
+            .field("types", &self.functions.len())
+            .finish()
+    }
+}
+
+#[cfg(feature = "user-operations")]
+impl<'a> ElementwiseOperationBuilder<'a> {
+    /// Registers the closure used to combine elements of type `T`.
+    ///
+    /// The closure receives an element `x` of `invec` and the corresponding element `y` of
+    /// `inoutvec` and shall set `y` to the value of `f(x, y)`.
+    pub fn with_type<T, F>(mut self, function: F) -> Self
+    where
+        T: Equivalence + 'a,
+        F: Fn(&T, &mut T) + Sync + 'a,
+    {
+        self.functions
+            .push(Box::new(move |invec: DynBuffer, inoutvec: DynBufferMut| {
+                if !(invec.is::<T>() && inoutvec.is::<T>()) {
+                    return false;
+                }
+                let invec: &[T] = invec.downcast().unwrap();
+                let inoutvec: &mut [T] = inoutvec.downcast().unwrap();
+                for (x, y) in invec.iter().zip(inoutvec) {
+                    function(x, y);
+                }
+                true
+            }));
+        self
+    }
+
+    /// Creates the operation.
+    pub fn build(self) -> UserOperation<'a> {
+        let functions = self.functions;
+        UserOperation::new(self.commute, move |invec, mut inoutvec| {
+            let handled = functions
+                .iter()
+                .any(|function| function(invec, inoutvec.reborrow_mut()));
+            assert!(
+                handled,
+                "UserOperation has no element-wise function for this datatype"
+            );
+        })
+    }
+}
+
 /// An unsafe user-defined operation.
 ///
 /// Unsafe user-defined operations are created from pointers to functions that have the unsafe
@@ -52,7 +52,6 @@
 //!
 //! # Unfinished features
 //!
-//! - **4.1.3**: Subarray datatype constructors, `MPI_Type_create_subarray()`,
 //! - **4.1.4**: Distributed array datatype constructors, `MPI_Type_create_darray()`
 //! - **4.1.5**: Address and size functions, `MPI_Get_address()`, `MPI_Aint_add()`,
 //! `MPI_Aint_diff()`, `MPI_Type_size()`, `MPI_Type_size_x()`
@@ -62,15 +61,21 @@
 //! `MPI_Type_get_true_extent_x()`
 //! - **4.1.11**: `MPI_Get_elements()`, `MPI_Get_elements_x()`
 //! - **4.1.13**: Decoding a datatype, `MPI_Type_get_envelope()`, `MPI_Type_get_contents()`
-//! - **4.3**: Canonical pack and unpack, `MPI_Pack_external()`, `MPI_Unpack_external()`,
-//! `MPI_Pack_external_size()`
 
-use std::{borrow::Borrow, marker::PhantomData, mem, os::raw::c_void, slice};
+use std::{
+    borrow::Borrow,
+    marker::PhantomData,
+    mem,
+    os::raw::{c_char, c_int, c_void},
+    slice,
+};
 
 use conv::ConvUtil;
 
-use super::{Address, Count};
-use crate::{ffi, ffi::MPI_Datatype, raw::traits::*, with_uninitialized};
+use super::{Address, Count, Fint};
+use crate::{
+    ffi, ffi::MPI_Datatype, raw::traits::*, topology::Communicator, with_uninitialized, MpiError,
+};
 
 /// Datatype traits
 pub mod traits {
@@ -105,6 +110,31 @@ impl<'a> FromRaw for DatatypeRef<'a> {
     }
 }
 
+impl<'a> DatatypeRef<'a> {
+    /// Borrows a committed datatype owned by someone else, e.g. a C or Fortran library. Returns
+    /// `None` if `datatype` is `MPI_DATATYPE_NULL`.
+    ///
+    /// # Safety
+    /// - `datatype` must be a live, committed datatype handle or `MPI_DATATYPE_NULL`
+    /// - `datatype` must not be freed during `'a`
+    pub unsafe fn from_raw_borrowed(datatype: MPI_Datatype) -> Option<Self> {
+        (datatype != ffi::RSMPI_DATATYPE_NULL).then(|| Self::from_raw(datatype))
+    }
+
+    /// Borrows the committed datatype with the Fortran handle `datatype`. Returns `None` if
+    /// `datatype` is `MPI_DATATYPE_NULL`.
+    ///
+    /// # Safety
+    /// - `datatype` must be the handle of a live, committed datatype or of `MPI_DATATYPE_NULL`
+    /// - the datatype must not be freed during `'a`
+    ///
+    /// # Standard section(s)
+    /// 17.2.4
+    pub unsafe fn from_fortran(datatype: Fint) -> Option<Self> {
+        Self::from_raw_borrowed(ffi::RSMPI_Type_f2c(datatype))
+    }
+}
+
 unsafe impl<'a> MatchesRaw for DatatypeRef<'a> {}
 
 impl<'a> Datatype for DatatypeRef<'a> {}
@@ -230,6 +260,45 @@ equivalent_system_datatype!(usize, ffi::RSMPI_UINT64_T);
 #[cfg(target_pointer_width = "64")]
 equivalent_system_datatype!(isize, ffi::RSMPI_INT64_T);
 
+/// A value paired with an index, e.g. the rank of the process that holds the value
+///
+/// This has the memory layout of the C structs described by the MPI pair types such as
+/// `MPI_DOUBLE_INT` and is needed for reductions with `SystemOperation::min_loc()` and
+/// `SystemOperation::max_loc()`. Tuples like `(f64, i32)` convert to and from this type, but do
+/// not have a guaranteed layout themselves.
+///
+/// # Examples
+/// See `examples/reduce.rs`
+///
+/// # Standard section(s)
+///
+/// 5.9.4
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ValueIndex<T> {
+    /// The value that is compared
+    pub value: T,
+    /// The index of the value, reduced alongside it
+    pub index: c_int,
+}
+
+impl<T> From<(T, c_int)> for ValueIndex<T> {
+    fn from((value, index): (T, c_int)) -> Self {
+        ValueIndex { value, index }
+    }
+}
+
+impl<T> From<ValueIndex<T>> for (T, c_int) {
+    fn from(pair: ValueIndex<T>) -> Self {
+        (pair.value, pair.index)
+    }
+}
+
+equivalent_system_datatype!(ValueIndex<f32>, ffi::RSMPI_FLOAT_INT);
+equivalent_system_datatype!(ValueIndex<f64>, ffi::RSMPI_DOUBLE_INT);
+equivalent_system_datatype!(ValueIndex<i16>, ffi::RSMPI_SHORT_INT);
+equivalent_system_datatype!(ValueIndex<i32>, ffi::RSMPI_2INT);
+
 #[cfg(feature = "complex")]
 /// Implement direct equivalence for complex types
 pub mod complex_datatype {
@@ -240,6 +309,30 @@ pub mod complex_datatype {
     equivalent_system_datatype!(Complex64, ffi::RSMPI_DOUBLE_COMPLEX);
 }
 
+/// The storage order of a multi-dimensional array
+///
+/// # Standard section(s)
+///
+/// 4.1.3
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ArrayOrder {
+    /// The last dimension varies fastest, as in C and Rust (`MPI_ORDER_C`)
+    RowMajor,
+    /// The first dimension varies fastest, as in Fortran (`MPI_ORDER_FORTRAN`)
+    ColumnMajor,
+}
+
+impl ArrayOrder {
+    fn as_raw(self) -> c_int {
+        unsafe {
+            match self {
+                ArrayOrder::RowMajor => ffi::RSMPI_ORDER_C,
+                ArrayOrder::ColumnMajor => ffi::RSMPI_ORDER_FORTRAN,
+            }
+        }
+    }
+}
+
 /// A user defined MPI datatype
 ///
 /// # Standard section(s)
@@ -382,6 +475,25 @@ impl UserDatatype {
         UncommittedUserDatatype::structured(blocklengths, displacements, types).commit()
     }
 
+    /// Constructs a new datatype describing the `subsizes` block starting at `starts` of an
+    /// n-dimensional array of `oldtype` with extents `sizes`, stored in `order`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.1.3
+    pub fn subarray<D>(
+        sizes: &[Count],
+        subsizes: &[Count],
+        starts: &[Count],
+        order: ArrayOrder,
+        oldtype: &D,
+    ) -> UserDatatype
+    where
+        D: UncommittedDatatype,
+    {
+        UncommittedUserDatatype::subarray(sizes, subsizes, starts, order, oldtype).commit()
+    }
+
     /// Creates a DatatypeRef from this datatype object.
     pub fn as_ref(&self) -> DatatypeRef<'_> {
         unsafe { DatatypeRef::from_raw(self.as_raw()) }
@@ -693,6 +805,51 @@ impl UncommittedUserDatatype {
         }
     }
 
+    /// Constructs a new datatype describing the `subsizes` block starting at `starts` of an
+    /// n-dimensional array of `oldtype` with extents `sizes`, stored in `order`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 4.1.3
+    pub fn subarray<D>(
+        sizes: &[Count],
+        subsizes: &[Count],
+        starts: &[Count],
+        order: ArrayOrder,
+        oldtype: &D,
+    ) -> Self
+    where
+        D: UncommittedDatatype,
+    {
+        assert_eq!(
+            sizes.len(),
+            subsizes.len(),
+            "'sizes', 'subsizes', and 'starts' must be the same length"
+        );
+        assert_eq!(
+            sizes.len(),
+            starts.len(),
+            "'sizes', 'subsizes', and 'starts' must be the same length"
+        );
+
+        unsafe {
+            UncommittedUserDatatype(
+                with_uninitialized(|newtype| {
+                    ffi::MPI_Type_create_subarray(
+                        sizes.count(),
+                        sizes.as_ptr(),
+                        subsizes.as_ptr(),
+                        starts.as_ptr(),
+                        order.as_raw(),
+                        oldtype.as_raw(),
+                        newtype,
+                    )
+                })
+                .1,
+            )
+        }
+    }
+
     /// Commits a datatype to a specific representation so that it can be used in MPI calls.
     ///
     /// # Standard section(s)
@@ -779,6 +936,14 @@ pub trait UncommittedDatatype: AsRaw<Raw = MPI_Datatype> {
             )
         }
     }
+
+    /// The handle of the datatype in the Fortran bindings of MPI
+    ///
+    /// # Standard section(s)
+    /// 17.2.4
+    fn to_fortran(&self) -> Fint {
+        unsafe { ffi::RSMPI_Type_c2f(self.as_raw()) }
+    }
 }
 impl<'a, D> UncommittedDatatype for &'a D
 where
@@ -1581,6 +1746,59 @@ where
 {
 }
 
+/// Displacements of a contiguous partitioning with the given `counts`
+pub(crate) fn displacements(counts: &[Count]) -> Vec<Count> {
+    counts
+        .iter()
+        .scan(0, |acc, &count| {
+            let displ = *acc;
+            *acc += count;
+            Some(displ)
+        })
+        .collect()
+}
+
+/// Storage for a `Msg` that has not been written to yet, e.g. the element of a receive buffer.
+#[repr(transparent)]
+pub(crate) struct UninitMsg<Msg>(mem::MaybeUninit<Msg>);
+
+impl<Msg> UninitMsg<Msg> {
+    /// Storage for a single `Msg`
+    pub(crate) fn uninit() -> Self {
+        UninitMsg(mem::MaybeUninit::uninit())
+    }
+
+    /// Extracts the value after it has been written to.
+    ///
+    /// # Safety
+    /// The value must have been written to, e.g. by a completed receive operation.
+    pub(crate) unsafe fn assume_init(self) -> Msg {
+        self.0.assume_init()
+    }
+}
+
+unsafe impl<Msg: Equivalence> Equivalence for UninitMsg<Msg> {
+    type Out = Msg::Out;
+
+    fn equivalent_datatype() -> Self::Out {
+        Msg::equivalent_datatype()
+    }
+}
+
+/// Allocates a receive buffer for `len` instances of `Msg` without initializing it.
+pub(crate) fn uninit_vec<Msg>(len: usize) -> Vec<UninitMsg<Msg>> {
+    (0..len).map(|_| UninitMsg::uninit()).collect()
+}
+
+/// Converts a receive buffer from `uninit_vec()` into a `Vec` of its contents.
+///
+/// # Safety
+/// Every element of `buf` must have been written to, e.g. by a completed receive operation.
+pub(crate) unsafe fn assume_init_vec<Msg>(buf: Vec<UninitMsg<Msg>>) -> Vec<Msg> {
+    let mut buf = mem::ManuallyDrop::new(buf);
+    Vec::from_raw_parts(buf.as_mut_ptr() as *mut Msg, buf.len(), buf.capacity())
+}
+
 /// Returns the address of the argument in a format suitable for use with datatype constructors
 ///
 /// # Examples
@@ -1593,3 +1811,530 @@ pub fn address_of<T>(x: &T) -> Address {
     let x: *const T = x;
     unsafe { with_uninitialized(|address| ffi::MPI_Get_address(x as *const c_void, address)).1 }
 }
+
+/// Checks that at least `required` bytes remain in a packed buffer of length `len` after
+/// `position`.
+pub(crate) fn check_packed_remaining(
+    len: usize,
+    position: usize,
+    required: usize,
+) -> Result<(), MpiError> {
+    let remaining = len.saturating_sub(position);
+    if remaining < required {
+        Err(MpiError::PackedBufferTooShort(remaining, required))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that the packed bytes for `count` elements of `datatype` remain in a packed buffer of
+/// length `len` after `position`.
+///
+/// The elements fit if at least their `MPI_Pack_size` remains. That is only an upper bound, so if
+/// fewer bytes remain, e.g. for the last value of a buffer, the elements provided by `source` are
+/// packed into a scratch buffer to find their actual size.
+///
+/// # Safety
+/// The pointer returned by `source` must be valid for reading `count` elements of `datatype`.
+pub(crate) unsafe fn check_packed_remaining_native<Dt, F>(
+    len: usize,
+    position: usize,
+    count: Count,
+    datatype: &Dt,
+    comm: ffi::MPI_Comm,
+    source: F,
+) -> Result<(), MpiError>
+where
+    Dt: Datatype,
+    F: FnOnce() -> *const c_void,
+{
+    let bound: Count =
+        with_uninitialized(|size| ffi::MPI_Pack_size(count, datatype.as_raw(), comm, size)).1;
+    let bound_len = bound
+        .value_as::<usize>()
+        .expect("MPI_Pack_size returned a negative buffer size!");
+    if len.saturating_sub(position) >= bound_len {
+        return Ok(());
+    }
+
+    let mut scratch = vec![0u8; bound_len];
+    let mut packed: Count = 0;
+    ffi::MPI_Pack(
+        source(),
+        count,
+        datatype.as_raw(),
+        scratch.as_mut_ptr() as *mut _,
+        bound,
+        &mut packed,
+        comm,
+    );
+    check_packed_remaining(
+        len,
+        position,
+        packed
+            .value_as()
+            .expect("MPI_Pack returned a negative position!"),
+    )
+}
+
+/// The name of the portable data representation used by the `*_external` functions
+const EXTERNAL32: &[u8] = b"external32\0";
+
+fn external32() -> *const c_char {
+    EXTERNAL32.as_ptr() as *const c_char
+}
+
+/// Gets the buffer size required to pack `incount` elements of type `datatype` in the portable
+/// `"external32"` data representation.
+///
+/// # Standard section(s)
+///
+/// 4.3, see MPI_Pack_external_size
+pub fn pack_external_size<Dt>(incount: Count, datatype: &Dt) -> Address
+where
+    Dt: Datatype,
+{
+    unsafe {
+        with_uninitialized(|size| {
+            ffi::MPI_Pack_external_size(external32(), incount, datatype.as_raw(), size)
+        })
+        .1
+    }
+}
+
+/// Packs `inbuf` into a byte array in the portable `"external32"` data representation.
+///
+/// Unlike `Communicator::pack`, the result can be unpacked by any MPI implementation on any
+/// architecture, e.g. after being written to a file.
+///
+/// # Examples
+/// See `examples/pack.rs`
+///
+/// # Standard section(s)
+///
+/// 4.3, see MPI_Pack_external
+pub fn pack_external<Buf>(inbuf: &Buf) -> Vec<u8>
+where
+    Buf: ?Sized + Buffer,
+{
+    let inbuf_dt = inbuf.as_datatype();
+
+    let mut outbuf = vec![
+        0;
+        pack_external_size(inbuf.count(), &inbuf_dt)
+            .value_as::<usize>()
+            .expect("MPI_Pack_external_size returned a negative buffer size!")
+    ];
+
+    let position = pack_external_into(inbuf, &mut outbuf[..], 0);
+
+    outbuf.resize(
+        position
+            .value_as()
+            .expect("MPI_Pack_external returned a negative position!"),
+        0,
+    );
+
+    outbuf
+}
+
+/// Packs `inbuf` into `outbuf` at `position` in the portable `"external32"` data representation.
+/// Returns the position following the packed data.
+///
+/// # Standard section(s)
+///
+/// 4.3, see MPI_Pack_external
+pub fn pack_external_into<Buf>(inbuf: &Buf, outbuf: &mut [u8], position: Address) -> Address
+where
+    Buf: ?Sized + Buffer,
+{
+    let inbuf_dt = inbuf.as_datatype();
+
+    let mut position: Address = position;
+    unsafe {
+        ffi::MPI_Pack_external(
+            external32(),
+            inbuf.pointer(),
+            inbuf.count(),
+            inbuf_dt.as_raw(),
+            outbuf.as_mut_ptr() as *mut _,
+            outbuf
+                .len()
+                .value_as()
+                .expect("Length of packed buffer cannot be expressed as an MPI Address."),
+            &mut position,
+        );
+    }
+    position
+}
+
+/// Unpacks data in the `"external32"` data representation from `inbuf` at `position` into
+/// `outbuf`. Returns the position following the unpacked data.
+///
+/// Returns an error if fewer than `pack_external_size()` bytes for `outbuf` remain in `inbuf`.
+///
+/// # Examples
+/// See `examples/pack.rs`
+///
+/// # Standard section(s)
+///
+/// 4.3, see MPI_Unpack_external
+pub fn unpack_external_from<Buf>(
+    inbuf: &[u8],
+    outbuf: &mut Buf,
+    position: Address,
+) -> Result<Address, MpiError>
+where
+    Buf: ?Sized + BufferMut,
+{
+    let outbuf_dt = outbuf.as_datatype();
+    let required = pack_external_size(outbuf.count(), &outbuf_dt);
+    check_packed_remaining(
+        inbuf.len(),
+        position
+            .value_as()
+            .expect("Packed buffer position must be non-negative"),
+        required
+            .value_as()
+            .expect("MPI_Pack_external_size returned a negative buffer size!"),
+    )?;
+
+    let mut position: Address = position;
+    unsafe {
+        ffi::MPI_Unpack_external(
+            external32(),
+            inbuf.as_ptr() as *const _,
+            inbuf
+                .len()
+                .value_as()
+                .expect("Length of packed buffer cannot be expressed as an MPI Address."),
+            &mut position,
+            outbuf.pointer_mut(),
+            outbuf.count(),
+            outbuf_dt.as_raw(),
+        );
+    }
+    Ok(position)
+}
+
+/// Unpacks `count` elements of type `Msg` in the `"external32"` data representation from `inbuf`
+/// at `position` into a newly allocated `Vec`.
+///
+/// Returns the unpacked elements and the position following them.
+///
+/// # Standard section(s)
+///
+/// 4.3, see MPI_Unpack_external
+pub fn unpack_external_vec<Msg>(
+    inbuf: &[u8],
+    count: Count,
+    position: Address,
+) -> Result<(Vec<Msg>, Address), MpiError>
+where
+    Msg: Equivalence,
+{
+    let datatype = Msg::equivalent_datatype();
+    let required = pack_external_size(count, &datatype);
+    check_packed_remaining(
+        inbuf.len(),
+        position
+            .value_as()
+            .expect("Packed buffer position must be non-negative"),
+        required
+            .value_as()
+            .expect("MPI_Pack_external_size returned a negative buffer size!"),
+    )?;
+
+    let len = count
+        .value_as::<usize>()
+        .expect("Cannot unpack a negative number of elements");
+    let mut outbuf = Vec::<Msg>::with_capacity(len);
+    let mut position: Address = position;
+    unsafe {
+        ffi::MPI_Unpack_external(
+            external32(),
+            inbuf.as_ptr() as *const _,
+            inbuf
+                .len()
+                .value_as()
+                .expect("Length of packed buffer cannot be expressed as an MPI Address."),
+            &mut position,
+            outbuf.as_mut_ptr() as *mut _,
+            count,
+            datatype.as_raw(),
+        );
+        outbuf.set_len(len);
+    }
+    Ok((outbuf, position))
+}
+
+/// The data representation used by a `PackedBuffer`
+#[derive(Copy, Clone)]
+enum PackEncoding<'c> {
+    /// Implementation-defined representation tied to a communicator, see `MPI_Pack`
+    Native(&'c dyn Communicator),
+    /// The portable `"external32"` representation, see `MPI_Pack_external`
+    External32,
+}
+
+/// A growable byte buffer that packs a sequence of `Buffer`s and unpacks them again in the same
+/// order.
+///
+/// Values are appended with `append()` and read back front to back with `read_into()` or
+/// `read_vec()`. The packed bytes can be sent as a `[u8]` message and the receiving side can wrap
+/// them with `from_bytes()` (or `external32_from_bytes()`) to read them back.
+///
+/// # Examples
+/// See `examples/packed_buffer.rs`
+///
+/// # Standard section(s)
+///
+/// 4.2, 4.3
+pub struct PackedBuffer<'c> {
+    bytes: Vec<u8>,
+    read_position: usize,
+    encoding: PackEncoding<'c>,
+}
+
+impl<'c> PackedBuffer<'c> {
+    /// Creates an empty buffer that packs in the implementation-defined representation of `comm`.
+    pub fn new(comm: &'c dyn Communicator) -> Self {
+        Self::from_bytes(comm, Vec::new())
+    }
+
+    /// Wraps bytes produced by a `PackedBuffer` created with `new()` for reading.
+    pub fn from_bytes(comm: &'c dyn Communicator, bytes: Vec<u8>) -> Self {
+        PackedBuffer {
+            bytes,
+            read_position: 0,
+            encoding: PackEncoding::Native(comm),
+        }
+    }
+
+    /// Creates an empty buffer that packs in the portable `"external32"` representation.
+    pub fn external32() -> PackedBuffer<'static> {
+        PackedBuffer::external32_from_bytes(Vec::new())
+    }
+
+    /// Wraps bytes produced by a `PackedBuffer` created with `external32()` for reading.
+    pub fn external32_from_bytes(bytes: Vec<u8>) -> PackedBuffer<'static> {
+        PackedBuffer {
+            bytes,
+            read_position: 0,
+            encoding: PackEncoding::External32,
+        }
+    }
+
+    /// Packs `inbuf` at the end of the buffer.
+    pub fn append<Buf>(&mut self, inbuf: &Buf) -> &mut Self
+    where
+        Buf: ?Sized + Buffer,
+    {
+        let inbuf_dt = inbuf.as_datatype();
+        let start = self.bytes.len();
+        let size = self.packed_size(inbuf.count(), &inbuf_dt);
+        self.bytes.resize(start + size, 0);
+
+        let end = match self.encoding {
+            PackEncoding::Native(comm) => {
+                let mut position: Count = start
+                    .value_as()
+                    .expect("Packed buffer length cannot be expressed as an MPI Count.");
+                unsafe {
+                    ffi::MPI_Pack(
+                        inbuf.pointer(),
+                        inbuf.count(),
+                        inbuf_dt.as_raw(),
+                        self.bytes.as_mut_ptr() as *mut _,
+                        self.bytes.count(),
+                        &mut position,
+                        comm.as_raw(),
+                    );
+                }
+                position
+                    .value_as::<usize>()
+                    .expect("MPI_Pack returned a negative position!")
+            }
+            PackEncoding::External32 => {
+                let start = start
+                    .value_as()
+                    .expect("Packed buffer length cannot be expressed as an MPI Address.");
+                pack_external_into(inbuf, &mut self.bytes[..], start)
+                    .value_as::<usize>()
+                    .expect("MPI_Pack_external returned a negative position!")
+            }
+        };
+        self.bytes.truncate(end);
+        self
+    }
+
+    /// Unpacks the next value from the buffer into `outbuf`.
+    ///
+    /// Returns an error, without advancing, if not enough packed bytes remain.
+    pub fn read_into<Buf>(&mut self, outbuf: &mut Buf) -> Result<(), MpiError>
+    where
+        Buf: ?Sized + BufferMut,
+    {
+        let outbuf_dt = outbuf.as_datatype();
+        let ptr = outbuf.pointer_mut();
+        unsafe { self.read_raw(ptr, outbuf.count(), &outbuf_dt, || ptr as *const c_void) }
+    }
+
+    /// Unpacks the next `count` elements of type `Msg` from the buffer into a newly allocated
+    /// `Vec`.
+    ///
+    /// Returns an error, without advancing, if not enough packed bytes remain.
+    pub fn read_vec<Msg>(&mut self, count: Count) -> Result<Vec<Msg>, MpiError>
+    where
+        Msg: Equivalence,
+    {
+        let len = count
+            .value_as::<usize>()
+            .expect("Cannot unpack a negative number of elements");
+        let mut outbuf = Vec::<Msg>::with_capacity(len);
+        let ptr = outbuf.as_mut_ptr();
+        unsafe {
+            self.read_raw(
+                ptr as *mut c_void,
+                count,
+                &Msg::equivalent_datatype(),
+                || {
+                    // Only needed to measure the last value of the buffer, which is packed from
+                    // `outbuf`.
+                    ptr.write_bytes(0, len);
+                    ptr as *const c_void
+                },
+            )?;
+            outbuf.set_len(len);
+        }
+        Ok(outbuf)
+    }
+
+    /// Upper bound on the number of bytes needed to pack `count` elements of `datatype`.
+    fn packed_size<Dt>(&self, count: Count, datatype: &Dt) -> usize
+    where
+        Dt: Datatype,
+    {
+        match self.encoding {
+            PackEncoding::Native(comm) => unsafe {
+                with_uninitialized(|size| {
+                    ffi::MPI_Pack_size(count, datatype.as_raw(), comm.as_raw(), size)
+                })
+                .1
+            }
+            .value_as()
+            .expect("MPI_Pack_size returned a negative buffer size!"),
+            PackEncoding::External32 => pack_external_size(count, datatype)
+                .value_as()
+                .expect("MPI_Pack_external_size returned a negative buffer size!"),
+        }
+    }
+
+    /// Unpacks `count` elements of `datatype` at the read position into `outbuf`.
+    ///
+    /// # Safety
+    /// `outbuf` must be valid for writing `count` elements of `datatype`, and the pointer returned
+    /// by `source` for reading them. `source` is only called to measure the last value of the
+    /// buffer, see `check_packed_remaining_native()`.
+    unsafe fn read_raw<Dt, F>(
+        &mut self,
+        outbuf: *mut c_void,
+        count: Count,
+        datatype: &Dt,
+        source: F,
+    ) -> Result<(), MpiError>
+    where
+        Dt: Datatype,
+        F: FnOnce() -> *const c_void,
+    {
+        match self.encoding {
+            PackEncoding::Native(comm) => check_packed_remaining_native(
+                self.bytes.len(),
+                self.read_position,
+                count,
+                datatype,
+                comm.as_raw(),
+                source,
+            )?,
+            PackEncoding::External32 => check_packed_remaining(
+                self.bytes.len(),
+                self.read_position,
+                self.packed_size(count, datatype),
+            )?,
+        }
+
+        let end = match self.encoding {
+            PackEncoding::Native(comm) => {
+                let mut position: Count = self
+                    .read_position
+                    .value_as()
+                    .expect("Packed buffer length cannot be expressed as an MPI Count.");
+                ffi::MPI_Unpack(
+                    self.bytes.as_ptr() as *const _,
+                    self.bytes.count(),
+                    &mut position,
+                    outbuf,
+                    count,
+                    datatype.as_raw(),
+                    comm.as_raw(),
+                );
+                position
+                    .value_as::<usize>()
+                    .expect("MPI_Unpack returned a negative position!")
+            }
+            PackEncoding::External32 => {
+                let mut position: Address = self
+                    .read_position
+                    .value_as()
+                    .expect("Packed buffer length cannot be expressed as an MPI Address.");
+                ffi::MPI_Unpack_external(
+                    external32(),
+                    self.bytes.as_ptr() as *const _,
+                    self.bytes
+                        .len()
+                        .value_as()
+                        .expect("Length of packed buffer cannot be expressed as an MPI Address."),
+                    &mut position,
+                    outbuf,
+                    count,
+                    datatype.as_raw(),
+                );
+                position
+                    .value_as::<usize>()
+                    .expect("MPI_Unpack_external returned a negative position!")
+            }
+        };
+        self.read_position = end;
+        Ok(())
+    }
+
+    /// Moves the read position back to the start of the buffer.
+    pub fn rewind(&mut self) {
+        self.read_position = 0;
+    }
+
+    /// The number of packed bytes that have not been read yet.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.read_position
+    }
+
+    /// The total number of packed bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether nothing has been packed into the buffer.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// The packed bytes, suitable for sending as a `[u8]` message.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Consumes the buffer, returning the packed bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
@@ -0,0 +1,703 @@
+//! Detecting deadlocks and mismatched collective calls
+//!
+//! With the `debug-checks` feature, every blocking point to point, collective and request
+//! completion call made through this crate is registered while it runs, together with its
+//! routine, peer, tag and communicator name. The registered calls of this process are returned by
+//! `outstanding()`.
+//!
+//! A watchdog thread started during initialization checks the registered calls periodically. Once
+//! a call has been outstanding for longer than `timeout()`, the watchdogs of all processes gather
+//! their outstanding calls over a communicator duplicated from the world communicator during
+//! initialization, and the process with rank 0 prints a `Report` to standard error. The report
+//! contains the wait-for graph between the processes, its cycles, and collective calls on the
+//! same communicator that differ in their routine or root. Every stuck call triggers one report.
+//!
+//! Gathering the state of the other processes from the watchdog thread requires
+//! `Threading::Multiple`. At lower threading levels, each process reports its own outstanding
+//! calls only.
+//!
+//! Registering calls involves a few local MPI calls, so the checks are meant for debugging.
+//!
+//! # Examples
+//!
+//! See `examples/debug_checks.rs`
+
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt, ptr,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use conv::ConvUtil;
+
+use crate::{
+    datatype::traits::*,
+    environment::Threading,
+    ffi::{self, MPI_Comm, MPI_Group},
+    instrument::{Call, Category},
+    with_uninitialized, Rank, Tag,
+};
+
+static TIMEOUT: Mutex<Duration> = Mutex::new(Duration::from_secs(60));
+
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    next_id: 0,
+    entries: Vec::new(),
+});
+
+static WATCHDOG: Mutex<Option<Watchdog>> = Mutex::new(None);
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .expect("rsmpi internal error: debug-checks lock poisoned")
+}
+
+/// Sets how long a call may be outstanding before the state of all processes is reported.
+///
+/// The default is 60 seconds.
+pub fn set_timeout(timeout: Duration) {
+    *lock(&TIMEOUT) = timeout;
+}
+
+/// How long a call may be outstanding before the state of all processes is reported
+pub fn timeout() -> Duration {
+    *lock(&TIMEOUT)
+}
+
+/// The blocking calls of this process that have not returned yet, in the order they were made
+pub fn outstanding() -> Vec<Operation> {
+    let now = Instant::now();
+    lock(&REGISTRY)
+        .entries
+        .iter()
+        .map(|entry| entry.snapshot(now))
+        .collect()
+}
+
+/// The family of MPI routines an outstanding call belongs to
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum OperationKind {
+    /// A point to point call, e.g. `MPI_Recv`
+    PointToPoint,
+    /// A collective call, e.g. `MPI_Bcast`
+    Collective,
+    /// A call completing requests, e.g. `MPI_Wait`
+    Request,
+}
+
+/// A blocking call that has not returned yet
+#[derive(Clone, Debug, PartialEq)]
+pub struct Operation {
+    /// The rank of the calling process in the world communicator
+    pub rank: Rank,
+    /// The MPI routine that was called, e.g. `MPI_Recv`
+    pub routine: String,
+    /// The family of the routine
+    pub kind: OperationKind,
+    /// The rank in the world communicator of the other process of a point to point call, or of
+    /// the root of a collective call
+    pub peer: Option<Rank>,
+    /// The tag of a point to point call
+    pub tag: Option<Tag>,
+    /// The name of the communicator, see `Communicator::get_name()`
+    pub communicator: Option<String>,
+    /// The ranks in the world communicator of the processes taking part in a collective call,
+    /// in increasing order
+    pub members: Vec<Rank>,
+    /// How long the call has been outstanding
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rank {}: {}", self.rank, self.routine)?;
+        if let Some(peer) = self.peer {
+            match self.kind {
+                OperationKind::Collective => write!(f, " root {}", peer)?,
+                _ => write!(f, " peer {}", peer)?,
+            }
+        }
+        if let Some(tag) = self.tag {
+            write!(f, " tag {}", tag)?;
+        }
+        if let Some(ref comm) = self.communicator {
+            write!(f, " on {}", comm)?;
+        }
+        write!(f, " for {:.1} s", self.elapsed.as_secs_f64())
+    }
+}
+
+/// Collective calls on the same communicator that differ in their routine or root
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    /// The name of the communicator
+    pub communicator: Option<String>,
+    /// The outstanding collective calls on the communicator, one per process
+    pub calls: Vec<Operation>,
+}
+
+/// The outstanding calls of several processes, analysed for deadlocks
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    operations: Vec<Operation>,
+    edges: BTreeSet<(Rank, Rank)>,
+    cycles: Vec<Vec<Rank>>,
+    mismatches: Vec<Mismatch>,
+}
+
+impl Report {
+    /// Analyses the outstanding calls of several processes.
+    ///
+    /// A process waits for the peer of an outstanding point to point call, and for every member
+    /// of a collective call that has no outstanding collective call on the same communicator.
+    /// Calls completing requests and receives from any source do not name the process they wait
+    /// for and add no edges to the wait-for graph.
+    pub fn new(operations: Vec<Operation>) -> Report {
+        let mut edges = BTreeSet::new();
+        let mut collectives: BTreeMap<(&Option<String>, &[Rank]), Vec<&Operation>> =
+            BTreeMap::new();
+        for op in &operations {
+            match op.kind {
+                OperationKind::PointToPoint => {
+                    if let Some(peer) = op.peer.filter(|&peer| peer != op.rank) {
+                        edges.insert((op.rank, peer));
+                    }
+                }
+                OperationKind::Collective => collectives
+                    .entry((&op.communicator, &op.members[..]))
+                    .or_default()
+                    .push(op),
+                OperationKind::Request => {}
+            }
+        }
+
+        let mut mismatches = Vec::new();
+        for ((communicator, members), calls) in collectives {
+            let arrived: BTreeSet<Rank> = calls.iter().map(|op| op.rank).collect();
+            for op in &calls {
+                for &member in members {
+                    if !arrived.contains(&member) {
+                        edges.insert((op.rank, member));
+                    }
+                }
+            }
+            let variants: BTreeSet<(&str, Option<Rank>)> = calls
+                .iter()
+                .map(|op| (op.routine.as_str(), op.peer))
+                .collect();
+            if variants.len() > 1 {
+                mismatches.push(Mismatch {
+                    communicator: communicator.clone(),
+                    calls: calls.into_iter().cloned().collect(),
+                });
+            }
+        }
+
+        let cycles = find_cycles(&edges);
+        Report {
+            operations,
+            edges,
+            cycles,
+            mismatches,
+        }
+    }
+
+    /// The outstanding calls the report is based on
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    /// The edges `(waiting, awaited)` of the wait-for graph between the ranks in the world
+    /// communicator, in increasing order
+    pub fn edges(&self) -> impl Iterator<Item = (Rank, Rank)> + '_ {
+        self.edges.iter().copied()
+    }
+
+    /// One cycle for each group of processes that wait for each other, starting at the lowest
+    /// rank of the group
+    pub fn cycles(&self) -> &[Vec<Rank>] {
+        &self.cycles
+    }
+
+    /// The collective calls that do not match across processes
+    pub fn mismatches(&self) -> &[Mismatch] {
+        &self.mismatches
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "rsmpi: outstanding calls")?;
+        for op in &self.operations {
+            writeln!(f, "  {}", op)?;
+        }
+        if !self.edges.is_empty() {
+            writeln!(f, "rsmpi: wait-for graph")?;
+            for (waiting, awaited) in &self.edges {
+                writeln!(f, "  {} -> {}", waiting, awaited)?;
+            }
+        }
+        for cycle in &self.cycles {
+            let path: Vec<String> = cycle
+                .iter()
+                .chain(cycle.first())
+                .map(Rank::to_string)
+                .collect();
+            writeln!(f, "rsmpi: deadlock {}", path.join(" -> "))?;
+        }
+        for mismatch in &self.mismatches {
+            write!(f, "rsmpi: mismatched collective calls")?;
+            if let Some(ref comm) = mismatch.communicator {
+                write!(f, " on {}", comm)?;
+            }
+            writeln!(f)?;
+            for op in &mismatch.calls {
+                writeln!(f, "  {}", op)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Finds one cycle in every strongly connected component with more than one process.
+fn find_cycles(edges: &BTreeSet<(Rank, Rank)>) -> Vec<Vec<Rank>> {
+    let mut successors: BTreeMap<Rank, Vec<Rank>> = BTreeMap::new();
+    let mut predecessors: BTreeMap<Rank, Vec<Rank>> = BTreeMap::new();
+    for &(from, to) in edges {
+        successors.entry(from).or_default().push(to);
+        predecessors.entry(to).or_default().push(from);
+    }
+    let reachable = |start: Rank, graph: &BTreeMap<Rank, Vec<Rank>>| {
+        let mut seen = BTreeSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(rank) = stack.pop() {
+            for &next in graph.get(&rank).into_iter().flatten() {
+                if seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    };
+
+    let mut assigned = BTreeSet::new();
+    let mut cycles = Vec::new();
+    for &start in successors.keys() {
+        if assigned.contains(&start) {
+            continue;
+        }
+        let component: BTreeSet<Rank> = reachable(start, &successors)
+            .intersection(&reachable(start, &predecessors))
+            .copied()
+            .collect();
+        assigned.extend(component.iter().copied());
+        if component.len() < 2 {
+            continue;
+        }
+
+        // The shortest path back to `start` within the component
+        let mut parent = BTreeMap::new();
+        let mut queue = VecDeque::from([start]);
+        'search: while let Some(rank) = queue.pop_front() {
+            for &next in &successors[&rank] {
+                if next == start {
+                    let mut cycle = vec![rank];
+                    while let Some(&previous) = parent.get(cycle.last().unwrap_or(&start)) {
+                        cycle.push(previous);
+                    }
+                    cycle.reverse();
+                    cycles.push(cycle);
+                    break 'search;
+                }
+                if component.contains(&next) && !parent.contains_key(&next) {
+                    parent.insert(next, rank);
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    cycles
+}
+
+struct Registry {
+    next_id: u64,
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    id: u64,
+    operation: Operation,
+    start: Instant,
+    reported: bool,
+}
+
+impl Entry {
+    fn snapshot(&self, now: Instant) -> Operation {
+        Operation {
+            elapsed: now.duration_since(self.start),
+            ..self.operation.clone()
+        }
+    }
+}
+
+/// Registers a blocking call as outstanding until dropped
+pub(crate) struct Outstanding {
+    id: u64,
+}
+
+impl Outstanding {
+    pub(crate) fn new(call: &Call) -> Outstanding {
+        let kind = match call.category {
+            Category::PointToPoint => OperationKind::PointToPoint,
+            Category::Collective => OperationKind::Collective,
+            Category::Request => OperationKind::Request,
+        };
+        let (peer, members) = call.comm.map_or((None, Vec::new()), |comm| unsafe {
+            locate(comm, call.peer, kind == OperationKind::Collective)
+        });
+        let operation = Operation {
+            rank: world_rank(),
+            routine: call.name.to_string(),
+            kind,
+            peer,
+            tag: call.tag,
+            communicator: call.comm_name(),
+            members,
+            elapsed: Duration::ZERO,
+        };
+
+        let mut registry = lock(&REGISTRY);
+        let id = registry.next_id;
+        registry.next_id += 1;
+        registry.entries.push(Entry {
+            id,
+            operation,
+            start: Instant::now(),
+            reported: false,
+        });
+        Outstanding { id }
+    }
+}
+
+impl Drop for Outstanding {
+    fn drop(&mut self) {
+        // Never panic here, the call may be unwinding already.
+        if let Ok(mut registry) = REGISTRY.lock() {
+            registry.entries.retain(|entry| entry.id != self.id);
+        }
+    }
+}
+
+fn world_rank() -> Rank {
+    unsafe { with_uninitialized(|rank| ffi::MPI_Comm_rank(ffi::RSMPI_COMM_WORLD, rank)).1 }
+}
+
+/// Translates `peer`, a rank in the (remote) group of `comm`, to a rank in the world
+/// communicator, and lists the ranks in the world communicator of all processes in `comm` for a
+/// collective call.
+unsafe fn locate(
+    comm: MPI_Comm,
+    peer: Option<Rank>,
+    collective: bool,
+) -> (Option<Rank>, Vec<Rank>) {
+    let inter = with_uninitialized(|flag| ffi::MPI_Comm_test_inter(comm, flag)).1 != 0;
+    let mut local = with_uninitialized(|group| ffi::MPI_Comm_group(comm, group)).1;
+    let mut remote = if inter {
+        with_uninitialized(|group| ffi::MPI_Comm_remote_group(comm, group)).1
+    } else {
+        local
+    };
+
+    // Roots of intercommunicator collectives are given as `MPI_ROOT` or `MPI_PROC_NULL`.
+    let peer = peer
+        .filter(|&peer| peer >= 0 && peer < group_size(remote))
+        .and_then(|peer| to_world(remote, &[peer]).pop());
+    let mut members = Vec::new();
+    if collective {
+        members.extend(to_world(local, &(0..group_size(local)).collect::<Vec<_>>()));
+        if inter {
+            members.extend(to_world(
+                remote,
+                &(0..group_size(remote)).collect::<Vec<_>>(),
+            ));
+        }
+        members.sort_unstable();
+    }
+
+    if inter {
+        ffi::MPI_Group_free(&mut remote);
+    }
+    ffi::MPI_Group_free(&mut local);
+    (peer, members)
+}
+
+unsafe fn group_size(group: MPI_Group) -> Rank {
+    with_uninitialized(|size| ffi::MPI_Group_size(group, size)).1
+}
+
+/// Translates `ranks` in `group` to ranks in the world communicator, leaving out processes that
+/// are not part of it.
+unsafe fn to_world(group: MPI_Group, ranks: &[Rank]) -> Vec<Rank> {
+    let mut world = with_uninitialized(|world| ffi::MPI_Comm_group(ffi::RSMPI_COMM_WORLD, world)).1;
+    let mut translated = vec![0; ranks.len()];
+    ffi::MPI_Group_translate_ranks(
+        group,
+        ranks.count(),
+        ranks.as_ptr(),
+        world,
+        translated.as_mut_ptr(),
+    );
+    ffi::MPI_Group_free(&mut world);
+    translated.retain(|&rank| rank != ffi::RSMPI_UNDEFINED);
+    translated
+}
+
+/// Marks the calls that have been outstanding for longer than `timeout` as reported and returns
+/// whether there were any that had not been reported before.
+fn newly_stuck(timeout: Duration) -> bool {
+    let now = Instant::now();
+    let mut stuck = false;
+    for entry in &mut lock(&REGISTRY).entries {
+        if !entry.reported && now.duration_since(entry.start) >= timeout {
+            entry.reported = true;
+            stuck = true;
+        }
+    }
+    stuck
+}
+
+struct Watchdog {
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    thread: JoinHandle<()>,
+}
+
+/// The communicator used by the watchdog threads
+struct SideChannel(MPI_Comm);
+
+// The side channel is only ever used by the watchdog thread.
+unsafe impl Send for SideChannel {}
+
+/// Starts the watchdog thread. Called right after MPI has been initialized with the `provided`
+/// threading level.
+pub(crate) fn start(provided: Threading) {
+    let side = (provided == Threading::Multiple).then(|| unsafe {
+        SideChannel(with_uninitialized(|comm| ffi::MPI_Comm_dup(ffi::RSMPI_COMM_WORLD, comm)).1)
+    });
+    let stop = Arc::new((Mutex::new(false), Condvar::new()));
+    let thread = {
+        let stop = Arc::clone(&stop);
+        thread::Builder::new()
+            .name("rsmpi-debug-checks".to_string())
+            .spawn(move || watch(side, &stop))
+            .expect("Failed to start the debug-checks watchdog thread")
+    };
+    *lock(&WATCHDOG) = Some(Watchdog { stop, thread });
+}
+
+/// Stops the watchdog thread. Called while the `Universe` is dropped, before MPI is finalized.
+pub(crate) fn finalize() {
+    let watchdog = lock(&WATCHDOG).take();
+    if let Some(watchdog) = watchdog {
+        *lock(&watchdog.stop.0) = true;
+        watchdog.stop.1.notify_one();
+        if watchdog.thread.join().is_err() {
+            eprintln!("rsmpi: the debug-checks watchdog thread panicked");
+        }
+    }
+}
+
+fn watch(side: Option<SideChannel>, stop: &(Mutex<bool>, Condvar)) {
+    loop {
+        let timeout = timeout();
+        let stopping = *stop
+            .1
+            .wait_timeout_while(
+                lock(&stop.0),
+                timeout.min(Duration::from_secs(1)),
+                |stopping| !*stopping,
+            )
+            .expect("rsmpi internal error: debug-checks lock poisoned")
+            .0;
+        let stuck = newly_stuck(timeout);
+
+        let Some(SideChannel(comm)) = side else {
+            if stuck {
+                eprint!("{}", Report::new(outstanding()));
+            }
+            if stopping {
+                return;
+            }
+            continue;
+        };
+        // Every round starts with all watchdogs agreeing whether to report and whether to stop,
+        // so that they take part in the same collective calls on the side channel.
+        let (any_stuck, all_stopping) = unsafe { agree(comm, stuck, stopping) };
+        if any_stuck {
+            if let Some(operations) = unsafe { gather(comm) } {
+                eprint!("{}", Report::new(operations));
+            }
+        }
+        if all_stopping {
+            let mut comm = comm;
+            unsafe {
+                ffi::MPI_Comm_free(&mut comm);
+            }
+            return;
+        }
+    }
+}
+
+unsafe fn agree(comm: MPI_Comm, stuck: bool, stopping: bool) -> (bool, bool) {
+    let flags = [i32::from(stuck), i32::from(!stopping)];
+    let mut result = [0i32; 2];
+    ffi::MPI_Allreduce(
+        flags.as_ptr().cast(),
+        result.as_mut_ptr().cast(),
+        flags.count(),
+        ffi::RSMPI_INT32_T,
+        ffi::RSMPI_MAX,
+        comm,
+    );
+    (result[0] != 0, result[1] == 0)
+}
+
+/// Gathers the outstanding calls of all processes at rank 0 of `comm`, which returns them.
+unsafe fn gather(comm: MPI_Comm) -> Option<Vec<Operation>> {
+    let rank = with_uninitialized(|rank| ffi::MPI_Comm_rank(comm, rank)).1;
+    let size = with_uninitialized(|size| ffi::MPI_Comm_size(comm, size)).1;
+    let local = encode(&outstanding());
+    let count = local.count();
+
+    let mut counts = vec![
+        0;
+        if rank == 0 {
+            size.value_as().unwrap_or(0)
+        } else {
+            0
+        }
+    ];
+    ffi::MPI_Gather(
+        ptr::addr_of!(count).cast(),
+        1,
+        ffi::RSMPI_INT32_T,
+        counts.as_mut_ptr().cast(),
+        1,
+        ffi::RSMPI_INT32_T,
+        0,
+        comm,
+    );
+    let displs: Vec<i32> = counts
+        .iter()
+        .scan(0, |offset, &count| {
+            let displ = *offset;
+            *offset += count;
+            Some(displ)
+        })
+        .collect();
+    let total = counts.iter().sum::<i32>().value_as().unwrap_or(0);
+    let mut all = vec![0u8; total];
+    ffi::MPI_Gatherv(
+        local.as_ptr().cast(),
+        count,
+        ffi::RSMPI_UINT8_T,
+        all.as_mut_ptr().cast(),
+        counts.as_ptr(),
+        displs.as_ptr(),
+        ffi::RSMPI_UINT8_T,
+        0,
+        comm,
+    );
+
+    (rank == 0).then(|| decode(&all))
+}
+
+fn encode(operations: &[Operation]) -> Vec<u8> {
+    fn put_i32(buf: &mut Vec<u8>, value: i32) {
+        buf.extend_from_slice(&value.to_le_bytes());
+    }
+    fn put_option(buf: &mut Vec<u8>, value: Option<i32>) {
+        buf.push(u8::from(value.is_some()));
+        put_i32(buf, value.unwrap_or(0));
+    }
+    fn put_str(buf: &mut Vec<u8>, value: &str) {
+        put_i32(buf, value.len().value_as().unwrap_or(0));
+        buf.extend_from_slice(value.as_bytes());
+    }
+
+    let mut buf = Vec::new();
+    for op in operations {
+        put_i32(&mut buf, op.rank);
+        put_str(&mut buf, &op.routine);
+        buf.push(match op.kind {
+            OperationKind::PointToPoint => 0,
+            OperationKind::Collective => 1,
+            OperationKind::Request => 2,
+        });
+        put_option(&mut buf, op.peer);
+        put_option(&mut buf, op.tag);
+        buf.push(u8::from(op.communicator.is_some()));
+        put_str(&mut buf, op.communicator.as_deref().unwrap_or(""));
+        put_i32(&mut buf, op.members.count());
+        for &member in &op.members {
+            put_i32(&mut buf, member);
+        }
+        buf.extend_from_slice(&op.elapsed.as_secs_f64().to_le_bytes());
+    }
+    buf
+}
+
+fn decode(mut buf: &[u8]) -> Vec<Operation> {
+    fn take<const N: usize>(buf: &mut &[u8]) -> [u8; N] {
+        let (head, tail) = buf.split_at(N);
+        *buf = tail;
+        head.try_into()
+            .expect("rsmpi internal error: malformed debug-checks state")
+    }
+    fn take_i32(buf: &mut &[u8]) -> i32 {
+        i32::from_le_bytes(take(buf))
+    }
+    fn take_option(buf: &mut &[u8]) -> Option<i32> {
+        let [some] = take::<1>(buf);
+        let value = take_i32(buf);
+        (some != 0).then_some(value)
+    }
+    fn take_str(buf: &mut &[u8]) -> String {
+        let len = take_i32(buf).value_as().unwrap_or(0);
+        let (head, tail) = buf.split_at(len);
+        *buf = tail;
+        String::from_utf8_lossy(head).into_owned()
+    }
+
+    let mut operations = Vec::new();
+    while !buf.is_empty() {
+        let rank = take_i32(&mut buf);
+        let routine = take_str(&mut buf);
+        let kind = match take::<1>(&mut buf) {
+            [0] => OperationKind::PointToPoint,
+            [1] => OperationKind::Collective,
+            _ => OperationKind::Request,
+        };
+        let peer = take_option(&mut buf);
+        let tag = take_option(&mut buf);
+        let [named] = take::<1>(&mut buf);
+        let communicator = Some(take_str(&mut buf)).filter(|_| named != 0);
+        let members = (0..take_i32(&mut buf))
+            .map(|_| take_i32(&mut buf))
+            .collect();
+        let elapsed = Duration::from_secs_f64(f64::from_le_bytes(take(&mut buf)));
+        operations.push(Operation {
+            rank,
+            routine,
+            kind,
+            peer,
+            tag,
+            communicator,
+            members,
+            elapsed,
+        });
+    }
+    operations
+}
@@ -4,16 +4,16 @@
 //!
 //! # Unfinished features
 //!
-//! - **8.1.2**: `MPI_TAG_UB`, ...
-//! - **8.2**: Memory allocation
 //! - **8.3, 8.4, and 8.5**: Error handling
 
 use std::{
+    cell::Cell,
     cmp::Ordering,
+    mem,
     os::raw::{c_char, c_double, c_int, c_void},
-    ptr,
+    panic, ptr,
     string::FromUtf8Error,
-    sync::RwLock,
+    sync::{Mutex, RwLock},
     thread::{self, ThreadId},
 };
 
@@ -21,17 +21,17 @@ use conv::ConvUtil;
 use once_cell::sync::Lazy;
 
 use crate::{
-    attribute::{AppNum, UniverseSize},
+    attribute::{AppNum, Host, IoRank, LastUsedCode, TagUpperBound, UniverseSize, WtimeIsGlobal},
     ffi,
+    threading::{ThreadLevel, Token},
     topology::{traits::AnyCommunicator, Communicator, InterCommunicator, SimpleCommunicator},
     traits::{AsRaw, FromRaw},
-    with_uninitialized, with_uninitialized2,
+    with_uninitialized, with_uninitialized2, Error, MpiError, Rank, Tag,
 };
 
 /// Internal data structure used to uphold certain MPI invariants.
-/// State is currently only used with the derive feature.
+/// State is currently used with the derive feature and to check calls at low threading levels.
 pub(crate) struct UniverseState {
-    #[allow(unused)]
     pub main_thread: ThreadId,
 }
 
@@ -41,9 +41,41 @@ pub(crate) static UNIVERSE_STATE: Lazy<RwLock<Option<UniverseState>>> =
 /// Global context
 pub struct Universe {
     buffer: Option<Vec<u8>>,
+    /// Whether MPI is finalized with the universe, i.e. whether rsmpi initialized it
+    finalize: bool,
 }
 
 impl Universe {
+    /// The `Universe` of MPI initialized by other code, e.g. a C library used by the application
+    ///
+    /// The calling thread is taken as the thread that initialized MPI. Dropping the universe
+    /// releases what rsmpi allocated, but does not finalize MPI, which is left to the code that
+    /// initialized it.
+    ///
+    /// # Errors
+    ///
+    /// - `InitError::NotInitialized` if MPI has not been initialized
+    /// - `InitError::AlreadyFinalized` if MPI has been finalized
+    /// - `InitError::AlreadyInitialized` if another `Universe` exists
+    ///
+    /// # Examples
+    /// See `examples/from_existing.rs`
+    pub fn from_existing() -> Result<Universe, InitError> {
+        let mut universe_state = UNIVERSE_STATE
+            .write()
+            .expect("rsmpi internal error: UNIVERSE_STATE lock poisoned");
+
+        if is_finalized() {
+            Err(InitError::AlreadyFinalized)
+        } else if !is_initialized() {
+            Err(InitError::NotInitialized)
+        } else if universe_state.is_some() {
+            Err(InitError::AlreadyInitialized)
+        } else {
+            Ok(start(&mut universe_state, threading_support(), false))
+        }
+    }
+
     /// The 'world communicator'
     ///
     /// Contains all processes initially partaking in the computation.
@@ -81,6 +113,83 @@ impl Universe {
         self.world().get_attr::<AppNum>().map(isize::from)
     }
 
+    /// The largest tag value that can be used in point-to-point communication
+    ///
+    /// The standard guarantees at least 32767.
+    ///
+    /// # Examples
+    /// See `examples/env_inq.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 8.1.2
+    pub fn tag_upper_bound(&self) -> Tag {
+        self.world()
+            .get_attr::<TagUpperBound>()
+            .map(Tag::from)
+            .expect("MPI_TAG_UB is not attached to MPI_COMM_WORLD")
+    }
+
+    /// The rank of the host process in the world communicator, if there is one
+    ///
+    /// # Standard section(s)
+    ///
+    /// 8.1.2
+    pub fn host(&self) -> Option<Rank> {
+        self.world()
+            .get_attr::<Host>()
+            .and_then(Option::<Rank>::from)
+    }
+
+    /// Which process of the world communicator can provide language-standard I/O facilities
+    ///
+    /// Processes may receive different answers.
+    ///
+    /// # Examples
+    /// See `examples/env_inq.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 8.1.2
+    pub fn io_rank(&self) -> IoProcess {
+        self.world()
+            .get_attr::<IoRank>()
+            .map_or(IoProcess::None, IoProcess::from)
+    }
+
+    /// Whether the clocks of all processes are synchronized, so that `time()` can be compared
+    /// across processes
+    ///
+    /// # Standard section(s)
+    ///
+    /// 8.1.2
+    pub fn wtime_is_global(&self) -> bool {
+        self.world()
+            .get_attr::<WtimeIsGlobal>()
+            .is_some_and(|g| bool::from(g))
+    }
+
+    /// The largest error code in use, including error codes added with `MPI_Add_error_code()`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 8.5
+    pub fn last_used_code(&self) -> Error {
+        self.world()
+            .get_attr::<LastUsedCode>()
+            .map(Error::from)
+            .expect("MPI_LASTUSEDCODE is not attached to MPI_COMM_WORLD")
+    }
+
+    /// A token proving that MPI provides at least the level of multithreading support `L`, or
+    /// `None` if it does not
+    ///
+    /// # Examples
+    /// See `examples/thread_levels.rs`
+    pub fn token<L: ThreadLevel>(&self) -> Option<Token<L>> {
+        Token::new()
+    }
+
     /// The size in bytes of the buffer used for buffered communication.
     pub fn buffer_size(&self) -> usize {
         self.buffer.as_ref().map_or(0, Vec::len)
@@ -156,27 +265,169 @@ impl Universe {
             unsafe { ffi::MPI_Comm_free_keyval(&mut k) };
         }
     }
-}
 
-impl Drop for Universe {
-    fn drop(&mut self) {
+    /// Finalizes MPI, like dropping the `Universe`, and returns an error if `MPI_Finalize()`
+    /// reports one. A universe from `Universe::from_existing()` leaves MPI initialized.
+    ///
+    /// MPI reports errors through the error handler of the world communicator, which aborts
+    /// by default, so errors are only returned if it has been changed.
+    ///
+    /// # Examples
+    /// See `examples/finalize.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 8.7
+    pub fn finalize(mut self) -> Result<(), MpiError> {
+        let code = self.shutdown();
+        // Everything the universe holds has been released by `shutdown()`.
+        mem::forget(self);
+        if code == ffi::MPI_SUCCESS as c_int {
+            Ok(())
+        } else {
+            Err(MpiError::Call("MPI_Finalize", code))
+        }
+    }
+
+    fn shutdown(&mut self) -> Error {
+        // Finalize hooks may use the universe state, e.g. through `#[derive(Equivalence)]`.
+        run_finalize_hooks();
+
         // This can only ever be called once since it's only possible to initialize a single
         // Universe per application run.
         //
         // NOTE: The write lock is taken to prevent racing with `#[derive(Equivalence)]`
-        let mut _universe_state = UNIVERSE_STATE
+        let mut universe_state = UNIVERSE_STATE
             .write()
             .expect("rsmpi internal error: UNIVERSE_STATE lock poisoned");
 
+        #[cfg(feature = "debug-checks")]
+        crate::debug_checks::finalize();
+        #[cfg(feature = "profiling")]
+        crate::profiling::finalize();
         self.detach_buffer();
         self.disconnect_parent();
         self.free_attribute_keys();
-        unsafe {
-            ffi::MPI_Finalize();
+        crate::threading::finalized();
+        if self.finalize {
+            unsafe { ffi::MPI_Finalize() }
+        } else {
+            // MPI stays initialized, so `Universe::from_existing()` may be called again.
+            *universe_state = None;
+            ffi::MPI_SUCCESS as Error
+        }
+    }
+}
+
+impl Drop for Universe {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+type FinalizeHook = Box<dyn FnOnce() + Send>;
+
+static FINALIZE_HOOKS: Mutex<Vec<FinalizeHook>> = Mutex::new(Vec::new());
+
+/// Registers `callback` to be called when the `Universe` is dropped or finalized.
+///
+/// Callbacks run in the reverse order of their registration, before any resources held by the
+/// `Universe` are released, so they can still make MPI calls, e.g. to free objects or flush
+/// pending communication.
+///
+/// # Examples
+/// See `examples/finalize.rs`
+pub fn on_finalize<F>(callback: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    FINALIZE_HOOKS
+        .lock()
+        .expect("rsmpi internal error: FINALIZE_HOOKS lock poisoned")
+        .push(Box::new(callback));
+}
+
+fn run_finalize_hooks() {
+    // Callbacks may register further callbacks, so the lock is not held while they run.
+    loop {
+        let hook = FINALIZE_HOOKS
+            .lock()
+            .expect("rsmpi internal error: FINALIZE_HOOKS lock poisoned")
+            .pop();
+        match hook {
+            Some(hook) => hook(),
+            None => break,
         }
     }
 }
 
+thread_local! {
+    /// Whether panics on this thread are caught by `catch_panics()`
+    static CATCHING_PANICS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` and catches its panics, which the hook of `abort_on_panic()` then leaves to the
+/// caller to report instead of aborting all processes.
+#[cfg_attr(not(feature = "serde"), allow(dead_code))]
+pub(crate) fn catch_panics<F, R>(f: F) -> thread::Result<R>
+where
+    F: FnOnce() -> R + panic::UnwindSafe,
+{
+    let catching = CATCHING_PANICS.with(|c| c.replace(true));
+    let result = panic::catch_unwind(f);
+    CATCHING_PANICS.with(|c| c.set(catching));
+    result
+}
+
+/// Installs a panic hook that aborts all processes with `error_code` when any thread of this
+/// process panics.
+///
+/// Without it, a panic ends only the process that panicked, and the other processes wait forever
+/// in their next communication with it. The hook prints the panic message prefixed with the rank
+/// in the world communicator and calls `MPI_Abort()` on the world communicator. Panics before
+/// MPI is initialized or after it is finalized are passed on to the previous hook, as are panics
+/// that rsmpi catches and reports itself, e.g. those of active message handlers.
+///
+/// # Examples
+/// See `examples/finalize.rs`
+///
+/// # Standard section(s)
+///
+/// 8.7
+pub fn abort_on_panic(error_code: c_int) {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !is_initialized() || is_finalized() || CATCHING_PANICS.with(Cell::get) {
+            previous(info);
+            return;
+        }
+        let rank =
+            unsafe { with_uninitialized(|rank| ffi::MPI_Comm_rank(ffi::RSMPI_COMM_WORLD, rank)).1 };
+        let thread = thread::current();
+        let message = format!("thread '{}' {}", thread.name().unwrap_or("<unnamed>"), info);
+        for line in message.lines() {
+            eprintln!("[rank {}] {}", rank, line);
+        }
+        SimpleCommunicator::world().abort(error_code);
+    }));
+}
+
+/// The processes that can provide language-standard I/O facilities, see `Universe::io_rank()`
+///
+/// # Standard section(s)
+///
+/// 8.1.2
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum IoProcess {
+    /// No process can perform I/O
+    None,
+    /// Every process can perform I/O
+    Any,
+    /// The process with this rank in the world communicator can perform I/O, possibly among
+    /// others
+    Rank(Rank),
+}
+
 /// Describes the various levels of multithreading that can be supported by an MPI library.
 ///
 /// # Examples
@@ -202,7 +453,7 @@ pub enum Threading {
 
 impl Threading {
     /// The raw value understood by the MPI C API
-    fn as_raw(self) -> c_int {
+    pub(crate) fn as_raw(self) -> c_int {
         match self {
             Threading::Single => unsafe { ffi::RSMPI_THREAD_SINGLE },
             Threading::Funneled => unsafe { ffi::RSMPI_THREAD_FUNNELED },
@@ -244,9 +495,7 @@ pub(crate) fn is_initialized() -> bool {
     unsafe { with_uninitialized(|initialized| ffi::MPI_Initialized(initialized)).1 != 0 }
 }
 
-/// Whether the MPI library has been initialized
-/// NOTE: Used by "derive" feature
-#[allow(unused)]
+/// Whether the MPI library has been finalized
 pub(crate) fn is_finalized() -> bool {
     unsafe { with_uninitialized(|finalized| ffi::MPI_Finalized(finalized)).1 != 0 }
 }
@@ -283,6 +532,12 @@ pub fn initialize() -> Option<Universe> {
 ///
 /// 12.4.3
 pub fn initialize_with_threading(threading: Threading) -> Option<(Universe, Threading)> {
+    init_thread(threading).ok()
+}
+
+/// Initializes MPI with the desired level of multithreading support unless it has been
+/// initialized before.
+fn init_thread(threading: Threading) -> Result<(Universe, Threading), InitError> {
     // Takes the lock before checking if MPI is initialized to prevent a race condition
     // leading to two threads both calling `MPI_Init_thread` at the same time.
     //
@@ -292,8 +547,11 @@ pub fn initialize_with_threading(threading: Threading) -> Option<(Universe, Thre
         .write()
         .expect("rsmpi internal error: UNIVERSE_STATE lock poisoned");
 
+    if is_finalized() {
+        return Err(InitError::AlreadyFinalized);
+    }
     if is_initialized() {
-        return None;
+        return Err(InitError::AlreadyInitialized);
     }
 
     let (_, provided) = unsafe {
@@ -309,11 +567,169 @@ pub fn initialize_with_threading(threading: Threading) -> Option<(Universe, Thre
 
     // No need to check if UNIVERSE_STATE has already been set - only one thread can enter this
     // code section per MPI run thanks to the `is_initialized()` check before.
+    let provided = provided.into();
+    Ok((start(&mut universe_state, provided, true), provided))
+}
+
+/// Sets up rsmpi for MPI that has been initialized with the `provided` level of multithreading
+/// support by the calling thread.
+fn start(
+    universe_state: &mut Option<UniverseState>,
+    provided: Threading,
+    finalize: bool,
+) -> Universe {
     *universe_state = Some(UniverseState {
         main_thread: thread::current().id(),
     });
+    crate::threading::initialized(provided);
+    #[cfg(feature = "debug-checks")]
+    crate::debug_checks::start(provided);
+
+    Universe {
+        buffer: None,
+        finalize,
+    }
+}
+
+/// Reasons for `Config::init()` and `Universe::from_existing()` to fail
+#[derive(thiserror::Error, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum InitError {
+    /// MPI has been initialized before
+    #[error("MPI has already been initialized")]
+    AlreadyInitialized,
+    /// MPI has been finalized and cannot be initialized again
+    #[error("MPI has already been finalized")]
+    AlreadyFinalized,
+    /// MPI has not been initialized by other code
+    #[error("MPI has not been initialized")]
+    NotInitialized,
+    /// The MPI library does not provide the required level of multithreading support. MPI has
+    /// been finalized again.
+    #[error("Threading level {required:?} is required, but MPI only provides {provided:?}")]
+    ThreadingNotMet {
+        /// The level passed to `Config::threading()`
+        required: Threading,
+        /// The level provided by the MPI library
+        provided: Threading,
+    },
+}
 
-    Some((Universe { buffer: None }, provided.into()))
+/// How MPI handles errors in calls on the world communicator and in calls that are not
+/// associated with an MPI object
+///
+/// # Standard section(s)
+///
+/// 8.3
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ErrorMode {
+    /// Errors abort all processes, `MPI_ERRORS_ARE_FATAL`. This is the default.
+    Fatal,
+    /// Errors are returned as error codes, `MPI_ERRORS_RETURN`. Only routines of rsmpi that
+    /// return a `Result`, like `Universe::finalize()`, report them, see `Config::errors()`.
+    Return,
+}
+
+/// Options for initializing MPI
+///
+/// ```no_run
+/// use mpi::{Config, Threading};
+///
+/// let universe = Config::new()
+///     .threading(Threading::Funneled)
+///     .bsend_buffer(1 << 20)
+///     .panic_abort(true)
+///     .init()
+///     .unwrap();
+/// ```
+///
+/// # Examples
+/// See `examples/config.rs`
+#[derive(Clone, Debug)]
+pub struct Config {
+    threading: Threading,
+    errors: ErrorMode,
+    bsend_buffer: usize,
+    panic_abort: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config::new()
+    }
+}
+
+impl Config {
+    /// The options of `initialize()`: `Threading::Single`, `ErrorMode::Fatal`, no buffer for
+    /// buffered sends and no panic hook
+    pub fn new() -> Self {
+        Config {
+            threading: Threading::Single,
+            errors: ErrorMode::Fatal,
+            bsend_buffer: 0,
+            panic_abort: false,
+        }
+    }
+
+    /// Requires the MPI library to provide at least `threading`.
+    pub fn threading(mut self, threading: Threading) -> Self {
+        self.threading = threading;
+        self
+    }
+
+    /// Sets how MPI handles errors.
+    ///
+    /// # Safety
+    ///
+    /// Most routines of rsmpi do not check the error code of the MPI call they wrap and read its
+    /// outputs regardless. With `ErrorMode::Return`, a failing call therefore leaves them reading
+    /// uninitialized memory. The caller must only use routines that return a `Result` while
+    /// errors are returned, or otherwise ensure that no MPI call fails.
+    pub unsafe fn errors(mut self, errors: ErrorMode) -> Self {
+        self.errors = errors;
+        self
+    }
+
+    /// Attaches a buffer of `size` bytes for buffered sends, see `Universe::set_buffer_size()`.
+    pub fn bsend_buffer(mut self, size: usize) -> Self {
+        self.bsend_buffer = size;
+        self
+    }
+
+    /// Whether a panic on any process aborts all of them, see `abort_on_panic()`. The error code
+    /// is 101, the exit code of a panicking Rust program.
+    pub fn panic_abort(mut self, abort: bool) -> Self {
+        self.panic_abort = abort;
+        self
+    }
+
+    /// Initializes MPI with these options.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 8.3, 8.7, 12.4.3
+    pub fn init(self) -> Result<Universe, InitError> {
+        let (mut universe, provided) = init_thread(self.threading)?;
+        if provided < self.threading {
+            drop(universe);
+            return Err(InitError::ThreadingNotMet {
+                required: self.threading,
+                provided,
+            });
+        }
+
+        let errhandler = match self.errors {
+            ErrorMode::Fatal => unsafe { ffi::RSMPI_ERRORS_ARE_FATAL },
+            ErrorMode::Return => unsafe { ffi::RSMPI_ERRORS_RETURN },
+        };
+        unsafe {
+            ffi::MPI_Comm_set_errhandler(ffi::RSMPI_COMM_WORLD, errhandler);
+        }
+        universe.set_buffer_size(self.bsend_buffer);
+        if self.panic_abort {
+            abort_on_panic(101);
+        }
+        Ok(universe)
+    }
 }
 
 /// Level of multithreading supported by this MPI universe
@@ -0,0 +1,217 @@
+//! Info objects
+//!
+//! An `Info` is an unordered set of string key/value pairs that is passed to MPI routines to
+//! provide hints or, in some cases, required arguments such as the hardware resource type of
+//! `SplitType::HwGuided`.
+//!
+//! # Unfinished features
+//!
+//! - **10.1**: `MPI_Info_get_string()`, `MPI_Info_create_env()`
+
+use std::{
+    ffi::{CStr, CString},
+    fmt,
+    os::raw::{c_char, c_int},
+};
+
+use conv::ConvUtil;
+
+use crate::{
+    ffi, ffi::MPI_Info, raw::traits::*, with_uninitialized, with_uninitialized2, MpiError,
+};
+
+/// A set of key/value hints for MPI routines
+///
+/// # Standard section(s)
+///
+/// 10
+pub struct Info(MPI_Info);
+
+impl Info {
+    /// Creates an empty info object.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 10, see MPI_Info_create
+    pub fn new() -> Self {
+        Info(unsafe { with_uninitialized(|info| ffi::MPI_Info_create(info)).1 })
+    }
+
+    /// Creates an info object holding the given key/value pairs.
+    pub fn from_pairs<K, V>(pairs: impl IntoIterator<Item = (K, V)>) -> Result<Self, MpiError>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut info = Info::new();
+        for (key, value) in pairs {
+            info.set(key.as_ref(), value.as_ref())?;
+        }
+        Ok(info)
+    }
+
+    /// Adds the pair `key`/`value`, replacing any value previously stored under `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MpiError::StringNul` if `key` or `value` contain interior 0 bytes.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 10, see MPI_Info_set
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), MpiError> {
+        let key = CString::new(key)?;
+        let value = CString::new(value)?;
+        unsafe {
+            ffi::MPI_Info_set(self.0, key.as_ptr(), value.as_ptr());
+        }
+        Ok(())
+    }
+
+    /// The value stored under `key`, if any
+    ///
+    /// # Errors
+    ///
+    /// Returns `MpiError::StringNul` if `key` contains interior 0 bytes.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 10, see MPI_Info_get_valuelen and MPI_Info_get
+    pub fn get(&self, key: &str) -> Result<Option<String>, MpiError> {
+        let key = CString::new(key)?;
+        let Some(len) = self.value_len(&key) else {
+            return Ok(None);
+        };
+
+        let mut buf = vec![0 as c_char; len + 1];
+        let buf_len: c_int = len
+            .value_as()
+            .expect("Length of info value cannot be expressed as an MPI int.");
+        unsafe {
+            with_uninitialized(|flag| {
+                ffi::MPI_Info_get(self.0, key.as_ptr(), buf_len, buf.as_mut_ptr(), flag)
+            });
+            Ok(Some(
+                CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned(),
+            ))
+        }
+    }
+
+    /// Removes the value stored under `key`, returning whether there was one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MpiError::StringNul` if `key` contains interior 0 bytes.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 10, see MPI_Info_delete
+    pub fn remove(&mut self, key: &str) -> Result<bool, MpiError> {
+        let key = CString::new(key)?;
+        if self.value_len(&key).is_none() {
+            return Ok(false);
+        }
+        unsafe {
+            ffi::MPI_Info_delete(self.0, key.as_ptr());
+        }
+        Ok(true)
+    }
+
+    /// The number of keys with a value
+    ///
+    /// # Standard section(s)
+    ///
+    /// 10, see MPI_Info_get_nkeys
+    pub fn len(&self) -> usize {
+        let nkeys = unsafe { with_uninitialized(|nkeys| ffi::MPI_Info_get_nkeys(self.0, nkeys)).1 };
+        nkeys
+            .value_as()
+            .expect("MPI_Info_get_nkeys returned a negative number of keys!")
+    }
+
+    /// Whether no keys have a value
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// All keys that have a value, in the order MPI enumerates them
+    ///
+    /// # Standard section(s)
+    ///
+    /// 10, see MPI_Info_get_nthkey
+    pub fn keys(&self) -> Vec<String> {
+        let max_key: usize = unsafe { ffi::RSMPI_MAX_INFO_KEY }
+            .value_as()
+            .expect("MPI_MAX_INFO_KEY cannot be expressed as a usize.");
+        let mut buf = vec![0 as c_char; max_key + 1];
+
+        (0..self.len())
+            .map(|n| unsafe {
+                let n: c_int = n
+                    .value_as()
+                    .expect("Info key index cannot be expressed as an MPI int.");
+                ffi::MPI_Info_get_nthkey(self.0, n, buf.as_mut_ptr());
+                CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
+            })
+            .collect()
+    }
+
+    fn value_len(&self, key: &CStr) -> Option<usize> {
+        let (_, len, flag): (_, c_int, c_int) = unsafe {
+            with_uninitialized2(|len, flag| {
+                ffi::MPI_Info_get_valuelen(self.0, key.as_ptr(), len, flag)
+            })
+        };
+        if flag == 0 {
+            None
+        } else {
+            Some(
+                len.value_as()
+                    .expect("MPI_Info_get_valuelen returned a negative length!"),
+            )
+        }
+    }
+}
+
+impl Default for Info {
+    fn default() -> Self {
+        Info::new()
+    }
+}
+
+impl Clone for Info {
+    /// Duplicates the info object including all key/value pairs.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 10, see MPI_Info_dup
+    fn clone(&self) -> Self {
+        Info(unsafe { with_uninitialized(|newinfo| ffi::MPI_Info_dup(self.0, newinfo)).1 })
+    }
+}
+
+impl fmt::Debug for Info {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(self.keys().into_iter().map(|key| {
+                let value = self.get(&key).ok().flatten();
+                (key, value)
+            }))
+            .finish()
+    }
+}
+
+impl Drop for Info {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::MPI_Info_free(&mut self.0);
+        }
+    }
+}
+
+unsafe impl AsRaw for Info {
+    type Raw = MPI_Info;
+    fn as_raw(&self) -> Self::Raw {
+        self.0
+    }
+}
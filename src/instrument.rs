@@ -0,0 +1,272 @@
+//! Hooks through which communication calls are reported to the optional instrumentation
+//! features
+//!
+//! Every instrumented call describes itself with a `Call` and holds the `Active` guard returned by
+//! `enter()` while it runs. The description is only built if an instrumentation feature is
+//! compiled in and switched on, so the hooks cost nothing otherwise.
+//!
+//! In debug builds, `enter()` also checks that the calling thread may make MPI calls at all, see
+//! `crate::threading`.
+
+// Without an instrumentation feature, nothing reads the call descriptions.
+#![cfg_attr(
+    not(any(feature = "debug-checks", feature = "profiling", feature = "tracing")),
+    allow(dead_code)
+)]
+
+use std::{ffi::CStr, os::raw::c_char};
+
+use conv::ConvUtil;
+
+use crate::{
+    datatype::traits::*,
+    ffi,
+    ffi::{MPI_Comm, MPI_Datatype},
+    raw::traits::*,
+    with_uninitialized, Count, Rank, Tag,
+};
+
+/// The family of MPI routines a call belongs to
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Category {
+    PointToPoint,
+    Collective,
+    Request,
+}
+
+impl Category {
+    /// A short name for the category
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Category::PointToPoint => "p2p",
+            Category::Collective => "collective",
+            Category::Request => "request",
+        }
+    }
+}
+
+/// Describes a call into the MPI library
+pub(crate) struct Call {
+    pub(crate) name: &'static str,
+    pub(crate) category: Category,
+    pub(crate) comm: Option<MPI_Comm>,
+    pub(crate) peer: Option<Rank>,
+    pub(crate) tag: Option<Tag>,
+    /// The name of the datatype, see `MPI_Type_get_name()`
+    pub(crate) datatype: Option<String>,
+    /// The number of bytes described by the datatype and count
+    pub(crate) bytes: usize,
+}
+
+impl Call {
+    /// A point to point call, named after the MPI routine it wraps
+    pub(crate) fn point_to_point(name: &'static str) -> Call {
+        Call::new(name, Category::PointToPoint)
+    }
+
+    /// A collective call, named after the MPI routine it wraps
+    pub(crate) fn collective(name: &'static str) -> Call {
+        Call::new(name, Category::Collective)
+    }
+
+    /// A call completing requests, named after the MPI routine it wraps
+    pub(crate) fn request(name: &'static str) -> Call {
+        Call::new(name, Category::Request)
+    }
+
+    fn new(name: &'static str, category: Category) -> Call {
+        Call {
+            name,
+            category,
+            comm: None,
+            peer: None,
+            tag: None,
+            datatype: None,
+            bytes: 0,
+        }
+    }
+
+    /// The communicator the call communicates on
+    pub(crate) fn on<C>(mut self, comm: &C) -> Call
+    where
+        C: AsRaw<Raw = MPI_Comm> + ?Sized,
+    {
+        self.comm = Some(comm.as_raw());
+        self
+    }
+
+    /// The rank of the other process, or the root of a collective. `MPI_ANY_SOURCE` leaves the
+    /// peer unspecified.
+    pub(crate) fn peer(mut self, rank: Rank) -> Call {
+        if rank != unsafe { ffi::RSMPI_ANY_SOURCE } {
+            self.peer = Some(rank);
+        }
+        self
+    }
+
+    /// The message tag. `MPI_ANY_TAG` leaves the tag unspecified.
+    pub(crate) fn tag(mut self, tag: Tag) -> Call {
+        if tag != unsafe { ffi::RSMPI_ANY_TAG } {
+            self.tag = Some(tag);
+        }
+        self
+    }
+
+    /// The buffer the call sends from or receives into
+    pub(crate) fn buffer<B>(self, buf: &B) -> Call
+    where
+        B: AsDatatype + Collection + ?Sized,
+    {
+        self.typed(&buf.as_datatype(), buf.count())
+    }
+
+    /// The partitioned buffer the call sends from, counting the elements of all partitions
+    pub(crate) fn partitioned<B>(self, buf: &B) -> Call
+    where
+        B: AsDatatype + Partitioned + ?Sized,
+    {
+        self.typed(&buf.as_datatype(), buf.counts().iter().sum())
+    }
+
+    /// `count` elements of `datatype` are sent or received
+    ///
+    /// The size and name of the datatype are looked up right away, since the datatype may be
+    /// freed before the call is reported.
+    pub(crate) fn typed<D>(mut self, datatype: &D, count: Count) -> Call
+    where
+        D: AsRaw<Raw = MPI_Datatype> + ?Sized,
+    {
+        let datatype = datatype.as_raw();
+        let size = unsafe { with_uninitialized(|size| ffi::MPI_Type_size(datatype, size)).1 };
+        self.bytes = size.value_as::<usize>().unwrap_or(0) * count.value_as::<usize>().unwrap_or(0);
+        let mut buf = [0 as c_char; ffi::MPI_MAX_OBJECT_NAME as usize];
+        let mut len = 0;
+        self.datatype = Some(unsafe {
+            ffi::MPI_Type_get_name(datatype, buf.as_mut_ptr(), &mut len);
+            CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
+        });
+        self
+    }
+
+    /// Whether the call blocks until its operation has completed. Following the MPI naming
+    /// scheme, the routines that only start an operation begin with `MPI_I`.
+    pub(crate) fn is_blocking(&self) -> bool {
+        !self.name.starts_with("MPI_I") && self.name != "MPI_Cancel"
+    }
+
+    /// The rank of the calling process in the communicator
+    pub(crate) fn rank(&self) -> Option<Rank> {
+        self.comm
+            .map(|comm| unsafe { with_uninitialized(|rank| ffi::MPI_Comm_rank(comm, rank)).1 })
+    }
+
+    /// The name of the communicator, see `Communicator::get_name()`
+    pub(crate) fn comm_name(&self) -> Option<String> {
+        self.comm.map(|comm| {
+            let mut buf = [0 as c_char; ffi::MPI_MAX_OBJECT_NAME as usize];
+            let mut len = 0;
+            unsafe {
+                ffi::MPI_Comm_get_name(comm, buf.as_mut_ptr(), &mut len);
+                CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
+            }
+        })
+    }
+}
+
+/// Reports the end of a call when dropped
+#[must_use]
+pub(crate) struct Active {
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+    #[cfg(feature = "debug-checks")]
+    _check: Option<crate::debug_checks::Outstanding>,
+    #[cfg(feature = "profiling")]
+    _profile: Option<crate::profiling::Pending>,
+}
+
+/// Reports the start of the call described by `call` to the enabled instrumentation features.
+///
+/// The returned guard must be held until the call has returned.
+#[inline]
+pub(crate) fn enter<F>(call: F) -> Active
+where
+    F: FnOnce() -> Call,
+{
+    #[cfg(debug_assertions)]
+    crate::threading::check_thread();
+
+    #[cfg(any(feature = "debug-checks", feature = "profiling", feature = "tracing"))]
+    {
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            target: "mpi",
+            "mpi",
+            routine = tracing::field::Empty,
+            category = tracing::field::Empty,
+            rank = tracing::field::Empty,
+            peer = tracing::field::Empty,
+            tag = tracing::field::Empty,
+            datatype = tracing::field::Empty,
+            bytes = tracing::field::Empty,
+            comm = tracing::field::Empty,
+        );
+        #[cfg(feature = "tracing")]
+        let traced = !span.is_disabled();
+        #[cfg(not(feature = "tracing"))]
+        let traced = false;
+        #[cfg(feature = "profiling")]
+        let profiled = crate::profiling::is_enabled();
+        #[cfg(not(feature = "profiling"))]
+        let profiled = false;
+        let checked = cfg!(feature = "debug-checks");
+
+        let call = (traced || profiled || checked).then(call);
+        #[cfg(feature = "tracing")]
+        if let Some(ref call) = call {
+            if traced {
+                record(&span, call);
+            }
+        }
+        Active {
+            #[cfg(feature = "tracing")]
+            _span: span.entered(),
+            #[cfg(feature = "debug-checks")]
+            _check: call
+                .as_ref()
+                .filter(|call| call.is_blocking())
+                .map(crate::debug_checks::Outstanding::new),
+            #[cfg(feature = "profiling")]
+            _profile: call
+                .filter(|_| profiled)
+                .map(crate::profiling::Pending::new),
+        }
+    }
+    #[cfg(not(any(feature = "debug-checks", feature = "profiling", feature = "tracing")))]
+    {
+        let _ = call;
+        Active {}
+    }
+}
+
+/// Fills in the fields of the span of `call`.
+#[cfg(feature = "tracing")]
+fn record(span: &tracing::Span, call: &Call) {
+    span.record("routine", call.name);
+    span.record("category", call.category.as_str());
+    if let Some(rank) = call.rank() {
+        span.record("rank", rank);
+    }
+    if let Some(peer) = call.peer {
+        span.record("peer", peer);
+    }
+    if let Some(tag) = call.tag {
+        span.record("tag", tag);
+    }
+    if let Some(ref datatype) = call.datatype {
+        span.record("datatype", datatype.as_str());
+        span.record("bytes", call.bytes);
+    }
+    if let Some(comm) = call.comm_name() {
+        span.record("comm", comm.as_str());
+    }
+}
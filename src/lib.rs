@@ -105,10 +105,11 @@
 //!   - blocking and non-blocking variants
 //! - **Datatypes**: Bridging between Rust types and MPI basic types as well as custom MPI datatypes
 //! which can act as views into buffers.
+//! - **Shared memory windows** on node-local communicators
 //!
 //! Not supported (yet):
 //!
-//! - One-sided communication (RMA)
+//! - One-sided communication (RMA), apart from shared memory windows
 //! - MPI parallel I/O
 //! - A million small things
 //!
@@ -136,14 +137,31 @@ pub mod ffi {
     pub use mpi_sys::*;
 }
 
+#[cfg(feature = "serde")]
+pub mod active_messages;
 pub mod attribute;
+pub mod channel;
 pub mod collective;
 pub mod datatype;
+#[cfg(feature = "debug-checks")]
+pub mod debug_checks;
 pub mod environment;
+pub mod info;
+mod instrument;
+pub mod memory;
 pub mod point_to_point;
+#[cfg(feature = "profiling")]
+pub mod profiling;
 pub mod raw;
 pub mod request;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod tag;
+pub mod threading;
+#[cfg(not(msmpi))]
+pub mod tool;
 pub mod topology;
+pub mod window;
 
 /// Re-exports all traits.
 pub mod traits {
@@ -168,7 +186,8 @@ pub mod internal {
 
 #[doc(inline)]
 pub use crate::environment::{
-    initialize, initialize_with_threading, time, time_resolution, Threading,
+    initialize, initialize_with_threading, time, time_resolution, Config, ErrorMode, InitError,
+    Threading,
 };
 use crate::ffi::MPI_Aint;
 
@@ -180,6 +199,8 @@ pub type Count = c_int;
 pub type Tag = c_int;
 /// An address in memory
 pub type Address = MPI_Aint;
+/// A handle as passed to and from the Fortran bindings of MPI
+pub type Fint = ffi::RSMPI_Fint;
 /// Reexport the Rank type
 pub use crate::topology::Rank;
 
@@ -220,15 +241,59 @@ where
 /// stable error type than to propagate raw types like ``std::ffi::NulError` in
 /// our public interface.
 ///
+/// Some variants only exist with certain features or MPI implementations, so the enum is
+/// `#[non_exhaustive]` and matches on it need a wildcard arm.
+///
 /// # Standard section(s)
 ///
 /// 9.3
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum MpiError {
+    /// An MPI routine returned an error code
+    #[error("{0} failed with error code {1}")]
+    Call(&'static str, Error),
+    /// MPI could not be initialized
+    #[error("{0}")]
+    Init(#[from] crate::environment::InitError),
     /// Failed to spawn some processes
     #[error("Failed to spawn {0} of {1} processes")]
     Spawn(Rank, Rank),
     /// CString::new fails if a Rust string contains interior 0 bytes
     #[error("An interior 0 byte was found in string")]
     StringNul(#[from] std::ffi::NulError),
+    /// Unpacking would read past the end of a packed byte buffer
+    #[error("Unpacking requires {1} bytes, but only {0} remain in the packed buffer")]
+    PackedBufferTooShort(usize, usize),
+    /// The processes disagree on the arguments of a checked collective call
+    #[error("Mismatched arguments to {operation} on ranks {ranks:?}")]
+    CollectiveMismatch {
+        /// The MPI routine that was about to be called
+        operation: &'static str,
+        /// The ranks of the processes whose arguments differ from those of the majority
+        ranks: Vec<Rank>,
+    },
+    /// A value could not be encoded for sending
+    #[cfg(feature = "serde")]
+    #[error("Failed to serialize message: {0}")]
+    Serialize(Box<dyn std::error::Error + Send + Sync>),
+    /// A received message could not be decoded
+    #[cfg(feature = "serde")]
+    #[error("Failed to deserialize message: {0}")]
+    Deserialize(Box<dyn std::error::Error + Send + Sync>),
+    /// An active message could not be handled by the target process
+    #[cfg(feature = "serde")]
+    #[error("Active message to handler {handler} on rank {rank} failed: {reason}")]
+    ActiveMessage {
+        /// The rank of the target process
+        rank: Rank,
+        /// The ID of the handler
+        handler: u32,
+        /// Why the handler could not be called
+        reason: String,
+    },
+    /// A call to the tool information interface failed
+    #[cfg(not(msmpi))]
+    #[error("{0}")]
+    Tool(#[from] crate::tool::ToolError),
 }
@@ -0,0 +1,463 @@
+//! Memory allocated by the MPI library
+//!
+//! Memory obtained from `MPI_Alloc_mem()` may be pinned or pre-registered with the network, which
+//! makes communication from and into it faster on RDMA-capable interconnects. `MpiBox` and
+//! `MpiVec` are owning containers backed by such memory that can be used as buffers in all
+//! communication operations. With the `allocator-api2` feature, `MpiAllocator` makes the memory
+//! available to the collections of the [allocator-api2](https://docs.rs/allocator-api2) crate.
+//!
+//! The containers and the allocator borrow the `Universe`, so they can only be created while MPI
+//! is initialized and are dropped before it is finalized. Like communicators, they are neither
+//! `Send` nor `Sync`: dropping them calls `MPI_Free_mem()`, which other threads may not be
+//! allowed to do below `Threading::Multiple`.
+//!
+//! # Examples
+//!
+//! See `examples/mpi_memory.rs`
+//!
+//! # Standard section(s)
+//!
+//! 8.2
+
+use std::{
+    alloc::{handle_alloc_error, Layout},
+    fmt,
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+    os::raw::c_void,
+    ptr::{self, NonNull},
+    slice,
+};
+
+use conv::ConvUtil;
+
+use crate::{datatype::traits::*, environment::Universe, ffi, Address, Count};
+
+/// Allocates memory for `layout` with `MPI_Alloc_mem()`.
+///
+/// Returns `None` if the MPI library cannot provide the memory or does not align it as required.
+/// `layout` must not have size 0.
+fn allocate(layout: Layout) -> Option<NonNull<u8>> {
+    debug_assert_ne!(layout.size(), 0);
+    let size: Address = layout.size().value_as().ok()?;
+    let mut base: *mut u8 = ptr::null_mut();
+    let code = unsafe {
+        ffi::MPI_Alloc_mem(
+            size,
+            ffi::RSMPI_INFO_NULL,
+            &mut base as *mut *mut u8 as *mut c_void,
+        )
+    };
+    let base = NonNull::new(base).filter(|_| code == ffi::MPI_SUCCESS as i32)?;
+    if base.as_ptr() as usize % layout.align() != 0 {
+        unsafe { deallocate(base) };
+        return None;
+    }
+    Some(base)
+}
+
+/// Returns memory obtained from `allocate()` to the MPI library.
+unsafe fn deallocate(base: NonNull<u8>) {
+    ffi::MPI_Free_mem(base.as_ptr() as *mut c_void);
+}
+
+/// Allocates an array of `capacity` values of `T`, or a dangling pointer if no memory is needed.
+fn allocate_array<T>(capacity: usize) -> NonNull<T> {
+    let layout = Layout::array::<T>(capacity).expect("Capacity overflow");
+    if layout.size() == 0 {
+        return NonNull::dangling();
+    }
+    allocate(layout)
+        .unwrap_or_else(|| handle_alloc_error(layout))
+        .cast()
+}
+
+/// Frees an array allocated with `allocate_array()`.
+unsafe fn deallocate_array<T>(ptr: NonNull<T>, capacity: usize) {
+    if capacity != 0 && mem::size_of::<T>() != 0 {
+        deallocate(ptr.cast());
+    }
+}
+
+/// A single value in memory allocated by the MPI library
+///
+/// The `Box` analogue for `MPI_Alloc_mem()`. Usable as a buffer holding one value.
+///
+/// # Standard section(s)
+///
+/// 8.2
+pub struct MpiBox<'u, T> {
+    ptr: NonNull<T>,
+    phantom: PhantomData<(T, &'u Universe)>,
+}
+
+impl<'u, T> MpiBox<'u, T> {
+    /// Moves `value` into memory allocated by the MPI library of `universe`.
+    pub fn new(_universe: &'u Universe, value: T) -> Self {
+        let ptr = allocate_array::<T>(1);
+        unsafe { ptr::write(ptr.as_ptr(), value) };
+        MpiBox {
+            ptr,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Moves the value out of MPI memory, freeing it.
+    pub fn into_inner(boxed: Self) -> T {
+        let value = unsafe { ptr::read(boxed.ptr.as_ptr()) };
+        unsafe { deallocate_array(boxed.ptr, 1) };
+        mem::forget(boxed);
+        value
+    }
+}
+
+impl<'u, T> Deref for MpiBox<'u, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<'u, T> DerefMut for MpiBox<'u, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<'u, T> Drop for MpiBox<'u, T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.ptr.as_ptr());
+            deallocate_array(self.ptr, 1);
+        }
+    }
+}
+
+impl<'u, T: Clone> Clone for MpiBox<'u, T> {
+    fn clone(&self) -> Self {
+        let ptr = allocate_array::<T>(1);
+        unsafe { ptr::write(ptr.as_ptr(), (**self).clone()) };
+        MpiBox {
+            ptr,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'u, T: fmt::Debug> fmt::Debug for MpiBox<'u, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// A growable array in memory allocated by the MPI library
+///
+/// The `Vec` analogue for `MPI_Alloc_mem()`. It dereferences to a slice and is usable as a
+/// buffer holding its elements. Growing the vector moves the elements to a new allocation.
+///
+/// # Standard section(s)
+///
+/// 8.2
+pub struct MpiVec<'u, T> {
+    ptr: NonNull<T>,
+    len: usize,
+    capacity: usize,
+    phantom: PhantomData<(T, &'u Universe)>,
+}
+
+impl<'u, T> MpiVec<'u, T> {
+    /// An empty vector in memory of the MPI library of `universe`, which does not allocate yet
+    pub fn new(_universe: &'u Universe) -> Self {
+        MpiVec {
+            ptr: NonNull::dangling(),
+            len: 0,
+            capacity: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// An empty vector with space for at least `capacity` elements
+    pub fn with_capacity(universe: &'u Universe, capacity: usize) -> Self {
+        let mut vec = MpiVec::new(universe);
+        vec.reserve(capacity);
+        vec
+    }
+
+    /// A vector holding the values of `iter`
+    pub fn from_iter_in<I: IntoIterator<Item = T>>(universe: &'u Universe, iter: I) -> Self {
+        let mut vec = MpiVec::new(universe);
+        vec.extend(iter);
+        vec
+    }
+
+    /// The number of elements
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the vector has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of elements the vector can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        if mem::size_of::<T>() == 0 {
+            usize::MAX
+        } else {
+            self.capacity
+        }
+    }
+
+    /// Makes room for at least `additional` more elements.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len.checked_add(additional).expect("Capacity overflow");
+        if required <= self.capacity() {
+            return;
+        }
+        let capacity = required.max(2 * self.capacity).max(4);
+        let ptr = allocate_array::<T>(capacity);
+        unsafe {
+            ptr::copy_nonoverlapping(self.ptr.as_ptr(), ptr.as_ptr(), self.len);
+            deallocate_array(self.ptr, self.capacity);
+        }
+        self.ptr = ptr;
+        self.capacity = capacity;
+    }
+
+    /// Appends `value`.
+    pub fn push(&mut self, value: T) {
+        self.reserve(1);
+        unsafe { ptr::write(self.ptr.as_ptr().add(self.len), value) };
+        self.len += 1;
+    }
+
+    /// Removes and returns the last element, if any.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(unsafe { ptr::read(self.ptr.as_ptr().add(self.len)) })
+    }
+
+    /// Drops all elements past the first `len`.
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            self.pop();
+        }
+    }
+
+    /// Drops all elements, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+}
+
+impl<'u, T: Clone> MpiVec<'u, T> {
+    /// A vector holding clones of the elements of `values`
+    pub fn from_slice(universe: &'u Universe, values: &[T]) -> Self {
+        let mut vec = MpiVec::with_capacity(universe, values.len());
+        vec.extend_from_slice(values);
+        vec
+    }
+
+    /// Appends clones of the elements of `values`.
+    pub fn extend_from_slice(&mut self, values: &[T]) {
+        self.reserve(values.len());
+        for value in values {
+            self.push(value.clone());
+        }
+    }
+
+    /// Truncates the vector to `len` elements or appends clones of `value` until it has `len`
+    /// elements.
+    pub fn resize(&mut self, len: usize, value: T) {
+        if len <= self.len {
+            self.truncate(len);
+        } else {
+            self.reserve(len - self.len);
+            while self.len < len {
+                self.push(value.clone());
+            }
+        }
+    }
+}
+
+impl<'u, T> Deref for MpiVec<'u, T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<'u, T> DerefMut for MpiVec<'u, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<'u, T> Drop for MpiVec<'u, T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(&mut **self as *mut [T]);
+            deallocate_array(self.ptr, self.capacity);
+        }
+    }
+}
+
+impl<'u, T: Clone> Clone for MpiVec<'u, T> {
+    fn clone(&self) -> Self {
+        let mut vec = MpiVec {
+            ptr: NonNull::dangling(),
+            len: 0,
+            capacity: 0,
+            phantom: PhantomData,
+        };
+        vec.extend_from_slice(self);
+        vec
+    }
+}
+
+impl<'u, T: fmt::Debug> fmt::Debug for MpiVec<'u, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<'u, T> Extend<T> for MpiVec<'u, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+unsafe impl<'u, T> AsDatatype for MpiBox<'u, T>
+where
+    T: Equivalence,
+{
+    type Out = <T as Equivalence>::Out;
+    fn as_datatype(&self) -> Self::Out {
+        <T as Equivalence>::equivalent_datatype()
+    }
+}
+
+unsafe impl<'u, T> Collection for MpiBox<'u, T>
+where
+    T: Equivalence,
+{
+    fn count(&self) -> Count {
+        1
+    }
+}
+
+unsafe impl<'u, T> Pointer for MpiBox<'u, T>
+where
+    T: Equivalence,
+{
+    fn pointer(&self) -> *const c_void {
+        self.ptr.as_ptr() as _
+    }
+}
+
+unsafe impl<'u, T> PointerMut for MpiBox<'u, T>
+where
+    T: Equivalence,
+{
+    fn pointer_mut(&mut self) -> *mut c_void {
+        self.ptr.as_ptr() as _
+    }
+}
+
+unsafe impl<'u, T> Buffer for MpiBox<'u, T> where T: Equivalence {}
+unsafe impl<'u, T> BufferMut for MpiBox<'u, T> where T: Equivalence {}
+
+unsafe impl<'u, T> AsDatatype for MpiVec<'u, T>
+where
+    T: Equivalence,
+{
+    type Out = <T as Equivalence>::Out;
+    fn as_datatype(&self) -> Self::Out {
+        <T as Equivalence>::equivalent_datatype()
+    }
+}
+
+unsafe impl<'u, T> Collection for MpiVec<'u, T>
+where
+    T: Equivalence,
+{
+    fn count(&self) -> Count {
+        self.len()
+            .value_as()
+            .expect("Length of slice cannot be expressed as an MPI Count.")
+    }
+}
+
+unsafe impl<'u, T> Pointer for MpiVec<'u, T>
+where
+    T: Equivalence,
+{
+    fn pointer(&self) -> *const c_void {
+        self.as_ptr() as _
+    }
+}
+
+unsafe impl<'u, T> PointerMut for MpiVec<'u, T>
+where
+    T: Equivalence,
+{
+    fn pointer_mut(&mut self) -> *mut c_void {
+        self.as_mut_ptr() as _
+    }
+}
+
+unsafe impl<'u, T> Buffer for MpiVec<'u, T> where T: Equivalence {}
+unsafe impl<'u, T> BufferMut for MpiVec<'u, T> where T: Equivalence {}
+
+/// An allocator handing out memory from `MPI_Alloc_mem()`
+///
+/// Implements the `Allocator` trait of the [allocator-api2](https://docs.rs/allocator-api2)
+/// crate, e.g. for `allocator_api2::vec::Vec::new_in(MpiAllocator::new(&universe))`. Requires the
+/// `allocator-api2` feature.
+///
+/// # Standard section(s)
+///
+/// 8.2
+#[cfg(feature = "allocator-api2")]
+#[derive(Copy, Clone, Debug)]
+pub struct MpiAllocator<'u> {
+    phantom: PhantomData<(&'u Universe, *const ())>,
+}
+
+#[cfg(feature = "allocator-api2")]
+impl<'u> MpiAllocator<'u> {
+    /// Allocates from the MPI library of `universe`.
+    pub fn new(_universe: &'u Universe) -> Self {
+        MpiAllocator {
+            phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "allocator-api2")]
+unsafe impl<'u> allocator_api2::alloc::Allocator for MpiAllocator<'u> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, allocator_api2::alloc::AllocError> {
+        let base = if layout.size() == 0 {
+            // A dangling pointer with the requested alignment
+            NonNull::new(layout.align() as *mut u8).ok_or(allocator_api2::alloc::AllocError)?
+        } else {
+            allocate(layout).ok_or(allocator_api2::alloc::AllocError)?
+        };
+        Ok(NonNull::slice_from_raw_parts(base, layout.size()))
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() != 0 {
+            deallocate(ptr);
+        }
+    }
+}
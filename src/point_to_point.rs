@@ -14,22 +14,31 @@
 use std::{
     alloc::{self, Layout},
     fmt,
-    mem::{transmute, MaybeUninit},
+    mem::MaybeUninit,
     ptr,
 };
 
 use conv::ConvUtil;
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Serialize};
 
 use super::{Count, Tag};
 use crate::{
-    datatype::traits::*,
+    datatype::{assume_init_vec, traits::*, uninit_vec},
     ffi,
     ffi::{MPI_Message, MPI_Status},
+    instrument::{self, Call},
     raw::traits::*,
     request::{Request, Scope, StaticScope},
+    tag::AsTag,
     topology::{traits::*, AnyProcess, CommunicatorRelation, Process, Rank},
     with_uninitialized, with_uninitialized2,
 };
+#[cfg(feature = "serde")]
+use crate::{
+    serialize::{DefaultEncoding, Encoding},
+    MpiError,
+};
 
 // TODO: rein in _with_tag ugliness, use optional tags or make tag part of Source and Destination
 
@@ -66,6 +75,12 @@ pub unsafe trait Source: AsCommunicator {
     ///
     /// 3.8.1
     fn probe_with_tag(&self, tag: Tag) -> Status {
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Probe")
+                .on(self.as_communicator())
+                .peer(self.source_rank())
+                .tag(tag)
+        });
         unsafe {
             Status(
                 with_uninitialized(|status| {
@@ -109,6 +124,12 @@ pub unsafe trait Source: AsCommunicator {
     ///
     /// 3.8.2
     fn matched_probe_with_tag(&self, tag: Tag) -> (Message, Status) {
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Mprobe")
+                .on(self.as_communicator())
+                .peer(self.source_rank())
+                .tag(tag)
+        });
         let (_, message, status) = unsafe {
             with_uninitialized2(|message, status| {
                 ffi::MPI_Mprobe(
@@ -141,15 +162,26 @@ pub unsafe trait Source: AsCommunicator {
     /// Receive a message containing a single instance of type `Msg`.
     ///
     /// Receive a message from `Source` `&self` tagged `tag` containing a single instance of type
-    /// `Msg`.
+    /// `Msg`. If `tag` is a `TypedTag<Msg>`, `Msg` is inferred from it.
+    ///
+    /// # Examples
+    /// See `examples/typed_tags.rs`
     ///
     /// # Standard section(s)
     ///
     /// 3.2.4
-    fn receive_with_tag<Msg>(&self, tag: Tag) -> (Msg, Status)
+    fn receive_with_tag<Msg>(&self, tag: impl AsTag<Msg>) -> (Msg, Status)
     where
         Msg: Equivalence,
     {
+        let tag = tag.as_tag();
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Recv")
+                .on(self.as_communicator())
+                .peer(self.source_rank())
+                .tag(tag)
+                .typed(&Msg::equivalent_datatype(), 1)
+        });
         unsafe {
             let (_, msg, status) = with_uninitialized2(|msg, status| {
                 ffi::MPI_Recv(
@@ -202,10 +234,18 @@ pub unsafe trait Source: AsCommunicator {
     /// # Standard section(s)
     ///
     /// 3.2.4
-    fn receive_into_with_tag<Buf: ?Sized>(&self, buf: &mut Buf, tag: Tag) -> Status
+    fn receive_into_with_tag<Buf: ?Sized>(&self, buf: &mut Buf, tag: impl AsTag<Buf>) -> Status
     where
         Buf: BufferMut,
     {
+        let tag = tag.as_tag();
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Recv")
+                .on(self.as_communicator())
+                .peer(self.source_rank())
+                .tag(tag)
+                .buffer(&*buf)
+        });
         unsafe {
             Status(
                 with_uninitialized(|status| {
@@ -241,16 +281,17 @@ pub unsafe trait Source: AsCommunicator {
     /// Receive a message containing multiple instances of type `Msg` into a `Vec`.
     ///
     /// Receive a message from `Source` `&self` tagged `tag` containing multiple instances of type
-    /// `Msg` into a `Vec`.
+    /// `Msg` into a `Vec`. If `tag` is a `TypedTag<[Msg]>`, `Msg` is inferred from it.
     ///
     /// # Standard section(s)
     ///
     /// 3.2.4
-    fn receive_vec_with_tag<Msg>(&self, tag: Tag) -> (Vec<Msg>, Status)
+    fn receive_vec_with_tag<Msg>(&self, tag: impl AsTag<[Msg]>) -> (Vec<Msg>, Status)
     where
         Msg: Equivalence,
     {
-        self.matched_probe_with_tag(tag).matched_receive_vec()
+        self.matched_probe_with_tag(tag.as_tag())
+            .matched_receive_vec()
     }
 
     /// Receive a message containing multiple instances of type `Msg` into a `Vec`.
@@ -271,6 +312,64 @@ pub unsafe trait Source: AsCommunicator {
         self.receive_vec_with_tag(unsafe { ffi::RSMPI_ANY_TAG })
     }
 
+    /// Receive a message containing a value of a `serde` type, decoding it with `encoding`.
+    ///
+    /// Receive a message from `Source` `&self` tagged `tag` that was sent with
+    /// `Destination::send_serialized_with_encoding()`. The message is matched with a probe first,
+    /// so its size does not need to be known in advance.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.8.3
+    #[cfg(feature = "serde")]
+    fn receive_deserialized_with_encoding<Msg, E>(
+        &self,
+        tag: Tag,
+        encoding: E,
+    ) -> Result<(Msg, Status), MpiError>
+    where
+        Msg: DeserializeOwned,
+        E: Encoding,
+    {
+        let (bytes, status) = self.matched_probe_with_tag(tag).matched_receive_vec::<u8>();
+        Ok((encoding.decode(&bytes)?, status))
+    }
+
+    /// Receive a message containing a value of a `serde` type.
+    ///
+    /// Receive a message from `Source` `&self` tagged `tag` that was sent with
+    /// `Destination::send_serialized_with_tag()`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.8.3
+    #[cfg(feature = "serde")]
+    fn receive_deserialized_with_tag<Msg>(&self, tag: Tag) -> Result<(Msg, Status), MpiError>
+    where
+        Msg: DeserializeOwned,
+    {
+        self.receive_deserialized_with_encoding(tag, DefaultEncoding::default())
+    }
+
+    /// Receive a message containing a value of a `serde` type.
+    ///
+    /// Receive a message from `Source` `&self` that was sent with
+    /// `Destination::send_serialized()`.
+    ///
+    /// # Examples
+    /// See `examples/serialized.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.8.3
+    #[cfg(feature = "serde")]
+    fn receive_deserialized<Msg>(&self) -> Result<(Msg, Status), MpiError>
+    where
+        Msg: DeserializeOwned,
+    {
+        self.receive_deserialized_with_tag(unsafe { ffi::RSMPI_ANY_TAG })
+    }
+
     /// Initiate an immediate (non-blocking) receive operation.
     ///
     /// Initiate receiving a message matching `tag` into `buf`.
@@ -288,6 +387,13 @@ pub unsafe trait Source: AsCommunicator {
         Buf: 'a + BufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Irecv")
+                .on(self.as_communicator())
+                .peer(self.source_rank())
+                .tag(tag)
+                .buffer(&*buf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -339,6 +445,13 @@ pub unsafe trait Source: AsCommunicator {
     where
         Msg: Equivalence,
     {
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Irecv")
+                .on(self.as_communicator())
+                .peer(self.source_rank())
+                .tag(tag)
+                .typed(&Msg::equivalent_datatype(), 1)
+        });
         unsafe {
             let val = alloc::alloc(Layout::new::<Msg>()) as *mut Msg;
             let (_, request) = with_uninitialized(|request| {
@@ -493,15 +606,27 @@ pub trait Destination: AsCommunicator {
 
     /// Blocking standard mode send operation
     ///
-    /// Send the contents of a `Buffer` to the `Destination` `&self` and tag it.
+    /// Send the contents of a `Buffer` to the `Destination` `&self` and tag it. A
+    /// `TypedTag<Buf>` only accepts buffers of type `Buf`.
+    ///
+    /// # Examples
+    /// See `examples/typed_tags.rs`
     ///
     /// # Standard section(s)
     ///
     /// 3.2.1
-    fn send_with_tag<Buf: ?Sized>(&self, buf: &Buf, tag: Tag)
+    fn send_with_tag<Buf: ?Sized>(&self, buf: &Buf, tag: impl AsTag<Buf>)
     where
         Buf: Buffer,
     {
+        let tag = tag.as_tag();
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Send")
+                .on(self.as_communicator())
+                .peer(self.destination_rank())
+                .tag(tag)
+                .buffer(buf)
+        });
         unsafe {
             ffi::MPI_Send(
                 buf.pointer(),
@@ -542,6 +667,63 @@ pub trait Destination: AsCommunicator {
         self.send_with_tag(buf, Tag::default())
     }
 
+    /// Blocking standard mode send of a value of a `serde` type
+    ///
+    /// Encode `msg` with `encoding` and send the resulting bytes to the `Destination` `&self`,
+    /// tagged `tag`. Fails without sending anything if `msg` cannot be encoded.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.1
+    #[cfg(feature = "serde")]
+    fn send_serialized_with_encoding<Msg, E>(
+        &self,
+        msg: &Msg,
+        tag: Tag,
+        encoding: E,
+    ) -> Result<(), MpiError>
+    where
+        Msg: ?Sized + Serialize,
+        E: Encoding,
+    {
+        let bytes = encoding.encode(msg)?;
+        self.send_with_tag(&bytes[..], tag);
+        Ok(())
+    }
+
+    /// Blocking standard mode send of a value of a `serde` type
+    ///
+    /// Send `msg` to the `Destination` `&self` using the default `Encoding` and tag it.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.1
+    #[cfg(feature = "serde")]
+    fn send_serialized_with_tag<Msg>(&self, msg: &Msg, tag: Tag) -> Result<(), MpiError>
+    where
+        Msg: ?Sized + Serialize,
+    {
+        self.send_serialized_with_encoding(msg, tag, DefaultEncoding::default())
+    }
+
+    /// Blocking standard mode send of a value of a `serde` type
+    ///
+    /// Send `msg` to the `Destination` `&self` using the default `Encoding`.
+    ///
+    /// # Examples
+    /// See `examples/serialized.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 3.2.1
+    #[cfg(feature = "serde")]
+    fn send_serialized<Msg>(&self, msg: &Msg) -> Result<(), MpiError>
+    where
+        Msg: ?Sized + Serialize,
+    {
+        self.send_serialized_with_tag(msg, Tag::default())
+    }
+
     /// Blocking buffered mode send operation
     ///
     /// Send the contents of a `Buffer` to the `Destination` `&self` and tag it.
@@ -553,6 +735,13 @@ pub trait Destination: AsCommunicator {
     where
         Buf: Buffer,
     {
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Bsend")
+                .on(self.as_communicator())
+                .peer(self.destination_rank())
+                .tag(tag)
+                .buffer(buf)
+        });
         unsafe {
             ffi::MPI_Bsend(
                 buf.pointer(),
@@ -592,6 +781,13 @@ pub trait Destination: AsCommunicator {
     where
         Buf: Buffer,
     {
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Ssend")
+                .on(self.as_communicator())
+                .peer(self.destination_rank())
+                .tag(tag)
+                .buffer(buf)
+        });
         unsafe {
             ffi::MPI_Ssend(
                 buf.pointer(),
@@ -650,6 +846,13 @@ pub trait Destination: AsCommunicator {
     where
         Buf: Buffer,
     {
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Rsend")
+                .on(self.as_communicator())
+                .peer(self.destination_rank())
+                .tag(tag)
+                .buffer(buf)
+        });
         unsafe {
             ffi::MPI_Rsend(
                 buf.pointer(),
@@ -712,6 +915,13 @@ pub trait Destination: AsCommunicator {
         Buf: 'a + Buffer,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Isend")
+                .on(self.as_communicator())
+                .peer(self.destination_rank())
+                .tag(tag)
+                .buffer(buf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -767,6 +977,13 @@ pub trait Destination: AsCommunicator {
         Buf: 'a + Buffer,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Ibsend")
+                .on(self.as_communicator())
+                .peer(self.destination_rank())
+                .tag(tag)
+                .buffer(buf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -823,6 +1040,13 @@ pub trait Destination: AsCommunicator {
         Buf: 'a + Buffer,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Issend")
+                .on(self.as_communicator())
+                .peer(self.destination_rank())
+                .tag(tag)
+                .buffer(buf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -898,6 +1122,13 @@ pub trait Destination: AsCommunicator {
         Buf: 'a + Buffer,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Irsend")
+                .on(self.as_communicator())
+                .peer(self.destination_rank())
+                .tag(tag)
+                .buffer(buf)
+        });
         unsafe {
             Request::from_raw(
                 with_uninitialized(|request| {
@@ -1033,6 +1264,9 @@ impl Message {
     where
         Msg: Equivalence,
     {
+        let _call = instrument::enter(|| {
+            Call::point_to_point("MPI_Mrecv").typed(&Msg::equivalent_datatype(), 1)
+        });
         unsafe {
             let (_, res, status) = with_uninitialized2(|res, status| {
                 ffi::MPI_Mrecv(
@@ -1062,6 +1296,7 @@ impl Message {
     where
         Buf: BufferMut,
     {
+        let _call = instrument::enter(|| Call::point_to_point("MPI_Mrecv").buffer(&*buf));
         let status;
         unsafe {
             status = with_uninitialized(|status| {
@@ -1095,6 +1330,7 @@ impl Message {
         Buf: BufferMut,
         Sc: Scope<'a>,
     {
+        let _call = instrument::enter(|| Call::point_to_point("MPI_Imrecv").buffer(&*buf));
         unsafe {
             let request = with_uninitialized(|request| {
                 ffi::MPI_Imrecv(
@@ -1158,24 +1394,11 @@ impl MatchedReceiveVec for (Message, Status) {
             .value_as()
             .expect("Message element count cannot be expressed as a usize.");
 
-        #[repr(transparent)]
-        struct UninitMsg<M>(MaybeUninit<M>);
-
-        unsafe impl<M: Equivalence> Equivalence for UninitMsg<M> {
-            type Out = M::Out;
-
-            fn equivalent_datatype() -> Self::Out {
-                M::equivalent_datatype()
-            }
-        }
-
-        let mut res = (0..count)
-            .map(|_| UninitMsg::<Msg>(MaybeUninit::uninit()))
-            .collect::<Vec<_>>();
+        let mut res = uninit_vec::<Msg>(count);
 
         let status = message.matched_receive_into(&mut res[..]);
 
-        let res = unsafe { transmute(res) };
+        let res = unsafe { assume_init_vec(res) };
 
         (res, status)
     }
@@ -1200,6 +1423,13 @@ where
     R: Equivalence,
     S: Source,
 {
+    let _call = instrument::enter(|| {
+        Call::point_to_point("MPI_Sendrecv")
+            .on(destination.as_communicator())
+            .peer(destination.destination_rank())
+            .tag(sendtag)
+            .buffer(msg)
+    });
     assert_eq!(
         source
             .as_communicator()
@@ -1270,6 +1500,13 @@ where
     B: BufferMut,
     S: Source,
 {
+    let _call = instrument::enter(|| {
+        Call::point_to_point("MPI_Sendrecv")
+            .on(destination.as_communicator())
+            .peer(destination.destination_rank())
+            .tag(sendtag)
+            .buffer(msg)
+    });
     assert_eq!(
         source
             .as_communicator()
@@ -1342,6 +1579,13 @@ where
     D: Destination,
     S: Source,
 {
+    let _call = instrument::enter(|| {
+        Call::point_to_point("MPI_Sendrecv_replace")
+            .on(destination.as_communicator())
+            .peer(destination.destination_rank())
+            .tag(sendtag)
+            .buffer(&*buf)
+    });
     assert_eq!(
         source
             .as_communicator()
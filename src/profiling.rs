@@ -0,0 +1,291 @@
+//! Recording a timeline of communication calls
+//!
+//! With the `profiling` feature, calls to the point to point, collective and request completion
+//! routines of this crate can record when they started, how long they took, how many bytes they
+//! described and which peer and tag they addressed. Recording is switched on and off at runtime
+//! with `enable()` and `disable()`; while it is off, instrumented calls only check a flag.
+//!
+//! The recorded events of each process are written to a trace file when the `Universe` is dropped,
+//! see `set_output()`. Chrome trace-event JSON can be opened in `chrome://tracing` or Perfetto to
+//! view all ranks side by side.
+//!
+//! Only the calls themselves are recorded: an immediate operation appears as the short call that
+//! starts it and the wait that completes it. Tests for completion are not recorded.
+//!
+//! # Examples
+//!
+//! See `examples/profiling.rs`
+
+use std::{
+    cell::Cell,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex, MutexGuard,
+    },
+};
+
+use once_cell::sync::Lazy;
+
+use crate::{environment::time, ffi, instrument::Call, with_uninitialized, Rank, Tag};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static PROFILE: Lazy<Mutex<Profile>> = Lazy::new(|| {
+    Mutex::new(Profile {
+        events: Vec::new(),
+        output: Some((PathBuf::from("rsmpi-trace"), TraceFormat::Chrome)),
+    })
+});
+
+struct Profile {
+    events: Vec<Event>,
+    output: Option<(PathBuf, TraceFormat)>,
+}
+
+fn profile() -> MutexGuard<'static, Profile> {
+    PROFILE
+        .lock()
+        .expect("rsmpi internal error: profile lock poisoned")
+}
+
+/// Starts recording calls.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Stops recording calls. Events recorded so far are kept.
+pub fn disable() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Whether calls are currently recorded
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// The events recorded so far, in the order the calls returned
+pub fn events() -> Vec<Event> {
+    profile().events.clone()
+}
+
+/// Discards the events recorded so far.
+pub fn clear() {
+    profile().events.clear();
+}
+
+/// Sets where the trace is written when the `Universe` is dropped.
+///
+/// Each process writes its events to `<prefix>.<rank>.json` for `TraceFormat::Chrome` or
+/// `<prefix>.<rank>.txt` for `TraceFormat::Text`, with `rank` the rank in the world
+/// communicator. `None` disables writing. The default is `rsmpi-trace` in the working directory
+/// in Chrome format. Nothing is written if no events were recorded.
+pub fn set_output(output: Option<(PathBuf, TraceFormat)>) {
+    profile().output = output;
+}
+
+/// Formats for writing the recorded events
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TraceFormat {
+    /// The Chrome trace-event JSON format, with one complete event per call. The process ID of
+    /// the events is the rank in the world communicator, so the traces of all ranks can be
+    /// concatenated into one timeline.
+    Chrome,
+    /// A plain text format in the spirit of OTF2 with one line per call, holding the start time,
+    /// end time, routine, peer, tag, bytes and communicator separated by spaces. Unspecified
+    /// fields are written as `-`.
+    Text,
+}
+
+/// A recorded call
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    /// The MPI routine that was called, e.g. `MPI_Send`
+    pub name: &'static str,
+    /// `"p2p"`, `"collective"` or `"request"`
+    pub category: &'static str,
+    /// When the call started, as returned by `mpi::time()`
+    pub start: f64,
+    /// When the call returned, as returned by `mpi::time()`
+    pub end: f64,
+    /// The number of bytes in the buffer the call sends or receives
+    pub bytes: usize,
+    /// The rank of the other process in `communicator`, or the root of a collective
+    pub peer: Option<Rank>,
+    /// The tag of a point to point call
+    pub tag: Option<Tag>,
+    /// The name of the communicator, see `Communicator::get_name()`
+    pub communicator: Option<String>,
+    /// A number identifying the thread that made the call, starting at 0
+    pub thread: u64,
+}
+
+impl Event {
+    /// The time the call took in seconds
+    pub fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// Writes `events` recorded by the process with rank `rank` to `writer`.
+pub fn write_trace<W: Write>(
+    mut writer: W,
+    events: &[Event],
+    rank: Rank,
+    format: TraceFormat,
+) -> io::Result<()> {
+    match format {
+        TraceFormat::Chrome => {
+            writeln!(writer, "{{\"traceEvents\":[")?;
+            for (i, event) in events.iter().enumerate() {
+                let separator = if i + 1 < events.len() { "," } else { "" };
+                write!(
+                    writer,
+                    "{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\
+                     \"pid\":{},\"tid\":{},\"args\":{{\"bytes\":{}",
+                    event.name,
+                    event.category,
+                    event.start * 1e6,
+                    event.duration() * 1e6,
+                    rank,
+                    event.thread,
+                    event.bytes
+                )?;
+                if let Some(peer) = event.peer {
+                    write!(writer, ",\"peer\":{}", peer)?;
+                }
+                if let Some(tag) = event.tag {
+                    write!(writer, ",\"tag\":{}", tag)?;
+                }
+                if let Some(ref comm) = event.communicator {
+                    write!(writer, ",\"comm\":\"{}\"", escape_json(comm))?;
+                }
+                writeln!(writer, "}}}}{}", separator)?;
+            }
+            writeln!(writer, "]}}")?;
+        }
+        TraceFormat::Text => {
+            let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+            for event in events {
+                writeln!(
+                    writer,
+                    "{:.9} {:.9} {} {} {} {} {}",
+                    event.start,
+                    event.end,
+                    event.name,
+                    or_dash(event.peer.map(|p| p.to_string())),
+                    or_dash(event.tag.map(|t| t.to_string())),
+                    event.bytes,
+                    or_dash(
+                        event
+                            .communicator
+                            .as_ref()
+                            .map(|c| c.replace(char::is_whitespace, "_"))
+                    ),
+                )?;
+            }
+        }
+    }
+    writer.flush()
+}
+
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes the trace of this process to the configured output. Called while the `Universe` is
+/// dropped, before MPI is finalized.
+pub(crate) fn finalize() {
+    disable();
+    let mut profile = profile();
+    let Some((ref prefix, format)) = profile.output else {
+        return;
+    };
+    if profile.events.is_empty() {
+        return;
+    }
+
+    let rank =
+        unsafe { with_uninitialized(|rank| ffi::MPI_Comm_rank(ffi::RSMPI_COMM_WORLD, rank)).1 };
+    let extension = match format {
+        TraceFormat::Chrome => "json",
+        TraceFormat::Text => "txt",
+    };
+    let mut path = prefix.clone().into_os_string();
+    path.push(format!(".{}.{}", rank, extension));
+
+    let result = File::create(&path)
+        .and_then(|file| write_trace(BufWriter::new(file), &profile.events, rank, format));
+    if let Err(e) = result {
+        eprintln!(
+            "rsmpi: failed to write trace to {}: {}",
+            PathBuf::from(path).display(),
+            e
+        );
+    }
+    profile.events.clear();
+}
+
+static NEXT_THREAD: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static THREAD: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+fn thread_index() -> u64 {
+    THREAD.with(|thread| {
+        thread.get().unwrap_or_else(|| {
+            let index = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+            thread.set(Some(index));
+            index
+        })
+    })
+}
+
+/// A call that is being recorded, which is added to the profile when dropped
+pub(crate) struct Pending {
+    call: Call,
+    start: f64,
+}
+
+impl Pending {
+    pub(crate) fn new(call: Call) -> Pending {
+        Pending {
+            call,
+            start: time(),
+        }
+    }
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        let end = time();
+        let event = Event {
+            name: self.call.name,
+            category: self.call.category.as_str(),
+            start: self.start,
+            end,
+            bytes: self.call.bytes,
+            peer: self.call.peer,
+            tag: self.call.tag,
+            communicator: self.call.comm_name(),
+            thread: thread_index(),
+        };
+        // Never panic here, the call may be unwinding already.
+        if let Ok(mut profile) = PROFILE.lock() {
+            profile.events.push(event);
+        }
+    }
+}
@@ -1,5 +1,7 @@
 //! Bridge between rust types and raw values
 
+use crate::{ffi, ffi::MPI_File, Fint};
+
 /// Rust C bridge traits
 pub mod traits {
     pub use super::{AsRaw, AsRawMut, FromRaw, MatchesRaw};
@@ -42,3 +44,26 @@ pub trait FromRaw: AsRaw {
 /// type, allowing slices of the type to be used with MPI APIs that accept arrays of its Raw MPI
 /// handle.
 pub unsafe trait MatchesRaw: AsRaw {}
+
+/// The handle of the file `file` in the Fortran bindings of MPI
+///
+/// rsmpi has no wrapper for files yet, this converts the raw handles of files opened elsewhere.
+///
+/// # Safety
+/// `file` must be a live file handle or `MPI_FILE_NULL`.
+///
+/// # Standard section(s)
+///
+/// 17.2.4
+pub unsafe fn file_to_fortran(file: MPI_File) -> Fint {
+    ffi::RSMPI_File_c2f(file)
+}
+
+/// The raw handle of the file with the Fortran handle `file`
+///
+/// # Standard section(s)
+///
+/// 17.2.4
+pub fn file_from_fortran(file: Fint) -> MPI_File {
+    unsafe { ffi::RSMPI_File_f2c(file) }
+}
@@ -39,9 +39,10 @@ use std::{
 use crate::{
     ffi,
     ffi::{MPI_Request, MPI_Status},
+    instrument::{self, Call},
     point_to_point::Status,
     raw::traits::*,
-    with_uninitialized,
+    with_uninitialized, Fint,
 };
 
 /// Check if the request is `MPI_REQUEST_NULL`.
@@ -113,6 +114,7 @@ impl<'a, D: ?Sized, S: Scope<'a>> Drop for Request<'a, D, S> {
 pub fn wait_any<'a, D, S: Scope<'a>>(
     requests: &mut Vec<Request<'a, D, S>>,
 ) -> Option<(usize, Status)> {
+    let _call = instrument::enter(|| Call::request("MPI_Waitany"));
     let mut mpi_requests: Vec<_> = requests.iter().map(|r| r.as_raw()).collect();
     let mut index: i32 = mpi_sys::MPI_UNDEFINED;
     let size: i32 = mpi_requests
@@ -167,6 +169,30 @@ impl<'a, D: ?Sized, S: Scope<'a>> Request<'a, D, S> {
         }
     }
 
+    /// Construct a request object from the handle of a request in the Fortran bindings of MPI,
+    /// e.g. one started by a Fortran library.
+    ///
+    /// # Safety
+    /// The requirements of `from_raw()` apply to the request with the handle `request`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 17.2.4
+    pub unsafe fn from_fortran(request: Fint, data: &'a D, scope: S) -> Self {
+        Self::from_raw(ffi::RSMPI_Request_f2c(request), data, scope)
+    }
+
+    /// The handle of the request in the Fortran bindings of MPI
+    ///
+    /// The request stays owned by `self` and must be completed through it.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 17.2.4
+    pub fn to_fortran(&self) -> Fint {
+        unsafe { ffi::RSMPI_Request_c2f(self.request) }
+    }
+
     /// Unregister the request object from its scope and deconstruct it into its raw parts.
     ///
     /// This is unsafe because the request may outlive its associated buffers.
@@ -188,6 +214,7 @@ impl<'a, D: ?Sized, S: Scope<'a>> Request<'a, D, S> {
     /// The referent `MPI_Status` object is never read. Also returns the data
     /// reference.
     fn wait_with(self, status: *mut MPI_Status) -> &'a D {
+        let _call = instrument::enter(|| Call::request("MPI_Wait"));
         unsafe {
             let (mut request, data, _) = self.into_raw();
             ffi::MPI_Wait(&mut request, status);
@@ -303,6 +330,7 @@ impl<'a, D: ?Sized, S: Scope<'a>> Request<'a, D, S> {
     ///
     /// 3.8.4
     pub fn cancel(&self) {
+        let _call = instrument::enter(|| Call::request("MPI_Cancel"));
         let mut request = self.as_raw();
         unsafe {
             ffi::MPI_Cancel(&mut request);
@@ -601,6 +629,7 @@ impl<'a, D: ?Sized> RequestCollection<'a, D> {
     /// Wait for any request to complete, and return an option containing
     /// (request_index, status, saved_data).
     pub fn wait_any(&mut self) -> Option<(usize, Status, &'a D)> {
+        let _call = instrument::enter(|| Call::request("MPI_Waitany"));
         let mut i: c_int = 0;
         let (_res, status) = unsafe {
             let count = self.requests.len() as c_int;
@@ -619,6 +648,7 @@ impl<'a, D: ?Sized> RequestCollection<'a, D> {
     /// to the (request_index, status, saved_data) for each completed request
     /// and return the total number of completed requests.
     pub fn wait_some(&mut self, result: &mut Vec<(usize, Status, &'a D)>) {
+        let _call = instrument::enter(|| Call::request("MPI_Waitsome"));
         result.clear();
         let mut count = 0;
         unsafe {
@@ -652,6 +682,7 @@ impl<'a, D: ?Sized> RequestCollection<'a, D> {
     /// Wait for all requests to complete, putting (request_index, status, saved_data)
     /// into result for every completed request.
     pub fn wait_all(&mut self, result: &mut Vec<(usize, Status, &'a D)>) {
+        let _call = instrument::enter(|| Call::request("MPI_Waitall"));
         let _res = unsafe {
             ffi::MPI_Waitall(
                 self.requests
@@ -0,0 +1,79 @@
+//! Messaging for types that implement `serde` traits but not `Equivalence`
+//!
+//! Values of any type implementing `serde::Serialize` can be sent with
+//! `Destination::send_serialized()` and received with `Source::receive_deserialized()` if the type
+//! also implements `serde::de::DeserializeOwned`. The receiving side uses a matched probe to size
+//! its buffer, so no separate length message is needed. `Root::broadcast_serialized()` and
+//! `Root::gather_serialized()` provide the corresponding collective operations.
+//!
+//! Values are turned into bytes by an `Encoding`. `Bincode` is used unless another encoding is
+//! passed to one of the `*_with_encoding` methods. `Postcard` is available with the `postcard`
+//! feature.
+//!
+//! This module is only available with the `serde` feature.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::MpiError;
+
+/// A wire format for sending `serde` types as byte messages
+///
+/// All processes taking part in a communication operation must use the same encoding.
+pub trait Encoding {
+    /// Serialize `value` into a newly allocated byte vector.
+    fn encode<T>(&self, value: &T) -> Result<Vec<u8>, MpiError>
+    where
+        T: ?Sized + Serialize;
+
+    /// Deserialize a value from `bytes`.
+    fn decode<T>(&self, bytes: &[u8]) -> Result<T, MpiError>
+    where
+        T: DeserializeOwned;
+}
+
+/// The encoding used by the `*_serialized` methods that do not take an `Encoding` argument
+pub type DefaultEncoding = Bincode;
+
+/// The [bincode](https://docs.rs/bincode) wire format
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Bincode;
+
+impl Encoding for Bincode {
+    fn encode<T>(&self, value: &T) -> Result<Vec<u8>, MpiError>
+    where
+        T: ?Sized + Serialize,
+    {
+        bincode::serialize(value).map_err(|e| MpiError::Serialize(Box::new(e)))
+    }
+
+    fn decode<T>(&self, bytes: &[u8]) -> Result<T, MpiError>
+    where
+        T: DeserializeOwned,
+    {
+        bincode::deserialize(bytes).map_err(|e| MpiError::Deserialize(Box::new(e)))
+    }
+}
+
+/// The [postcard](https://docs.rs/postcard) wire format
+///
+/// Produces smaller messages than `Bincode` by using variable length integers.
+#[cfg(feature = "postcard")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Postcard;
+
+#[cfg(feature = "postcard")]
+impl Encoding for Postcard {
+    fn encode<T>(&self, value: &T) -> Result<Vec<u8>, MpiError>
+    where
+        T: ?Sized + Serialize,
+    {
+        postcard::to_allocvec(value).map_err(|e| MpiError::Serialize(Box::new(e)))
+    }
+
+    fn decode<T>(&self, bytes: &[u8]) -> Result<T, MpiError>
+    where
+        T: DeserializeOwned,
+    {
+        postcard::from_bytes(bytes).map_err(|e| MpiError::Deserialize(Box::new(e)))
+    }
+}
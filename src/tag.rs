@@ -0,0 +1,233 @@
+//! Typed tags and tag allocation for code sharing a communicator
+//!
+//! A `TypedTag<M>` is a `Tag` that is only used for messages of type `M`. Passed to
+//! `Destination::send_with_tag()` or `Source::receive_with_tag()`, it fixes the type of the
+//! message, so receives infer the type from the tag. A `TypedTag<[T]>` is used for messages of
+//! several `T`, as received by `Source::receive_vec_with_tag()`.
+//!
+//! Libraries that communicate on the same communicator get disjoint tags from its `TagSpace`, a
+//! `CommAttribute` that hands out ranges of tags below `MPI_TAG_UB`. Tags below a floor, by default
+//! `TagSpace::DEFAULT_FLOOR`, are never handed out, so they remain free for tags picked by hand,
+//! including the default tag 0. Allocation is local to each process, so all processes of the
+//! communicator have to allocate in the same order, as with collective calls.
+//!
+//! # Examples
+//!
+//! See `examples/typed_tags.rs`
+
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::Range,
+    sync::atomic::{AtomicI32, Ordering},
+};
+
+use crate::{
+    attribute::{CommAttribute, TagUpperBound},
+    topology::{traits::*, SimpleCommunicator},
+    Tag,
+};
+
+/// Something that can be used as the tag of a message of type `M`
+///
+/// Implemented by `Tag`, for messages of any type, and by `TypedTag<M>`.
+pub trait AsTag<M: ?Sized> {
+    /// The raw tag
+    fn as_tag(&self) -> Tag;
+}
+
+impl<M: ?Sized> AsTag<M> for Tag {
+    fn as_tag(&self) -> Tag {
+        *self
+    }
+}
+
+/// A tag that is only used for messages of type `M`
+pub struct TypedTag<M: ?Sized> {
+    tag: Tag,
+    _message: PhantomData<fn(&M)>,
+}
+
+impl<M: ?Sized> TypedTag<M> {
+    /// Uses `tag` for messages of type `M`.
+    ///
+    /// Prefer tags from a `TagSpace`, which do not collide with tags used by other code.
+    pub fn new(tag: Tag) -> Self {
+        TypedTag {
+            tag,
+            _message: PhantomData,
+        }
+    }
+
+    /// The raw tag
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+}
+
+impl<M: ?Sized> AsTag<M> for TypedTag<M> {
+    fn as_tag(&self) -> Tag {
+        self.tag
+    }
+}
+
+impl<M: ?Sized> From<TypedTag<M>> for Tag {
+    fn from(tag: TypedTag<M>) -> Self {
+        tag.tag
+    }
+}
+
+impl<M: ?Sized> Copy for TypedTag<M> {}
+
+impl<M: ?Sized> Clone for TypedTag<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: ?Sized> PartialEq for TypedTag<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tag == other.tag
+    }
+}
+
+impl<M: ?Sized> Eq for TypedTag<M> {}
+
+impl<M: ?Sized> Hash for TypedTag<M> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.tag.hash(state);
+    }
+}
+
+impl<M: ?Sized> fmt::Debug for TypedTag<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypedTag").field(&self.tag).finish()
+    }
+}
+
+/// A range of consecutive tags for messages of type `M`, allocated from a `TagSpace`
+pub struct TagRange<M: ?Sized> {
+    tags: Range<Tag>,
+    _message: PhantomData<fn(&M)>,
+}
+
+impl<M: ?Sized> TagRange<M> {
+    /// The number of tags in the range
+    pub fn len(&self) -> Tag {
+        self.tags.end - self.tags.start
+    }
+
+    /// Whether the range contains no tags
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// The `index`th tag of the range, or `None` if `index` is out of bounds
+    pub fn get(&self, index: Tag) -> Option<TypedTag<M>> {
+        (0 <= index && index < self.len()).then(|| TypedTag::new(self.tags.start + index))
+    }
+
+    /// The tags of the range in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = TypedTag<M>> {
+        self.tags.clone().map(TypedTag::new)
+    }
+}
+
+impl<M: ?Sized> Clone for TagRange<M> {
+    fn clone(&self) -> Self {
+        TagRange {
+            tags: self.tags.clone(),
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<M: ?Sized> fmt::Debug for TagRange<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TagRange").field(&self.tags).finish()
+    }
+}
+
+/// Hands out disjoint tags on a communicator
+///
+/// Tags are allocated downwards from `MPI_TAG_UB` and never below a floor, so small tags picked
+/// by hand do not collide with allocated ones. A `TagSpace` is attached to a communicator with
+/// `TagSpace::attach()` and retrieved with `AnyCommunicator::get_attr()`. Duplicates of the
+/// communicator have a separate context, so they do not inherit it.
+pub struct TagSpace {
+    /// The lowest tag handed out so far
+    lowest: AtomicI32,
+    /// The tags below this are never handed out
+    floor: Tag,
+}
+
+impl TagSpace {
+    /// The floor of a `TagSpace` attached with `attach()`: tags `0` to `1023` are left for
+    /// tags picked by hand.
+    pub const DEFAULT_FLOOR: Tag = 1024;
+
+    /// Attaches a `TagSpace` with the floor `DEFAULT_FLOOR` to `comm`, unless it already has
+    /// one.
+    pub fn attach<C: ?Sized + Communicator>(comm: &mut C) {
+        Self::attach_with_floor(comm, Self::DEFAULT_FLOOR);
+    }
+
+    /// Attaches a `TagSpace` to `comm` that never hands out tags below `floor`, unless `comm`
+    /// already has one.
+    ///
+    /// # Panics
+    ///
+    /// If `floor` is negative
+    pub fn attach_with_floor<C: ?Sized + Communicator>(comm: &mut C, floor: Tag) {
+        assert!(floor >= 0, "The floor of a TagSpace must not be negative");
+        if comm.get_attr::<TagSpace>().is_none() {
+            let upper_bound = SimpleCommunicator::world()
+                .get_attr::<TagUpperBound>()
+                .map(Tag::from)
+                .expect("MPI_TAG_UB is not attached to MPI_COMM_WORLD");
+            comm.set_attr(TagSpace {
+                lowest: AtomicI32::new(upper_bound.saturating_add(1).max(floor)),
+                floor,
+            });
+        }
+    }
+
+    /// Allocates a tag for messages of type `M`, or returns `None` if all tags have been handed
+    /// out.
+    pub fn tag<M: ?Sized>(&self) -> Option<TypedTag<M>> {
+        self.tags(1).map(|range| TypedTag::new(range.tags.start))
+    }
+
+    /// Allocates `count` consecutive tags for messages of type `M`, or returns `None` if fewer
+    /// than `count` tags are left.
+    pub fn tags<M: ?Sized>(&self, count: Tag) -> Option<TagRange<M>> {
+        assert!(count >= 0, "Cannot allocate a negative number of tags");
+        let lowest = self
+            .lowest
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |lowest| {
+                (lowest - self.floor >= count).then_some(lowest - count)
+            })
+            .ok()?;
+        Some(TagRange {
+            tags: lowest - count..lowest,
+            _message: PhantomData,
+        })
+    }
+
+    /// The number of tags that have not been handed out yet
+    pub fn remaining(&self) -> Tag {
+        self.lowest.load(Ordering::Relaxed) - self.floor
+    }
+}
+
+impl Clone for TagSpace {
+    fn clone(&self) -> Self {
+        TagSpace {
+            lowest: AtomicI32::new(self.lowest.load(Ordering::Relaxed)),
+            floor: self.floor,
+        }
+    }
+}
+
+impl CommAttribute for TagSpace {}
@@ -0,0 +1,245 @@
+//! Using communicators from several threads
+//!
+//! Communicators are neither `Send` nor `Sync`, whatever level of multithreading MPI was
+//! initialized with. A `Token`, obtained from `Universe::token()`, proves that the MPI library
+//! provides a certain level and turns communicators into types that may be used from other
+//! threads at that level:
+//!
+//! - With `Threading::Multiple`, a `SharedCommunicator` is `Send` and `Sync`.
+//! - With `Threading::Serialized`, a `SerializedCommunicator` is only accessible through a guard,
+//!   and holding the guard excludes the guards of all other `SerializedCommunicator`s of the
+//!   process.
+//! - With `Threading::Funneled` or `Threading::Single`, only the thread that initialized MPI may
+//!   make calls. Debug builds check this in every communication call.
+//!
+//! # Examples
+//!
+//! See `examples/thread_levels.rs`
+
+use std::{
+    fmt,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, MutexGuard, PoisonError,
+    },
+    thread,
+};
+
+use crate::{
+    environment::{threading_support, Threading, UNIVERSE_STATE},
+    topology::traits::Communicator,
+};
+
+/// A level of multithreading support, as a type
+pub trait ThreadLevel: sealed::Sealed {
+    /// The corresponding `Threading` value
+    const LEVEL: Threading;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! thread_level {
+    ($name:ident, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+        pub enum $name {}
+
+        impl sealed::Sealed for $name {}
+
+        impl ThreadLevel for $name {
+            const LEVEL: Threading = Threading::$name;
+        }
+    };
+}
+
+thread_level!(Single, "The level of `Threading::Single`");
+thread_level!(Funneled, "The level of `Threading::Funneled`");
+thread_level!(Serialized, "The level of `Threading::Serialized`");
+thread_level!(Multiple, "The level of `Threading::Multiple`");
+
+/// Proof that the MPI library provides at least the level of multithreading support `L`
+///
+/// Created by `Universe::token()`.
+pub struct Token<L: ThreadLevel> {
+    _level: PhantomData<L>,
+}
+
+impl<L: ThreadLevel> Token<L> {
+    /// Returns a token if the MPI library provides at least level `L`.
+    pub(crate) fn new() -> Option<Token<L>> {
+        (threading_support() >= L::LEVEL).then_some(Token {
+            _level: PhantomData,
+        })
+    }
+}
+
+impl<L: ThreadLevel> Copy for Token<L> {}
+
+impl<L: ThreadLevel> Clone for Token<L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L: ThreadLevel> fmt::Debug for Token<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Token").field(&L::LEVEL).finish()
+    }
+}
+
+impl From<Token<Multiple>> for Token<Serialized> {
+    fn from(_: Token<Multiple>) -> Self {
+        Token {
+            _level: PhantomData,
+        }
+    }
+}
+
+impl Token<Multiple> {
+    /// Makes `comm` usable from any thread.
+    pub fn share<C: Communicator>(self, comm: C) -> SharedCommunicator<C> {
+        SharedCommunicator(comm)
+    }
+}
+
+impl Token<Serialized> {
+    /// Makes `comm` usable from any thread, one call at a time.
+    pub fn serialize<C: Communicator>(self, comm: C) -> SerializedCommunicator<C> {
+        SerializedCommunicator {
+            comm: ManuallyDrop::new(comm),
+        }
+    }
+}
+
+/// A communicator that can be used from several threads at once
+///
+/// Created by `Token::<Multiple>::share()`.
+pub struct SharedCommunicator<C>(C);
+
+// MPI was initialized with `Threading::Multiple`, see `Token::<Multiple>::share()`.
+unsafe impl<C: Communicator> Send for SharedCommunicator<C> {}
+unsafe impl<C: Communicator> Sync for SharedCommunicator<C> {}
+
+impl<C> SharedCommunicator<C> {
+    /// Returns the communicator, which can only be used from the current thread again.
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+
+impl<C> Deref for SharedCommunicator<C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        &self.0
+    }
+}
+
+/// Held while a thread has access to a `SerializedCommunicator`
+static SERIALIZED: Mutex<()> = Mutex::new(());
+
+/// A communicator that can be used from several threads, one at a time
+///
+/// All `SerializedCommunicator`s of a process share one lock, so no two threads make MPI calls
+/// through them at the same time. Other MPI calls, e.g. through communicators that have not been
+/// wrapped, must not overlap with them either.
+///
+/// Created by `Token::<Serialized>::serialize()`.
+pub struct SerializedCommunicator<C: Communicator> {
+    comm: ManuallyDrop<C>,
+}
+
+// Access to the communicator is serialized by `SERIALIZED`.
+unsafe impl<C: Communicator> Send for SerializedCommunicator<C> {}
+unsafe impl<C: Communicator> Sync for SerializedCommunicator<C> {}
+
+impl<C: Communicator> SerializedCommunicator<C> {
+    /// Blocks until no other thread has access to a `SerializedCommunicator` and returns access
+    /// to the communicator.
+    pub fn lock(&self) -> SerializedGuard<'_, C> {
+        SerializedGuard {
+            comm: &self.comm,
+            _lock: lock(),
+        }
+    }
+
+    /// Returns the communicator, which can only be used from the current thread again.
+    pub fn into_inner(self) -> C {
+        let mut this = ManuallyDrop::new(self);
+        unsafe { ManuallyDrop::take(&mut this.comm) }
+    }
+}
+
+impl<C: Communicator> Drop for SerializedCommunicator<C> {
+    fn drop(&mut self) {
+        // Freeing the communicator is an MPI call as well.
+        let _lock = lock();
+        unsafe { ManuallyDrop::drop(&mut self.comm) }
+    }
+}
+
+fn lock() -> MutexGuard<'static, ()> {
+    SERIALIZED.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Access to a `SerializedCommunicator`, see `SerializedCommunicator::lock()`
+pub struct SerializedGuard<'a, C> {
+    comm: &'a C,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl<'a, C> Deref for SerializedGuard<'a, C> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        self.comm
+    }
+}
+
+/// Whether MPI was initialized with `Threading::Funneled` or `Threading::Single`
+static MAIN_THREAD_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Records the level of multithreading provided by `MPI_Init_thread()`.
+pub(crate) fn initialized(provided: Threading) {
+    MAIN_THREAD_ONLY.store(provided <= Threading::Funneled, Ordering::Relaxed);
+}
+
+/// Lifts the restriction to the main thread once the `Universe` is gone.
+pub(crate) fn finalized() {
+    MAIN_THREAD_ONLY.store(false, Ordering::Relaxed);
+}
+
+/// Panics if MPI calls are restricted to the main thread and this is another thread.
+#[inline]
+pub(crate) fn check_thread() {
+    if MAIN_THREAD_ONLY.load(Ordering::Relaxed) {
+        check_main_thread();
+    }
+}
+
+#[cold]
+fn check_main_thread() {
+    let universe_state = UNIVERSE_STATE
+        .read()
+        .expect("rsmpi internal error: UNIVERSE_STATE lock poisoned");
+    let Some(ref universe_state) = *universe_state else {
+        return;
+    };
+    if universe_state.main_thread != thread::current().id() {
+        panic!(
+            "\n\
+             RSMPI PANIC: MPI called from another thread\n\
+             \n\
+             Application attempted to make an MPI call from a different thread than that which \
+             initialized `rsmpi`, but MPI only provides `mpi::Threading::{:?}`. Please \
+             initialize rsmpi with `mpi::Threading::Serialized` or `mpi::Threading::Multiple` \
+             and use `mpi::threading::Token` to share communicators between threads.",
+            threading_support()
+        );
+    }
+}
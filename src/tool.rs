@@ -0,0 +1,1189 @@
+//! The MPI tool information interface
+//!
+//! The tool information interface (`MPI_T`) exposes the internal settings of the MPI library as
+//! *control variables*, e.g. eager limits or algorithm selections, and its internal counters and
+//! timers as *performance variables*, e.g. the length of the unexpected message queue or the
+//! number of bytes sent. Which variables exist and what they are called depends entirely on the
+//! MPI library.
+//!
+//! The interface is initialized independently of the rest of MPI, so control variables can be
+//! changed before `mpi::initialize()` is called.
+//!
+//! # Examples
+//!
+//! See `examples/tool.rs`
+//!
+//! # Unfinished features
+//!
+//! - **14.3.5**: Enumerated datatypes, `MPI_T_enum_get_info()`, `MPI_T_enum_get_item()`
+//! - **14.3.7**: Starting, stopping and reading all handles of a session at once,
+//!   `MPI_T_PVAR_ALL_HANDLES`
+//! - **14.3.8**: Variable categories, `MPI_T_category_*()`
+
+use std::{
+    ffi::CStr,
+    marker::PhantomData,
+    mem,
+    os::raw::{c_char, c_int, c_uint, c_ulong, c_void},
+    ptr,
+};
+
+use conv::ConvUtil;
+
+use crate::{
+    collective::{BorrowedOperation, SystemOperation, UnsafeUserOperation, UserOperation},
+    datatype::{DatatypeRef, UncommittedDatatypeRef, UncommittedUserDatatype, UserDatatype},
+    environment::Threading,
+    ffi,
+    info::Info,
+    point_to_point::Message,
+    raw::traits::*,
+    request::{Request, Scope},
+    topology::{BorrowedGroup, Communicator, SystemGroup, UserGroup},
+    window::SharedWindow,
+    with_uninitialized, with_uninitialized2, MpiError,
+};
+
+/// Initializes the tool information interface with the lowest level of multithreading support.
+///
+/// Equivalent to: `initialize_with_threading(Threading::Single).map(|x| x.0)`
+///
+/// # Standard section(s)
+///
+/// 14.3.4
+pub fn initialize() -> Result<ToolInterface, MpiError> {
+    initialize_with_threading(Threading::Single).map(|x| x.0)
+}
+
+/// Initializes the tool information interface with the desired level of multithreading support.
+///
+/// Returns the interface and the level of multithreading actually supported. The interface can
+/// be initialized any number of times, before, during and after the lifetime of the MPI
+/// `Universe`. It remains usable until the last `ToolInterface` is dropped.
+///
+/// # Standard section(s)
+///
+/// 14.3.4, see MPI_T_init_thread
+pub fn initialize_with_threading(
+    threading: Threading,
+) -> Result<(ToolInterface, Threading), MpiError> {
+    let (code, provided) = unsafe {
+        with_uninitialized(|provided| ffi::MPI_T_init_thread(threading.as_raw(), provided))
+    };
+    check(code)?;
+    Ok((ToolInterface { _private: () }, provided.into()))
+}
+
+/// Errors reported by the tool information interface
+///
+/// # Standard section(s)
+///
+/// 14.3.9
+#[derive(thiserror::Error, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ToolError {
+    /// The MPI library ran out of memory
+    #[error("Out of memory")]
+    Memory,
+    /// The tool information interface is not initialized
+    #[error("The tool information interface is not initialized")]
+    NotInitialized,
+    /// The tool information interface cannot be initialized
+    #[error("The tool information interface cannot be initialized")]
+    CannotInitialize,
+    /// The variable index does not exist
+    #[error("Invalid variable index")]
+    InvalidIndex,
+    /// The handle is invalid, e.g. because it is bound to the wrong kind of object
+    #[error("Invalid handle")]
+    InvalidHandle,
+    /// The MPI library cannot allocate any more handles
+    #[error("No more handles available")]
+    OutOfHandles,
+    /// The MPI library cannot create any more performance variable sessions
+    #[error("No more sessions available")]
+    OutOfSessions,
+    /// The performance variable session is invalid
+    #[error("Invalid session")]
+    InvalidSession,
+    /// The control variable cannot be set at this time
+    #[error("The control variable cannot be set at this moment")]
+    SetNotNow,
+    /// The control variable can never be set
+    #[error("The control variable cannot be set")]
+    SetNever,
+    /// The performance variable is continuous and cannot be started or stopped
+    #[error("The performance variable cannot be started or stopped")]
+    NoStartStop,
+    /// The performance variable is read-only
+    #[error("The performance variable cannot be written or reset")]
+    NoWrite,
+    /// The performance variable cannot be read and reset atomically
+    #[error("The performance variable cannot be read and reset atomically")]
+    NoAtomic,
+    /// The variable does not hold values of the requested Rust type
+    #[error("The variable does not hold values of the requested type")]
+    TypeMismatch,
+    /// Any other error code returned by the MPI library
+    #[error("Tool information interface error {0}")]
+    Other(c_int),
+}
+
+impl ToolError {
+    fn from_raw(code: c_int) -> ToolError {
+        let known = unsafe {
+            [
+                (ffi::RSMPI_T_ERR_MEMORY, ToolError::Memory),
+                (ffi::RSMPI_T_ERR_NOT_INITIALIZED, ToolError::NotInitialized),
+                (ffi::RSMPI_T_ERR_CANNOT_INIT, ToolError::CannotInitialize),
+                (ffi::RSMPI_T_ERR_INVALID_INDEX, ToolError::InvalidIndex),
+                (ffi::RSMPI_T_ERR_INVALID_HANDLE, ToolError::InvalidHandle),
+                (ffi::RSMPI_T_ERR_OUT_OF_HANDLES, ToolError::OutOfHandles),
+                (ffi::RSMPI_T_ERR_OUT_OF_SESSIONS, ToolError::OutOfSessions),
+                (ffi::RSMPI_T_ERR_INVALID_SESSION, ToolError::InvalidSession),
+                (ffi::RSMPI_T_ERR_CVAR_SET_NOT_NOW, ToolError::SetNotNow),
+                (ffi::RSMPI_T_ERR_CVAR_SET_NEVER, ToolError::SetNever),
+                (ffi::RSMPI_T_ERR_PVAR_NO_STARTSTOP, ToolError::NoStartStop),
+                (ffi::RSMPI_T_ERR_PVAR_NO_WRITE, ToolError::NoWrite),
+                (ffi::RSMPI_T_ERR_PVAR_NO_ATOMIC, ToolError::NoAtomic),
+            ]
+        };
+        known
+            .iter()
+            .find(|&&(raw, _)| raw == code)
+            .map_or(ToolError::Other(code), |&(_, error)| error)
+    }
+}
+
+fn check(code: c_int) -> Result<(), ToolError> {
+    if code == ffi::MPI_SUCCESS as i32 {
+        Ok(())
+    } else {
+        Err(ToolError::from_raw(code))
+    }
+}
+
+/// How much of the MPI library a user has to understand to make use of a variable
+///
+/// # Standard section(s)
+///
+/// 14.3.1
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Verbosity {
+    /// Basic information of interest to users
+    UserBasic,
+    /// Detailed information of interest to users
+    UserDetail,
+    /// All remaining information of interest to users
+    UserAll,
+    /// Basic information required for tuning
+    TunerBasic,
+    /// Detailed information required for tuning
+    TunerDetail,
+    /// All remaining information required for tuning
+    TunerAll,
+    /// Basic low-level information for MPI implementors
+    MpidevBasic,
+    /// Detailed low-level information for MPI implementors
+    MpidevDetail,
+    /// All remaining low-level information for MPI implementors
+    MpidevAll,
+    /// A verbosity level that rsmpi does not know, e.g. from a later version of the standard
+    Other(c_int),
+}
+
+impl Verbosity {
+    fn from_raw(raw: c_int) -> Verbosity {
+        let known = unsafe {
+            [
+                (ffi::RSMPI_T_VERBOSITY_USER_BASIC, Verbosity::UserBasic),
+                (ffi::RSMPI_T_VERBOSITY_USER_DETAIL, Verbosity::UserDetail),
+                (ffi::RSMPI_T_VERBOSITY_USER_ALL, Verbosity::UserAll),
+                (ffi::RSMPI_T_VERBOSITY_TUNER_BASIC, Verbosity::TunerBasic),
+                (ffi::RSMPI_T_VERBOSITY_TUNER_DETAIL, Verbosity::TunerDetail),
+                (ffi::RSMPI_T_VERBOSITY_TUNER_ALL, Verbosity::TunerAll),
+                (ffi::RSMPI_T_VERBOSITY_MPIDEV_BASIC, Verbosity::MpidevBasic),
+                (
+                    ffi::RSMPI_T_VERBOSITY_MPIDEV_DETAIL,
+                    Verbosity::MpidevDetail,
+                ),
+                (ffi::RSMPI_T_VERBOSITY_MPIDEV_ALL, Verbosity::MpidevAll),
+            ]
+        };
+        known
+            .iter()
+            .find(|&&(r, _)| r == raw)
+            .map(|&(_, verbosity)| verbosity)
+            .unwrap_or(Verbosity::Other(raw))
+    }
+}
+
+/// The kind of MPI object a variable is bound to
+///
+/// # Standard section(s)
+///
+/// 14.3.2
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Binding {
+    /// The variable applies to the whole MPI library
+    NoObject,
+    /// The variable is bound to a communicator
+    Communicator,
+    /// The variable is bound to a datatype
+    Datatype,
+    /// The variable is bound to an error handler
+    ErrorHandler,
+    /// The variable is bound to a file
+    File,
+    /// The variable is bound to a group
+    Group,
+    /// The variable is bound to a reduction operation
+    Operation,
+    /// The variable is bound to a request
+    Request,
+    /// The variable is bound to a window
+    Window,
+    /// The variable is bound to a message
+    Message,
+    /// The variable is bound to an info object
+    Info,
+    /// A kind of object that rsmpi does not know, e.g. `MPI_T_BIND_MPI_SESSION` from MPI 4.0
+    Other(c_int),
+}
+
+impl Binding {
+    fn from_raw(raw: c_int) -> Binding {
+        let known = unsafe {
+            [
+                (ffi::RSMPI_T_BIND_NO_OBJECT, Binding::NoObject),
+                (ffi::RSMPI_T_BIND_MPI_COMM, Binding::Communicator),
+                (ffi::RSMPI_T_BIND_MPI_DATATYPE, Binding::Datatype),
+                (ffi::RSMPI_T_BIND_MPI_ERRHANDLER, Binding::ErrorHandler),
+                (ffi::RSMPI_T_BIND_MPI_FILE, Binding::File),
+                (ffi::RSMPI_T_BIND_MPI_GROUP, Binding::Group),
+                (ffi::RSMPI_T_BIND_MPI_OP, Binding::Operation),
+                (ffi::RSMPI_T_BIND_MPI_REQUEST, Binding::Request),
+                (ffi::RSMPI_T_BIND_MPI_WIN, Binding::Window),
+                (ffi::RSMPI_T_BIND_MPI_MESSAGE, Binding::Message),
+                (ffi::RSMPI_T_BIND_MPI_INFO, Binding::Info),
+            ]
+        };
+        known
+            .iter()
+            .find(|&&(r, _)| r == raw)
+            .map(|&(_, binding)| binding)
+            .unwrap_or(Binding::Other(raw))
+    }
+}
+
+/// An MPI object that control and performance variables can be bound to
+///
+/// Implemented for communicators, datatypes, groups, operations, requests, messages, windows
+/// and info objects.
+pub trait BoundObject: AsRaw + sealed::Sealed {
+    /// The kind of object, as reported by the `binding()` of the variables bound to it
+    const BINDING: Binding;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! bound_object {
+    ($binding:ident, [$($generics:tt)*] $name:ty) => {
+        impl<$($generics)*> sealed::Sealed for $name {}
+
+        impl<$($generics)*> BoundObject for $name {
+            const BINDING: Binding = Binding::$binding;
+        }
+    };
+}
+
+bound_object!(Communicator, [C: Communicator] C);
+bound_object!(Datatype, ['a] DatatypeRef<'a>);
+bound_object!(Datatype, ['a] UncommittedDatatypeRef<'a>);
+bound_object!(Datatype, [] UserDatatype);
+bound_object!(Datatype, [] UncommittedUserDatatype);
+bound_object!(Group, [] SystemGroup);
+bound_object!(Group, [] UserGroup);
+bound_object!(Group, ['a] BorrowedGroup<'a>);
+bound_object!(Operation, [] SystemOperation);
+bound_object!(Operation, ['a] BorrowedOperation<'a>);
+bound_object!(Operation, ['a] UserOperation<'a>);
+bound_object!(Operation, [] UnsafeUserOperation);
+bound_object!(Request, ['a, D: ?Sized, S: Scope<'a>] Request<'a, D, S>);
+bound_object!(Message, [] Message);
+bound_object!(Window, [T] SharedWindow<T>);
+bound_object!(Info, [] Info);
+
+/// The set of processes across which a control variable may be changed, and whether it may be
+/// changed at all
+///
+/// # Standard section(s)
+///
+/// 14.3.6
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Scope {
+    /// The value is constant
+    Constant,
+    /// The value cannot be changed through the tool information interface
+    Readonly,
+    /// The value may differ between processes
+    Local,
+    /// The value must be changed consistently across a group of processes
+    Group,
+    /// The value must be changed to the same value across a group of processes
+    GroupEqual,
+    /// The value must be changed consistently across all connected processes
+    All,
+    /// The value must be changed to the same value across all connected processes
+    AllEqual,
+    /// A scope that rsmpi does not know, e.g. from a later version of the standard
+    Other(c_int),
+}
+
+impl Scope {
+    fn from_raw(raw: c_int) -> Scope {
+        let known = unsafe {
+            [
+                (ffi::RSMPI_T_SCOPE_CONSTANT, Scope::Constant),
+                (ffi::RSMPI_T_SCOPE_READONLY, Scope::Readonly),
+                (ffi::RSMPI_T_SCOPE_LOCAL, Scope::Local),
+                (ffi::RSMPI_T_SCOPE_GROUP, Scope::Group),
+                (ffi::RSMPI_T_SCOPE_GROUP_EQ, Scope::GroupEqual),
+                (ffi::RSMPI_T_SCOPE_ALL, Scope::All),
+                (ffi::RSMPI_T_SCOPE_ALL_EQ, Scope::AllEqual),
+            ]
+        };
+        known
+            .iter()
+            .find(|&&(r, _)| r == raw)
+            .map(|&(_, scope)| scope)
+            .unwrap_or(Scope::Other(raw))
+    }
+}
+
+/// What a performance variable measures
+///
+/// # Standard section(s)
+///
+/// 14.3.7
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PerformanceClass {
+    /// A discrete state from an enumeration
+    State,
+    /// The current utilization of a resource
+    Level,
+    /// The fixed size of a resource
+    Size,
+    /// The utilization of a resource as a fraction between 0.0 and 1.0
+    Percentage,
+    /// The highest utilization of a resource since the variable was started
+    HighWatermark,
+    /// The lowest utilization of a resource since the variable was started
+    LowWatermark,
+    /// The number of occurrences of an event
+    Counter,
+    /// An aggregate value, e.g. the sum of the sizes of all messages sent
+    Aggregate,
+    /// The time spent executing a section of the MPI library
+    Timer,
+    /// Anything else
+    Generic,
+    /// A class that rsmpi does not know, e.g. from a later version of the standard
+    Other(c_int),
+}
+
+impl PerformanceClass {
+    fn from_raw(raw: c_int) -> PerformanceClass {
+        let known = unsafe {
+            [
+                (ffi::RSMPI_T_PVAR_CLASS_STATE, PerformanceClass::State),
+                (ffi::RSMPI_T_PVAR_CLASS_LEVEL, PerformanceClass::Level),
+                (ffi::RSMPI_T_PVAR_CLASS_SIZE, PerformanceClass::Size),
+                (
+                    ffi::RSMPI_T_PVAR_CLASS_PERCENTAGE,
+                    PerformanceClass::Percentage,
+                ),
+                (
+                    ffi::RSMPI_T_PVAR_CLASS_HIGHWATERMARK,
+                    PerformanceClass::HighWatermark,
+                ),
+                (
+                    ffi::RSMPI_T_PVAR_CLASS_LOWWATERMARK,
+                    PerformanceClass::LowWatermark,
+                ),
+                (ffi::RSMPI_T_PVAR_CLASS_COUNTER, PerformanceClass::Counter),
+                (
+                    ffi::RSMPI_T_PVAR_CLASS_AGGREGATE,
+                    PerformanceClass::Aggregate,
+                ),
+                (ffi::RSMPI_T_PVAR_CLASS_TIMER, PerformanceClass::Timer),
+                (ffi::RSMPI_T_PVAR_CLASS_GENERIC, PerformanceClass::Generic),
+            ]
+        };
+        known
+            .iter()
+            .find(|&&(r, _)| r == raw)
+            .map(|&(_, class)| class)
+            .unwrap_or(PerformanceClass::Other(raw))
+    }
+}
+
+/// Rust types that the values of tool information interface variables can be read into
+///
+/// Variables hold values of one of the C types `int`, `unsigned`, `unsigned long`,
+/// `unsigned long long`, `MPI_Count`, `char` or `double`.
+///
+/// # Safety
+///
+/// `Self` must have the same size and representation as every MPI datatype it is compatible
+/// with.
+pub unsafe trait ToolValue: Copy + Default {
+    /// Whether values of MPI datatype `datatype` can be read into `Self`
+    fn is_compatible(datatype: ffi::MPI_Datatype) -> bool;
+}
+
+unsafe impl ToolValue for i32 {
+    fn is_compatible(datatype: ffi::MPI_Datatype) -> bool {
+        datatype == unsafe { ffi::RSMPI_INT }
+    }
+}
+
+unsafe impl ToolValue for u32 {
+    fn is_compatible(datatype: ffi::MPI_Datatype) -> bool {
+        datatype == unsafe { ffi::RSMPI_UNSIGNED }
+            || (mem::size_of::<c_ulong>() == mem::size_of::<u32>()
+                && datatype == unsafe { ffi::RSMPI_UNSIGNED_LONG })
+    }
+}
+
+unsafe impl ToolValue for u64 {
+    fn is_compatible(datatype: ffi::MPI_Datatype) -> bool {
+        datatype == unsafe { ffi::RSMPI_UNSIGNED_LONG_LONG }
+            || (mem::size_of::<c_ulong>() == mem::size_of::<u64>()
+                && datatype == unsafe { ffi::RSMPI_UNSIGNED_LONG })
+            || (mem::size_of::<c_uint>() == mem::size_of::<u64>()
+                && datatype == unsafe { ffi::RSMPI_UNSIGNED })
+    }
+}
+
+unsafe impl ToolValue for i64 {
+    fn is_compatible(datatype: ffi::MPI_Datatype) -> bool {
+        mem::size_of::<ffi::MPI_Count>() == mem::size_of::<i64>()
+            && datatype == unsafe { ffi::RSMPI_COUNT }
+    }
+}
+
+unsafe impl ToolValue for f64 {
+    fn is_compatible(datatype: ffi::MPI_Datatype) -> bool {
+        datatype == unsafe { ffi::RSMPI_DOUBLE }
+    }
+}
+
+unsafe impl ToolValue for u8 {
+    fn is_compatible(datatype: ffi::MPI_Datatype) -> bool {
+        datatype == unsafe { ffi::RSMPI_CHAR }
+    }
+}
+
+/// An initialized tool information interface
+///
+/// Finalizes the interface when dropped.
+///
+/// # Standard section(s)
+///
+/// 14.3
+pub struct ToolInterface {
+    _private: (),
+}
+
+impl ToolInterface {
+    /// The number of control variables
+    ///
+    /// The number can grow, e.g. when the MPI library loads a component.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.6, see MPI_T_cvar_get_num
+    pub fn num_control_variables(&self) -> usize {
+        let num = unsafe { with_uninitialized(|num| ffi::MPI_T_cvar_get_num(num)).1 };
+        num.value_as()
+            .expect("MPI_T_cvar_get_num returned a negative number of variables")
+    }
+
+    /// Describes the control variable with index `index`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.6, see MPI_T_cvar_get_info
+    pub fn control_variable_info(&self, index: usize) -> Result<ControlVariableInfo, MpiError> {
+        let raw_index = Self::raw_index(index);
+        let (mut verbosity, mut datatype, mut binding, mut scope) =
+            (0, unsafe { ffi::RSMPI_DATATYPE_NULL }, 0, 0);
+        let (name, description) = read_strings(|name, name_len, desc, desc_len| unsafe {
+            let mut enumtype = mem::zeroed();
+            ffi::MPI_T_cvar_get_info(
+                raw_index,
+                name,
+                name_len,
+                &mut verbosity,
+                &mut datatype,
+                &mut enumtype,
+                desc,
+                desc_len,
+                &mut binding,
+                &mut scope,
+            )
+        })?;
+        Ok(ControlVariableInfo {
+            index,
+            name,
+            description,
+            verbosity: Verbosity::from_raw(verbosity),
+            datatype,
+            binding: Binding::from_raw(binding),
+            scope: Scope::from_raw(scope),
+        })
+    }
+
+    /// Describes all control variables.
+    pub fn control_variables(&self) -> Result<Vec<ControlVariableInfo>, MpiError> {
+        (0..self.num_control_variables())
+            .map(|index| self.control_variable_info(index))
+            .collect()
+    }
+
+    /// Describes the control variable called `name`, if the MPI library has one.
+    pub fn find_control_variable(
+        &self,
+        name: &str,
+    ) -> Result<Option<ControlVariableInfo>, MpiError> {
+        for index in 0..self.num_control_variables() {
+            let info = self.control_variable_info(index)?;
+            if info.name == name {
+                return Ok(Some(info));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Allocates a handle for reading and writing a control variable that is not bound to an MPI
+    /// object.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ToolError::TypeMismatch` if the variable does not hold values of type `T` and
+    /// `ToolError::InvalidHandle` if it is bound to an MPI object.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.6, see MPI_T_cvar_handle_alloc
+    pub fn control_variable<T: ToolValue>(
+        &self,
+        info: &ControlVariableInfo,
+    ) -> Result<ControlVariable<'_, T>, MpiError> {
+        if info.binding != Binding::NoObject {
+            return Err(ToolError::InvalidHandle.into());
+        }
+        self.alloc_control_variable(info, ptr::null_mut())
+    }
+
+    /// Allocates a handle for reading and writing a control variable bound to `object`, e.g. a
+    /// communicator.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ToolError::TypeMismatch` if the variable does not hold values of type `T` and
+    /// `ToolError::InvalidHandle` if `object` is not of the kind the variable is bound to.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.6, see MPI_T_cvar_handle_alloc
+    pub fn control_variable_for<T: ToolValue, O: BoundObject>(
+        &self,
+        info: &ControlVariableInfo,
+        object: &O,
+    ) -> Result<ControlVariable<'_, T>, MpiError> {
+        if info.binding != O::BINDING {
+            return Err(ToolError::InvalidHandle.into());
+        }
+        let mut raw = object.as_raw();
+        self.alloc_control_variable(info, &mut raw as *mut O::Raw as *mut c_void)
+    }
+
+    fn alloc_control_variable<T: ToolValue>(
+        &self,
+        info: &ControlVariableInfo,
+        object: *mut c_void,
+    ) -> Result<ControlVariable<'_, T>, MpiError> {
+        if !T::is_compatible(info.datatype) {
+            return Err(ToolError::TypeMismatch.into());
+        }
+        let (code, handle, count) = unsafe {
+            with_uninitialized2(|handle, count| {
+                ffi::MPI_T_cvar_handle_alloc(Self::raw_index(info.index), object, handle, count)
+            })
+        };
+        check(code)?;
+        Ok(ControlVariable {
+            handle,
+            count: count
+                .value_as()
+                .expect("MPI_T_cvar_handle_alloc returned a negative count"),
+            phantom: PhantomData,
+        })
+    }
+
+    /// The number of performance variables
+    ///
+    /// The number can grow, e.g. when the MPI library loads a component.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.7, see MPI_T_pvar_get_num
+    pub fn num_performance_variables(&self) -> usize {
+        let num = unsafe { with_uninitialized(|num| ffi::MPI_T_pvar_get_num(num)).1 };
+        num.value_as()
+            .expect("MPI_T_pvar_get_num returned a negative number of variables")
+    }
+
+    /// Describes the performance variable with index `index`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.7, see MPI_T_pvar_get_info
+    pub fn performance_variable_info(
+        &self,
+        index: usize,
+    ) -> Result<PerformanceVariableInfo, MpiError> {
+        let raw_index = Self::raw_index(index);
+        let (mut verbosity, mut class, mut datatype, mut binding) =
+            (0, 0, unsafe { ffi::RSMPI_DATATYPE_NULL }, 0);
+        let (mut readonly, mut continuous, mut atomic) = (0, 0, 0);
+        let (name, description) = read_strings(|name, name_len, desc, desc_len| unsafe {
+            let mut enumtype = mem::zeroed();
+            ffi::MPI_T_pvar_get_info(
+                raw_index,
+                name,
+                name_len,
+                &mut verbosity,
+                &mut class,
+                &mut datatype,
+                &mut enumtype,
+                desc,
+                desc_len,
+                &mut binding,
+                &mut readonly,
+                &mut continuous,
+                &mut atomic,
+            )
+        })?;
+        Ok(PerformanceVariableInfo {
+            index,
+            name,
+            description,
+            verbosity: Verbosity::from_raw(verbosity),
+            class: PerformanceClass::from_raw(class),
+            datatype,
+            binding: Binding::from_raw(binding),
+            readonly: readonly != 0,
+            continuous: continuous != 0,
+            atomic: atomic != 0,
+        })
+    }
+
+    /// Describes all performance variables.
+    pub fn performance_variables(&self) -> Result<Vec<PerformanceVariableInfo>, MpiError> {
+        (0..self.num_performance_variables())
+            .map(|index| self.performance_variable_info(index))
+            .collect()
+    }
+
+    /// Describes the performance variable of class `class` called `name`, if the MPI library
+    /// has one.
+    ///
+    /// Names of performance variables are only unique within a class.
+    pub fn find_performance_variable(
+        &self,
+        name: &str,
+        class: PerformanceClass,
+    ) -> Result<Option<PerformanceVariableInfo>, MpiError> {
+        for index in 0..self.num_performance_variables() {
+            let info = self.performance_variable_info(index)?;
+            if info.name == name && info.class == class {
+                return Ok(Some(info));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Creates a session for reading performance variables.
+    ///
+    /// Sessions isolate tools from each other: starting, stopping or resetting a variable in one
+    /// session does not affect the handles of other sessions.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.7, see MPI_T_pvar_session_create
+    pub fn session(&self) -> Result<PerformanceSession<'_>, MpiError> {
+        let (code, session) =
+            unsafe { with_uninitialized(|session| ffi::MPI_T_pvar_session_create(session)) };
+        check(code)?;
+        Ok(PerformanceSession {
+            session,
+            phantom: PhantomData,
+        })
+    }
+
+    fn raw_index(index: usize) -> c_int {
+        index
+            .value_as()
+            .expect("Variable index cannot be expressed as an MPI int")
+    }
+}
+
+impl Drop for ToolInterface {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::MPI_T_finalize();
+        }
+    }
+}
+
+/// Queries a variable's name and description, which MPI returns through the same call as the
+/// rest of its properties.
+///
+/// `get_info` is called once to determine the string lengths and once more to fill them in.
+fn read_strings<F>(mut get_info: F) -> Result<(String, String), ToolError>
+where
+    F: FnMut(*mut c_char, *mut c_int, *mut c_char, *mut c_int) -> c_int,
+{
+    let (mut name_len, mut desc_len): (c_int, c_int) = (0, 0);
+    check(get_info(
+        ptr::null_mut(),
+        &mut name_len,
+        ptr::null_mut(),
+        &mut desc_len,
+    ))?;
+
+    let mut name = vec![0 as c_char; name_len.value_as().unwrap_or(0) + 1];
+    let mut desc = vec![0 as c_char; desc_len.value_as().unwrap_or(0) + 1];
+    let mut name_len: c_int = name
+        .len()
+        .value_as()
+        .expect("Variable name length cannot be expressed as an MPI int");
+    let mut desc_len: c_int = desc
+        .len()
+        .value_as()
+        .expect("Variable description length cannot be expressed as an MPI int");
+    check(get_info(
+        name.as_mut_ptr(),
+        &mut name_len,
+        desc.as_mut_ptr(),
+        &mut desc_len,
+    ))?;
+
+    let to_string =
+        |buf: &[c_char]| unsafe { CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned() };
+    Ok((to_string(&name), to_string(&desc)))
+}
+
+/// Describes a control variable
+///
+/// # Standard section(s)
+///
+/// 14.3.6
+#[derive(Clone, Debug)]
+pub struct ControlVariableInfo {
+    index: usize,
+    name: String,
+    description: String,
+    verbosity: Verbosity,
+    datatype: ffi::MPI_Datatype,
+    binding: Binding,
+    scope: Scope,
+}
+
+impl ControlVariableInfo {
+    /// The index of the variable
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The name of the variable
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// A description of the variable
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Who is expected to make use of the variable
+    pub fn verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
+
+    /// The kind of MPI object the variable is bound to
+    pub fn binding(&self) -> Binding {
+        self.binding
+    }
+
+    /// Whether and across which processes the variable can be changed
+    pub fn scope(&self) -> Scope {
+        self.scope
+    }
+
+    /// Whether the variable holds values that can be read into a `T`
+    pub fn holds<T: ToolValue>(&self) -> bool {
+        T::is_compatible(self.datatype)
+    }
+}
+
+/// A handle for reading and writing a control variable
+///
+/// # Standard section(s)
+///
+/// 14.3.6
+#[derive(Debug)]
+pub struct ControlVariable<'a, T> {
+    handle: ffi::MPI_T_cvar_handle,
+    count: usize,
+    phantom: PhantomData<(&'a ToolInterface, T)>,
+}
+
+impl<T: ToolValue> ControlVariable<'_, T> {
+    /// The number of values the variable holds
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Reads the first value of the variable.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.6, see MPI_T_cvar_read
+    pub fn read(&self) -> Result<T, MpiError> {
+        self.read_all()?
+            .first()
+            .copied()
+            .ok_or_else(|| ToolError::InvalidHandle.into())
+    }
+
+    /// Reads all values of the variable.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.6, see MPI_T_cvar_read
+    pub fn read_all(&self) -> Result<Vec<T>, MpiError> {
+        let mut values = vec![T::default(); self.count];
+        check(unsafe { ffi::MPI_T_cvar_read(self.handle, values.as_mut_ptr() as *mut c_void) })?;
+        Ok(values)
+    }
+
+    /// Sets the variable to `value`, which must hold `count()` values.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ToolError::SetNotNow` or `ToolError::SetNever` if the MPI library does not allow
+    /// changing the variable at this time or at all.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.6, see MPI_T_cvar_write
+    pub fn write(&mut self, value: &[T]) -> Result<(), MpiError> {
+        assert_eq!(
+            self.count,
+            value.len(),
+            "The new value must hold one element per value of the variable"
+        );
+        check(unsafe { ffi::MPI_T_cvar_write(self.handle, value.as_ptr() as *const c_void) })?;
+        Ok(())
+    }
+}
+
+impl ControlVariable<'_, u8> {
+    /// Reads a variable holding a string.
+    pub fn read_string(&self) -> Result<String, MpiError> {
+        let mut bytes = self.read_all()?;
+        if let Some(end) = bytes.iter().position(|&b| b == 0) {
+            bytes.truncate(end);
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+impl<T> Drop for ControlVariable<'_, T> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::MPI_T_cvar_handle_free(&mut self.handle);
+        }
+    }
+}
+
+/// Describes a performance variable
+///
+/// # Standard section(s)
+///
+/// 14.3.7
+#[derive(Clone, Debug)]
+pub struct PerformanceVariableInfo {
+    index: usize,
+    name: String,
+    description: String,
+    verbosity: Verbosity,
+    class: PerformanceClass,
+    datatype: ffi::MPI_Datatype,
+    binding: Binding,
+    readonly: bool,
+    continuous: bool,
+    atomic: bool,
+}
+
+impl PerformanceVariableInfo {
+    /// The index of the variable
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The name of the variable
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// A description of the variable
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Who is expected to make use of the variable
+    pub fn verbosity(&self) -> Verbosity {
+        self.verbosity
+    }
+
+    /// What the variable measures
+    pub fn class(&self) -> PerformanceClass {
+        self.class
+    }
+
+    /// The kind of MPI object the variable is bound to
+    pub fn binding(&self) -> Binding {
+        self.binding
+    }
+
+    /// Whether the variable can be written and reset
+    pub fn is_readonly(&self) -> bool {
+        self.readonly
+    }
+
+    /// Whether the variable is active from allocation on and cannot be started or stopped
+    pub fn is_continuous(&self) -> bool {
+        self.continuous
+    }
+
+    /// Whether the variable can be read and reset in one atomic operation
+    pub fn is_atomic(&self) -> bool {
+        self.atomic
+    }
+
+    /// Whether the variable holds values that can be read into a `T`
+    pub fn holds<T: ToolValue>(&self) -> bool {
+        T::is_compatible(self.datatype)
+    }
+}
+
+/// A session for reading performance variables
+///
+/// # Standard section(s)
+///
+/// 14.3.7
+#[derive(Debug)]
+pub struct PerformanceSession<'a> {
+    session: ffi::MPI_T_pvar_session,
+    phantom: PhantomData<&'a ToolInterface>,
+}
+
+impl PerformanceSession<'_> {
+    /// Allocates a handle for reading a performance variable that is not bound to an MPI
+    /// object.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ToolError::TypeMismatch` if the variable does not hold values of type `T` and
+    /// `ToolError::InvalidHandle` if it is bound to an MPI object.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.7, see MPI_T_pvar_handle_alloc
+    pub fn variable<T: ToolValue>(
+        &self,
+        info: &PerformanceVariableInfo,
+    ) -> Result<PerformanceVariable<'_, T>, MpiError> {
+        if info.binding != Binding::NoObject {
+            return Err(ToolError::InvalidHandle.into());
+        }
+        self.alloc_variable(info, ptr::null_mut())
+    }
+
+    /// Allocates a handle for reading a performance variable bound to `object`, e.g. a
+    /// communicator.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ToolError::TypeMismatch` if the variable does not hold values of type `T` and
+    /// `ToolError::InvalidHandle` if `object` is not of the kind the variable is bound to.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.7, see MPI_T_pvar_handle_alloc
+    pub fn variable_for<T: ToolValue, O: BoundObject>(
+        &self,
+        info: &PerformanceVariableInfo,
+        object: &O,
+    ) -> Result<PerformanceVariable<'_, T>, MpiError> {
+        if info.binding != O::BINDING {
+            return Err(ToolError::InvalidHandle.into());
+        }
+        let mut raw = object.as_raw();
+        self.alloc_variable(info, &mut raw as *mut O::Raw as *mut c_void)
+    }
+
+    fn alloc_variable<T: ToolValue>(
+        &self,
+        info: &PerformanceVariableInfo,
+        object: *mut c_void,
+    ) -> Result<PerformanceVariable<'_, T>, MpiError> {
+        if !T::is_compatible(info.datatype) {
+            return Err(ToolError::TypeMismatch.into());
+        }
+        let (code, handle, count) = unsafe {
+            with_uninitialized2(|handle, count| {
+                ffi::MPI_T_pvar_handle_alloc(
+                    self.session,
+                    ToolInterface::raw_index(info.index),
+                    object,
+                    handle,
+                    count,
+                )
+            })
+        };
+        check(code)?;
+        Ok(PerformanceVariable {
+            session: self,
+            handle,
+            count: count
+                .value_as()
+                .expect("MPI_T_pvar_handle_alloc returned a negative count"),
+            continuous: info.continuous,
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl Drop for PerformanceSession<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::MPI_T_pvar_session_free(&mut self.session);
+        }
+    }
+}
+
+/// A handle for reading a performance variable within a session
+///
+/// Continuous variables are active from allocation on, all others have to be started before they
+/// measure anything.
+///
+/// # Standard section(s)
+///
+/// 14.3.7
+#[derive(Debug)]
+pub struct PerformanceVariable<'a, T> {
+    session: &'a PerformanceSession<'a>,
+    handle: ffi::MPI_T_pvar_handle,
+    count: usize,
+    continuous: bool,
+    phantom: PhantomData<T>,
+}
+
+impl<T: ToolValue> PerformanceVariable<'_, T> {
+    /// The number of values the variable holds
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Starts measuring. Does nothing for continuous variables.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.7, see MPI_T_pvar_start
+    pub fn start(&mut self) -> Result<(), MpiError> {
+        if !self.continuous {
+            check(unsafe { ffi::MPI_T_pvar_start(self.session.session, self.handle) })?;
+        }
+        Ok(())
+    }
+
+    /// Stops measuring. Does nothing for continuous variables.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.7, see MPI_T_pvar_stop
+    pub fn stop(&mut self) -> Result<(), MpiError> {
+        if !self.continuous {
+            check(unsafe { ffi::MPI_T_pvar_stop(self.session.session, self.handle) })?;
+        }
+        Ok(())
+    }
+
+    /// Reads the first value of the variable.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.7, see MPI_T_pvar_read
+    pub fn read(&self) -> Result<T, MpiError> {
+        self.read_all()?
+            .first()
+            .copied()
+            .ok_or_else(|| ToolError::InvalidHandle.into())
+    }
+
+    /// Reads all values of the variable.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.7, see MPI_T_pvar_read
+    pub fn read_all(&self) -> Result<Vec<T>, MpiError> {
+        let mut values = vec![T::default(); self.count];
+        check(unsafe {
+            ffi::MPI_T_pvar_read(
+                self.session.session,
+                self.handle,
+                values.as_mut_ptr() as *mut c_void,
+            )
+        })?;
+        Ok(values)
+    }
+
+    /// Resets the variable to its starting value.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ToolError::NoWrite` if the variable is read-only.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 14.3.7, see MPI_T_pvar_reset
+    pub fn reset(&mut self) -> Result<(), MpiError> {
+        check(unsafe { ffi::MPI_T_pvar_reset(self.session.session, self.handle) })?;
+        Ok(())
+    }
+}
+
+impl<T> Drop for PerformanceVariable<'_, T> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::MPI_T_pvar_handle_free(self.session.session, &mut self.handle);
+        }
+    }
+}
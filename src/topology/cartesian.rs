@@ -1,11 +1,15 @@
-use std::mem;
+use std::{mem, os::raw::c_void};
 
 use conv::ConvUtil;
 
 use super::{sealed, AsCommunicator, Communicator, IntoTopology, Rank};
 use crate::{
-    datatype::traits::*, ffi, ffi::MPI_Comm, raw::traits::*, topology::SimpleCommunicator,
-    with_uninitialized, with_uninitialized2, Count, IntArray,
+    datatype::{traits::*, ArrayOrder, UserDatatype},
+    ffi,
+    ffi::{MPI_Comm, MPI_Request},
+    raw::traits::*,
+    topology::SimpleCommunicator,
+    with_uninitialized, with_uninitialized2, Count, IntArray, Tag,
 };
 
 /// Contains arrays describing the layout of the
@@ -27,6 +31,169 @@ pub struct CartesianLayout {
     pub coords: Vec<Count>,
 }
 
+/// Chooses a balanced distribution of `nnodes` processes over `dims.len()` dimensions.
+///
+/// Entries of `dims` that are non-zero on input are left unchanged, the zero entries are set such
+/// that the product of all entries is `nnodes` and the set entries are as close to each other as
+/// possible, in non-increasing order. The result can be passed to
+/// [`create_cartesian_communicator`](trait.Communicator.html#method.create_cartesian_communicator).
+///
+/// Panics if an entry of `dims` is negative or `nnodes` is not a multiple of the product of the
+/// non-zero entries of `dims`.
+///
+/// # Parameters
+/// * `nnodes` - the number of processes to distribute
+/// * `dims` - `dims[i]` is the extent of axis i, or 0 if it is to be chosen
+///
+/// # Examples
+/// See `examples/halo_exchange.rs`
+///
+/// # Standard section(s)
+/// 7.5.2 (MPI_Dims_create)
+pub fn dims_create(nnodes: Count, dims: &mut [Count]) {
+    assert!(
+        dims.iter().all(|&d| d >= 0),
+        "The extents in dims ({:?}) must not be negative",
+        dims
+    );
+    let fixed: Count = dims.iter().filter(|&&d| d != 0).product();
+    assert!(
+        nnodes % fixed == 0,
+        "nnodes ({}) must be a multiple of the product of the fixed extents in dims ({:?})",
+        nnodes,
+        dims
+    );
+
+    unsafe {
+        ffi::MPI_Dims_create(nnodes, dims.count(), dims.as_mut_ptr());
+    }
+}
+
+/// A process adjacent to the calling process in a
+/// [`CartesianCommunicator`](struct.CartesianCommunicator.html), as returned by
+/// [`neighbors`](struct.CartesianCommunicator.html#method.neighbors).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CartesianNeighbor {
+    /// `offset[i]` is -1, 0 or 1, the direction of the neighbor from the calling process in axis i
+    pub offset: Vec<Count>,
+    /// The rank of the neighbor, or `None` if the offset leaves the grid along a non-periodic axis
+    pub rank: Option<Rank>,
+}
+
+/// An iterator over the neighbors of a process in a
+/// [`CartesianCommunicator`](struct.CartesianCommunicator.html)
+///
+/// Neighbors are visited in lexicographic order of their offsets, where axis 0 varies slowest.
+pub struct CartesianNeighbors<'a> {
+    comm: &'a CartesianCommunicator,
+    layout: CartesianLayout,
+    diagonals: bool,
+    next: usize,
+    end: usize,
+}
+
+impl<'a> Iterator for CartesianNeighbors<'a> {
+    type Item = CartesianNeighbor;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.end {
+            let mut index = self.next;
+            self.next += 1;
+
+            let mut offset = vec![0; self.layout.dims.len()];
+            for o in offset.iter_mut().rev() {
+                *o = [-1, 0, 1][index % 3];
+                index /= 3;
+            }
+
+            let nonzero = offset.iter().filter(|&&o| o != 0).count();
+            if nonzero == 0 || (nonzero > 1 && !self.diagonals) {
+                continue;
+            }
+
+            let rank = self.neighbor_rank(&offset);
+            return Some(CartesianNeighbor { offset, rank });
+        }
+        None
+    }
+}
+
+impl<'a> CartesianNeighbors<'a> {
+    fn neighbor_rank(&self, offset: &[Count]) -> Option<Rank> {
+        let CartesianLayout {
+            dims,
+            periods,
+            coords,
+        } = &self.layout;
+
+        let mut neighbor = Vec::with_capacity(offset.len());
+        for i in 0..offset.len() {
+            let coord = coords[i] + offset[i];
+            if 0 <= coord && coord < dims[i] {
+                neighbor.push(coord);
+            } else if periods[i] {
+                neighbor.push(coord.rem_euclid(dims[i]));
+            } else {
+                return None;
+            }
+        }
+
+        Some(unsafe { self.comm.coordinates_to_rank_unchecked(&neighbor) })
+    }
+}
+
+/// The layout of a local n-dimensional array that is surrounded by ghost cells, for use with
+/// [`halo_exchange`](struct.CartesianCommunicator.html#method.halo_exchange).
+///
+/// Axis i of the array corresponds to axis i of the
+/// [`CartesianCommunicator`](struct.CartesianCommunicator.html). The `widths[i]` outermost cells on
+/// either side of axis i are ghost cells that mirror the interior of the neighboring process.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HaloLayout {
+    /// `sizes[i]` is the extent of the array in axis i, including the ghost cells on both sides
+    pub sizes: Vec<Count>,
+    /// `widths[i]` is the number of ghost cells on each side of axis i
+    pub widths: Vec<Count>,
+    /// The storage order of the array
+    pub order: ArrayOrder,
+}
+
+impl HaloLayout {
+    /// The layout of a row-major array with extents `sizes` and ghost cell widths `widths`
+    pub fn new(sizes: Vec<Count>, widths: Vec<Count>) -> Self {
+        HaloLayout {
+            sizes,
+            widths,
+            order: ArrayOrder::RowMajor,
+        }
+    }
+
+    /// The interior extents, i.e. `sizes[i] - 2 * widths[i]`
+    pub fn interior(&self) -> Vec<Count> {
+        self.sizes
+            .iter()
+            .zip(&self.widths)
+            .map(|(size, width)| size - 2 * width)
+            .collect()
+    }
+
+    /// The number of elements in the array including ghost cells
+    pub fn len(&self) -> usize {
+        self.sizes
+            .iter()
+            .map(|&size| {
+                size.value_as::<usize>()
+                    .expect("Array extents must not be negative")
+            })
+            .product()
+    }
+
+    /// Whether the array has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 /// A `CartesianCommunicator` is an MPI communicator object where ranks are laid out in an
 /// n-dimensional cartesian space. This gives ranks neighbors in each of those dimensions, and MPI
 /// is able to optimize the layout of these ranks to improve physical locality.
@@ -392,6 +559,189 @@ impl CartesianCommunicator {
         unsafe { self.shift_unchecked(dimension, displacement) }
     }
 
+    /// Returns an iterator over the processes adjacent to this process across a face of its cell
+    /// in the cartesian grid, i.e. the neighbors that differ by one in exactly one axis.
+    ///
+    /// Each neighbor is reported with its offset and, unless the offset leaves the grid along a
+    /// non-periodic axis, its rank. In a periodic axis of extent 1 or 2, the same rank can be
+    /// reported for several offsets.
+    ///
+    /// # Examples
+    /// See `examples/halo_exchange.rs`
+    pub fn neighbors(&self) -> CartesianNeighbors<'_> {
+        self.neighbors_impl(false)
+    }
+
+    /// Like [`neighbors`](#method.neighbors), but also includes the processes adjacent across an
+    /// edge or corner of this process' cell, for a total of `3^n - 1` neighbors in n dimensions.
+    ///
+    /// # Examples
+    /// See `examples/halo_exchange.rs`
+    pub fn neighbors_with_diagonals(&self) -> CartesianNeighbors<'_> {
+        self.neighbors_impl(true)
+    }
+
+    fn neighbors_impl(&self, diagonals: bool) -> CartesianNeighbors<'_> {
+        let layout = self.get_layout();
+        let end = layout.dims.iter().fold(1, |end, _| end * 3);
+        CartesianNeighbors {
+            comm: self,
+            layout,
+            diagonals,
+            next: 0,
+            end,
+        }
+    }
+
+    /// Exchanges the ghost cells of a local n-dimensional array with the face neighbors of this
+    /// process.
+    ///
+    /// For every axis i, the `layout.widths[i]` interior slabs next to either end of the axis are
+    /// sent to the neighbor in that direction, and the ghost cells are filled with the slabs
+    /// received in turn. All transfers use subarray datatypes directly on `buf` and are in flight
+    /// concurrently. Only the face regions of the halo are exchanged; ghost cells in the edges and
+    /// corners, which lie outside the interior along more than one axis, are left unchanged. Ghost
+    /// cells at the boundary of a non-periodic axis are left unchanged as well.
+    ///
+    /// This is a collective operation. It uses the tags `tag_base` to
+    /// `tag_base + 2 * num_dimensions() - 1`, so no other messages with these tags may be in
+    /// flight on this communicator at the same time. A range of tags from a
+    /// [`TagSpace`](../tag/struct.TagSpace.html) is a good choice.
+    ///
+    /// Panics if the layout does not have [`num_dimensions`](#method.num_dimensions) axes, if
+    /// `buf` does not hold exactly `layout.len()` elements, if the interior of an axis is
+    /// narrower than its ghost cell width or if `tag_base` is negative.
+    ///
+    /// # Examples
+    /// See `examples/halo_exchange.rs`
+    ///
+    /// # Standard section(s)
+    /// 4.1.3 (MPI_Type_create_subarray), 3.7 (MPI_Isend, MPI_Irecv, MPI_Waitall)
+    pub fn halo_exchange<T>(&self, buf: &mut [T], layout: &HaloLayout, tag_base: Tag)
+    where
+        T: Equivalence,
+    {
+        assert!(tag_base >= 0, "Tags must not be negative");
+        let num_dims: usize = self
+            .num_dimensions()
+            .value_as()
+            .expect("Received unexpected value from MPI_Cartdim_get");
+        assert_eq!(
+            num_dims,
+            layout.sizes.len(),
+            "The halo layout must have as many axes as the CartesianCommunicator"
+        );
+        assert_eq!(
+            num_dims,
+            layout.widths.len(),
+            "The halo layout must have as many axes as the CartesianCommunicator"
+        );
+        assert_eq!(
+            layout.len(),
+            buf.len(),
+            "The buffer must hold exactly the number of elements described by the halo layout"
+        );
+
+        let interior = layout.interior();
+        for (i, (&extent, &width)) in interior.iter().zip(&layout.widths).enumerate() {
+            assert!(
+                width >= 0 && extent >= width,
+                "The interior of axis {} ({}) must be at least as wide as its ghost cells ({})",
+                i,
+                extent,
+                width
+            );
+        }
+
+        let slab = |axis: usize, start: Count| {
+            let mut subsizes = interior.clone();
+            subsizes[axis] = layout.widths[axis];
+            let mut starts = layout.widths.clone();
+            starts[axis] = start;
+            UserDatatype::subarray(
+                &layout.sizes,
+                &subsizes,
+                &starts,
+                layout.order,
+                &T::equivalent_datatype(),
+            )
+        };
+
+        let proc_null = unsafe { ffi::RSMPI_PROC_NULL };
+        let ptr = buf.as_mut_ptr() as *mut c_void;
+        let mut requests: Vec<MPI_Request> = Vec::with_capacity(4 * num_dims);
+
+        for axis in 0..num_dims {
+            let size = layout.sizes[axis];
+            let width = layout.widths[axis];
+            if width == 0 {
+                continue;
+            }
+
+            let dimension: Count = axis
+                .value_as()
+                .expect("Axis cannot be expressed as an MPI int");
+            let (lower, upper) = self.shift(dimension, 1);
+            let lower = lower.unwrap_or(proc_null);
+            let upper = upper.unwrap_or(proc_null);
+            let upward: Tag = tag_base
+                .checked_add(2 * dimension)
+                .expect("The halo exchange tags cannot be expressed as an MPI int");
+            let downward: Tag = upward
+                .checked_add(1)
+                .expect("The halo exchange tags cannot be expressed as an MPI int");
+
+            // (datatype, peer, tag, is_receive), MPI keeps the datatypes alive until the transfers
+            // complete.
+            let transfers = [
+                (slab(axis, 0), lower, upward, true),
+                (slab(axis, size - width), upper, downward, true),
+                (slab(axis, size - 2 * width), upper, upward, false),
+                (slab(axis, width), lower, downward, false),
+            ];
+            for (datatype, peer, tag, is_receive) in transfers {
+                let request = unsafe {
+                    with_uninitialized(|request| {
+                        if is_receive {
+                            ffi::MPI_Irecv(
+                                ptr,
+                                1,
+                                datatype.as_raw(),
+                                peer,
+                                tag,
+                                self.as_raw(),
+                                request,
+                            )
+                        } else {
+                            ffi::MPI_Isend(
+                                ptr,
+                                1,
+                                datatype.as_raw(),
+                                peer,
+                                tag,
+                                self.as_raw(),
+                                request,
+                            )
+                        }
+                    })
+                    .1
+                };
+                requests.push(request);
+            }
+        }
+
+        unsafe {
+            ffi::MPI_Waitall(
+                requests
+                    .len()
+                    .value_as()
+                    .expect("Number of requests cannot be expressed as an MPI int"),
+                requests.as_mut_ptr(),
+                ffi::RSMPI_STATUSES_IGNORE,
+            );
+        }
+    }
+
     /// Partitions an existing Cartesian communicator into a new Cartesian communicator in a lower
     /// dimension.
     ///
@@ -436,6 +786,31 @@ impl CartesianCommunicator {
 
         unsafe { self.subgroup_unchecked(retain) }
     }
+
+    /// Partitions an existing Cartesian communicator into one-dimensional Cartesian communicators
+    /// along `axis`, each holding the processes that share their coordinates in all other axes.
+    ///
+    /// Panics if `axis` is not a valid axis of this communicator.
+    ///
+    /// # Parameters
+    /// * `axis` - the only axis retained in the new communicator
+    ///
+    /// # Examples
+    /// See `examples/halo_exchange.rs`
+    ///
+    /// # Standard section(s)
+    /// 7.5.7 (MPI_Cart_sub)
+    pub fn axis_subgroup(&self, axis: Count) -> CartesianCommunicator {
+        assert!(
+            0 <= axis && axis < self.num_dimensions(),
+            "axis ({}) is not valid for this communicator (num_dimensions = {})",
+            axis,
+            self.num_dimensions(),
+        );
+
+        let retain: Vec<bool> = (0..self.num_dimensions()).map(|i| i == axis).collect();
+        unsafe { self.subgroup_unchecked(&retain) }
+    }
 }
 
 impl Communicator for CartesianCommunicator {
@@ -8,11 +8,7 @@
 //!
 //! # Unfinished features
 //!
-//! - **6.3**: Group management
-//!   - **6.3.2**: Constructors, `MPI_Group_range_incl()`, `MPI_Group_range_excl()`
 //! - **6.4**: Communicator management
-//!   - **6.4.2**: Constructors, `MPI_Comm_dup_with_info()`, `MPI_Comm_idup()`,
-//!     `MPI_Comm_split_type()`
 //!   - **6.4.4**: Info, `MPI_Comm_set_info()`, `MPI_Comm_get_info()`
 //! - **6.6**: Inter-communication
 //! - **6.7**: Caching
@@ -21,7 +17,9 @@
 //! - **Parts of sections**: 8, 10, 12
 use std::{
     ffi::{CStr, CString},
-    mem::MaybeUninit,
+    marker::PhantomData,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::RangeInclusive,
     os::raw::{c_char, c_int, c_void},
     process,
 };
@@ -29,25 +27,33 @@ use std::{
 use conv::ConvUtil;
 
 #[cfg(not(msmpi))]
-use crate::Tag;
+use std::fmt;
+
 use crate::{
     attribute::CommAttribute,
-    datatype::traits::*,
+    datatype::{check_packed_remaining_native, traits::*},
     ffi,
     ffi::{MPI_Comm, MPI_Group},
+    info::Info,
     raw::traits::*,
-    with_uninitialized, Count, IntArray,
+    with_uninitialized, Count, Fint, IntArray, MpiError,
+};
+#[cfg(not(msmpi))]
+use crate::{
+    request::{Request, StaticScope},
+    Tag,
 };
 
 mod cartesian;
+mod reorder;
 
 /// Topology traits
 pub mod traits {
     pub use super::{AnyCommunicator, AsCommunicator, Communicator, Group};
 }
 
-// Re-export cartesian functions and types from topology modules.
-pub use self::cartesian::*;
+// Re-export functions and types from topology modules.
+pub use self::{cartesian::*, reorder::*};
 
 /// Something that has a communicator associated with it
 pub trait AsCommunicator {
@@ -63,9 +69,13 @@ pub type Rank = c_int;
 /// An invisible module that hides away abstraction of communicator handles
 pub(crate) mod sealed;
 
+/// Keeps communicators from being `Send` or `Sync`, whatever the representation of `MPI_Comm`.
+/// See `crate::threading` for using them from several threads.
+type Unshared = PhantomData<*const ()>;
+
 /// A simple communicator, either a system-defined communicator like `MPI_COMM_WORLD` or a
 /// user-defined intra-communicator without a special topology.
-pub struct SimpleCommunicator(pub(crate) sealed::CommunicatorHandle);
+pub struct SimpleCommunicator(pub(crate) sealed::CommunicatorHandle, Unshared);
 
 impl SimpleCommunicator {
     /// The 'world communicator'
@@ -75,14 +85,14 @@ impl SimpleCommunicator {
     /// # Examples
     /// See `examples/simple.rs`
     pub fn world() -> SimpleCommunicator {
-        SimpleCommunicator(sealed::CommunicatorHandle::World)
+        SimpleCommunicator(sealed::CommunicatorHandle::World, PhantomData)
     }
 
     /// The 'self communicator'
     ///
     /// Contains only the current process.
     pub fn self_comm() -> SimpleCommunicator {
-        SimpleCommunicator(sealed::CommunicatorHandle::SelfComm)
+        SimpleCommunicator(sealed::CommunicatorHandle::SelfComm, PhantomData)
     }
 
     /// If the raw value is the null handle returns `None`, otherwise it tries to create a
@@ -97,7 +107,7 @@ impl SimpleCommunicator {
     unsafe fn try_from_raw(raw: MPI_Comm) -> Option<SimpleCommunicator> {
         let handle = sealed::CommunicatorHandle::try_from_raw(raw)?;
         if let sealed::CommunicatorHandle::User(_) = handle {
-            Some(SimpleCommunicator(handle))
+            Some(SimpleCommunicator(handle, PhantomData))
         } else {
             None
         }
@@ -163,7 +173,7 @@ impl FromRaw for SimpleCommunicator {
     /// - `handle` must not be used after calling this function.
     unsafe fn from_raw(handle: <Self as AsRaw>::Raw) -> Self {
         let handle = sealed::CommunicatorHandle::simple_comm_from_raw(handle);
-        SimpleCommunicator(handle)
+        SimpleCommunicator(handle, PhantomData)
     }
 }
 
@@ -210,13 +220,15 @@ pub enum IntoTopology {
 ///
 /// # Standard Sections
 /// 6.6
-pub struct InterCommunicator(pub(crate) sealed::CommunicatorHandle);
+pub struct InterCommunicator(pub(crate) sealed::CommunicatorHandle, Unshared);
 
 impl InterCommunicator {
     /// Construct an `InterCommunicator` from a raw handle
     pub unsafe fn try_from_raw(raw: MPI_Comm) -> Option<Self> {
         sealed::CommunicatorHandle::try_from_raw(raw).and_then(|handle| match handle {
-            sealed::CommunicatorHandle::InterComm(_) => Some(InterCommunicator(handle)),
+            sealed::CommunicatorHandle::InterComm(_) => {
+                Some(InterCommunicator(handle, PhantomData))
+            }
             _ => None,
         })
     }
@@ -296,7 +308,10 @@ impl FromRaw for InterCommunicator {
     /// - `handle` must be an inter-comms or inter-comm parent handle
     /// - `handle` must not be used after calling `from_raw`.
     unsafe fn from_raw(handle: <Self as AsRaw>::Raw) -> Self {
-        Self(sealed::CommunicatorHandle::inter_comm_from_raw(handle))
+        Self(
+            sealed::CommunicatorHandle::inter_comm_from_raw(handle),
+            PhantomData,
+        )
     }
 }
 
@@ -306,6 +321,81 @@ impl Communicator for InterCommunicator {
     }
 }
 
+/// A communicator owned by someone else, e.g. a C or Fortran library, that is valid for `'a`
+///
+/// Unlike the communicators created through `from_raw()`, the communicator is not freed when the
+/// `BorrowedCommunicator` is dropped. Use `Communicator::duplicate()` to get a communicator that
+/// outlives the borrow.
+///
+/// # Examples
+///
+/// See `examples/foreign_handles.rs`
+pub struct BorrowedCommunicator<'a>(
+    ManuallyDrop<sealed::CommunicatorHandle>,
+    PhantomData<&'a ()>,
+    Unshared,
+);
+
+impl<'a> BorrowedCommunicator<'a> {
+    /// Borrows the communicator `raw`, returns `None` if `raw` is `MPI_COMM_NULL`.
+    ///
+    /// # Safety
+    /// - `raw` must be a live communicator handle or `MPI_COMM_NULL`
+    /// - `raw` must not be freed during `'a`
+    pub unsafe fn from_raw_borrowed(raw: MPI_Comm) -> Option<Self> {
+        sealed::CommunicatorHandle::try_from_raw(raw)
+            .map(|handle| BorrowedCommunicator(ManuallyDrop::new(handle), PhantomData, PhantomData))
+    }
+
+    /// Borrows the communicator with the Fortran handle `comm`, returns `None` if `comm` is
+    /// `MPI_COMM_NULL`.
+    ///
+    /// # Safety
+    /// - `comm` must be the handle of a live communicator or of `MPI_COMM_NULL`
+    /// - the communicator must not be freed during `'a`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 17.2.4
+    pub unsafe fn from_fortran(comm: Fint) -> Option<Self> {
+        Self::from_raw_borrowed(ffi::RSMPI_Comm_f2c(comm))
+    }
+}
+
+unsafe impl<'a> AsRaw for BorrowedCommunicator<'a> {
+    type Raw = MPI_Comm;
+    fn as_raw(&self) -> Self::Raw {
+        self.0.as_raw()
+    }
+}
+
+impl<'a> sealed::AsHandle for BorrowedCommunicator<'a> {
+    fn as_handle(&self) -> &sealed::CommunicatorHandle {
+        &self.0
+    }
+}
+
+impl<'a> Communicator for BorrowedCommunicator<'a> {
+    fn target_size(&self) -> Rank {
+        if self.0.is_inter_comm() {
+            let mut size = Rank::min_value();
+            unsafe {
+                ffi::MPI_Comm_remote_size(self.as_raw(), &mut size);
+            }
+            size
+        } else {
+            self.size()
+        }
+    }
+}
+
+impl<'a> AsCommunicator for BorrowedCommunicator<'a> {
+    type Out = BorrowedCommunicator<'a>;
+    fn as_communicator(&self) -> &Self::Out {
+        self
+    }
+}
+
 /// Unimplemented
 #[allow(missing_copy_implementations)]
 pub struct GraphCommunicator;
@@ -343,6 +433,162 @@ impl Color {
 /// A key used when determining the rank order of processes after a communicator split.
 pub type Key = c_int;
 
+/// The criterion by which `Communicator::split_by_type()` groups processes
+///
+/// Split types that the MPI library does not provide are passed to `MPI_Comm_split_type()` as
+/// `MPI_UNDEFINED`, in which case no process receives a communicator. `is_available()` tells them
+/// apart beforehand.
+///
+/// # Standard section(s)
+///
+/// 6.4.2 (See: `MPI_Comm_split_type`)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SplitType {
+    /// Processes that can create a shared memory region (`MPI_COMM_TYPE_SHARED`)
+    Shared,
+    /// Processes that share a hardware resource of the named type, e.g. `"NUMANode"`,
+    /// `"L3Cache"` or `"Package"` (`MPI_COMM_TYPE_HW_GUIDED`, MPI 4.0)
+    ///
+    /// The resource type is passed to MPI as the `"mpi_hw_resource_type"` info key. Processes
+    /// that are not restricted to a single resource of that type receive no communicator.
+    HwGuided(String),
+    /// Processes that share some hardware resource, chosen by MPI such that the new communicator
+    /// is a strict subset of the old one (`MPI_COMM_TYPE_HW_UNGUIDED`, MPI 4.0)
+    ///
+    /// Repeating the split on the resulting communicators descends the hardware hierarchy until
+    /// no process receives a communicator.
+    HwUnguided,
+    /// Processes that belong to the named process set, e.g. `"mpi://WORLD"`
+    /// (`MPI_COMM_TYPE_RESOURCE_GUIDED`, MPI 4.1)
+    ///
+    /// The process set name is passed to MPI as the `"mpi_pset_name"` info key.
+    ResourceGuided(String),
+    /// An Open MPI specific split type (`OMPI_COMM_TYPE_*`)
+    OpenMpi(OpenMpiSplitType),
+}
+
+impl SplitType {
+    /// Whether the MPI library that rsmpi was built against provides this split type
+    pub fn is_available(&self) -> bool {
+        self.as_raw() != unsafe { ffi::RSMPI_UNDEFINED }
+    }
+
+    /// The name and value of the info key that carries this split type's argument
+    fn info_argument(&self) -> Option<(&'static str, &str)> {
+        match self {
+            SplitType::HwGuided(resource_type) => Some(("mpi_hw_resource_type", resource_type)),
+            SplitType::ResourceGuided(pset_name) => Some(("mpi_pset_name", pset_name)),
+            _ => None,
+        }
+    }
+
+    /// The raw value understood by the MPI C API
+    fn as_raw(&self) -> c_int {
+        unsafe {
+            match self {
+                SplitType::Shared => ffi::RSMPI_COMM_TYPE_SHARED,
+                SplitType::HwGuided(_) => ffi::RSMPI_COMM_TYPE_HW_GUIDED,
+                SplitType::HwUnguided => ffi::RSMPI_COMM_TYPE_HW_UNGUIDED,
+                SplitType::ResourceGuided(_) => ffi::RSMPI_COMM_TYPE_RESOURCE_GUIDED,
+                SplitType::OpenMpi(split_type) => split_type.as_raw(),
+            }
+        }
+    }
+}
+
+/// Hardware levels by which Open MPI can split communicators
+///
+/// These are only available when rsmpi is built against Open MPI, see `SplitType::is_available()`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OpenMpiSplitType {
+    /// Processes bound to the same hardware thread
+    HwThread,
+    /// Processes bound to the same core
+    Core,
+    /// Processes sharing an L1 cache
+    L1Cache,
+    /// Processes sharing an L2 cache
+    L2Cache,
+    /// Processes sharing an L3 cache
+    L3Cache,
+    /// Processes on the same socket
+    Socket,
+    /// Processes in the same NUMA domain
+    Numa,
+    /// Processes on the same board
+    Board,
+    /// Processes on the same host
+    Host,
+    /// Processes in the same computational unit
+    ComputeUnit,
+    /// Processes in the same cluster
+    Cluster,
+}
+
+impl OpenMpiSplitType {
+    fn as_raw(self) -> c_int {
+        unsafe {
+            match self {
+                OpenMpiSplitType::HwThread => ffi::RSMPI_OMPI_COMM_TYPE_HWTHREAD,
+                OpenMpiSplitType::Core => ffi::RSMPI_OMPI_COMM_TYPE_CORE,
+                OpenMpiSplitType::L1Cache => ffi::RSMPI_OMPI_COMM_TYPE_L1CACHE,
+                OpenMpiSplitType::L2Cache => ffi::RSMPI_OMPI_COMM_TYPE_L2CACHE,
+                OpenMpiSplitType::L3Cache => ffi::RSMPI_OMPI_COMM_TYPE_L3CACHE,
+                OpenMpiSplitType::Socket => ffi::RSMPI_OMPI_COMM_TYPE_SOCKET,
+                OpenMpiSplitType::Numa => ffi::RSMPI_OMPI_COMM_TYPE_NUMA,
+                OpenMpiSplitType::Board => ffi::RSMPI_OMPI_COMM_TYPE_BOARD,
+                OpenMpiSplitType::Host => ffi::RSMPI_OMPI_COMM_TYPE_HOST,
+                OpenMpiSplitType::ComputeUnit => ffi::RSMPI_OMPI_COMM_TYPE_CU,
+                OpenMpiSplitType::Cluster => ffi::RSMPI_OMPI_COMM_TYPE_CLUSTER,
+            }
+        }
+    }
+}
+
+/// Will contain a communicator created by a non-blocking duplication.
+///
+/// # Standard section(s)
+///
+/// 6.4.2 (See: `MPI_Comm_idup`)
+#[cfg(not(msmpi))]
+#[must_use]
+pub struct DuplicateFuture {
+    newcomm: Box<MPI_Comm>,
+    req: Request<'static, ()>,
+}
+
+#[cfg(not(msmpi))]
+impl DuplicateFuture {
+    /// Wait for the duplication to finish and return the new communicator.
+    pub fn get(self) -> SimpleCommunicator {
+        self.req.wait_without_status();
+        unsafe { SimpleCommunicator::from_raw(*self.newcomm) }
+    }
+
+    /// Check whether the duplication has finished.
+    ///
+    /// If the operation has finished, the new communicator is returned. Otherwise the future
+    /// itself is returned.
+    pub fn r#try(mut self) -> Result<SimpleCommunicator, Self> {
+        match self.req.test() {
+            Ok(_) => unsafe { Ok(SimpleCommunicator::from_raw(*self.newcomm)) },
+            Err(request) => {
+                self.req = request;
+                Err(self)
+            }
+        }
+    }
+}
+
+#[cfg(not(msmpi))]
+impl fmt::Debug for DuplicateFuture {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DuplicateFuture")
+            .field("req", &self.req)
+            .finish()
+    }
+}
+
 /// Communicators are contexts for communication
 pub trait Communicator: sealed::AsHandle {
     /// Returns the number of processes available to communicate with in this `Communicator`. For
@@ -410,6 +656,17 @@ pub trait Communicator: sealed::AsHandle {
         }
     }
 
+    /// The handle of the communicator in the Fortran bindings of MPI
+    ///
+    /// The communicator stays owned by `self`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 17.2.4
+    fn to_fortran(&self) -> Fint {
+        unsafe { ffi::RSMPI_Comm_c2f(self.as_raw()) }
+    }
+
     /// Duplicate a communicator.
     ///
     /// # Examples
@@ -427,6 +684,52 @@ pub trait Communicator: sealed::AsHandle {
         }
     }
 
+    /// Initiate the duplication of a communicator.
+    ///
+    /// The returned `DuplicateFuture` yields the new communicator once the operation has
+    /// completed, allowing other work to proceed while the new context is agreed upon. Like
+    /// `duplicate()`, this is a collective operation, and all processes must start it in the same
+    /// order relative to other collective operations on this communicator.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/duplicate.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 6.4.2 (See: `MPI_Comm_idup`)
+    #[cfg(not(msmpi))]
+    fn immediate_duplicate(&self) -> DuplicateFuture {
+        // MPI only writes the new handle when the request completes, so it needs a stable address.
+        let mut newcomm = Box::new(unsafe { ffi::RSMPI_COMM_NULL });
+        unsafe {
+            let (_, request) = with_uninitialized(|request| {
+                ffi::MPI_Comm_idup(self.as_raw(), &mut *newcomm, request)
+            });
+            DuplicateFuture {
+                newcomm,
+                req: Request::from_raw(request, &(), StaticScope),
+            }
+        }
+    }
+
+    /// Duplicate a communicator, passing hints about its intended use in `info`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 6.4.2 (See: `MPI_Comm_dup_with_info`)
+    #[cfg(not(msmpi))]
+    fn duplicate_with_info(&self, info: &Info) -> SimpleCommunicator {
+        unsafe {
+            SimpleCommunicator::from_raw(
+                with_uninitialized(|newcomm| {
+                    ffi::MPI_Comm_dup_with_info(self.as_raw(), info.as_raw(), newcomm)
+                })
+                .1,
+            )
+        }
+    }
+
     /// Split a communicator by color.
     ///
     /// Creates as many new communicators as distinct values of `color` are given. All processes
@@ -473,20 +776,65 @@ pub trait Communicator: sealed::AsHandle {
     ///
     /// 6.4.2 (See: `MPI_Comm_split_type`)
     fn split_shared(&self, key: c_int) -> SimpleCommunicator {
-        unsafe {
+        self.split_by_type(SplitType::Shared, key, &Info::new())
+            .ok()
+            .flatten()
+            .expect("rsmpi internal error: MPI implementation incorrectly returned MPI_COMM_NULL from MPI_Comm_split_type(..., MPI_COMM_TYPE_SHARED, ...)")
+    }
+
+    /// Split the communicator into subcommunicators of processes that share a resource, e.g. a
+    /// node, a NUMA domain or a cache, as described by `split_type`.
+    ///
+    /// Within each subgroup, the processes are ranked in the order defined by the value of the
+    /// argument key, with ties broken according to their rank in the old group. `info` may
+    /// carry further hints; the argument of `SplitType::HwGuided` and `SplitType::ResourceGuided`
+    /// is added to a copy of it.
+    ///
+    /// Returns `None` on processes that are not part of any subcommunicator, which is the case on
+    /// all processes if the split type is not available (see `SplitType::is_available()`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `MpiError::StringNul` if the argument of `split_type` contains interior 0 bytes.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/split_type.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 6.4.2 (See: `MPI_Comm_split_type`)
+    fn split_by_type(
+        &self,
+        split_type: SplitType,
+        key: Key,
+        info: &Info,
+    ) -> Result<Option<SimpleCommunicator>, MpiError> {
+        let with_argument;
+        let info = match split_type.info_argument() {
+            Some((info_key, value)) => {
+                let mut copy = info.clone();
+                copy.set(info_key, value)?;
+                with_argument = copy;
+                &with_argument
+            }
+            None => info,
+        };
+
+        Ok(unsafe {
             SimpleCommunicator::try_from_raw(
                 with_uninitialized(|newcomm| {
                     ffi::MPI_Comm_split_type(
                         self.as_raw(),
-                        ffi::RSMPI_COMM_TYPE_SHARED,
+                        split_type.as_raw(),
                         key,
-                        ffi::RSMPI_INFO_NULL,
+                        info.as_raw(),
                         newcomm,
                     )
                 })
-                    .1,
-            ).expect("rsmpi internal error: MPI implementation incorrectly returned MPI_COMM_NULL from MPI_Comm_split_type(..., MPI_COMM_TYPE_SHARED, ...)")
-        }
+                .1,
+            )
+        })
     }
 
     /// Split a communicator collectively by subgroup.
@@ -539,9 +887,16 @@ pub trait Communicator: sealed::AsHandle {
     /// Like `split_by_subgroup()` but can avoid collision of concurrent calls
     /// (i.e. multithreaded) by passing in distinct tags.
     ///
+    /// Only the members of `group` take part in this call, and all of them have to, passing the
+    /// same `group` and `tag`. Processes outside of the group can carry on with other work.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/split.rs`
+    ///
     /// # Standard section(s)
     ///
-    /// 6.4.2
+    /// 6.4.2 (See: `MPI_Comm_create_group`)
     #[cfg(not(msmpi))]
     fn split_by_subgroup_with_tag(
         &self,
@@ -806,6 +1161,108 @@ pub trait Communicator: sealed::AsHandle {
         position
     }
 
+    /// Unpacks an implementation-specific byte array from `pack` or `pack_into` into a buffer of a
+    /// specific datatype, starting at `position`.
+    ///
+    /// Unlike `unpack_into`, this checks that the packed bytes for `outbuf` remain in `inbuf`
+    /// after `position` and returns an error instead of letting MPI read past the end of `inbuf`.
+    /// Returns the position following the unpacked data.
+    ///
+    /// # Examples
+    /// See `examples/pack.rs`
+    ///
+    /// # Standard Sections
+    ///
+    /// 4.2, see MPI_Unpack
+    fn unpack_from<Buf>(
+        &self,
+        inbuf: &[u8],
+        outbuf: &mut Buf,
+        position: Count,
+    ) -> Result<Count, MpiError>
+    where
+        Buf: ?Sized + BufferMut,
+        Self: Sized,
+    {
+        let outbuf_dt = outbuf.as_datatype();
+        let count = outbuf.count();
+        let ptr = outbuf.pointer_mut();
+        unsafe {
+            check_packed_remaining_native(
+                inbuf.len(),
+                position
+                    .value_as()
+                    .expect("Packed buffer position must be non-negative"),
+                count,
+                &outbuf_dt,
+                self.as_raw(),
+                || ptr as *const _,
+            )?;
+        }
+        Ok(unsafe { self.unpack_into(inbuf, outbuf, position) })
+    }
+
+    /// Unpacks `count` elements of type `Msg` from an implementation-specific byte array produced
+    /// by `pack` or `pack_into` into a newly allocated `Vec`, starting at `position`.
+    ///
+    /// Returns the unpacked elements and the position following them.
+    ///
+    /// # Examples
+    /// See `examples/pack.rs`
+    ///
+    /// # Standard Sections
+    ///
+    /// 4.2, see MPI_Unpack
+    fn unpack_vec<Msg>(
+        &self,
+        inbuf: &[u8],
+        count: Count,
+        position: Count,
+    ) -> Result<(Vec<Msg>, Count), MpiError>
+    where
+        Msg: Equivalence,
+        Self: Sized,
+    {
+        let datatype = Msg::equivalent_datatype();
+        let len = count
+            .value_as::<usize>()
+            .expect("Cannot unpack a negative number of elements");
+        let mut outbuf = Vec::<Msg>::with_capacity(len);
+        let ptr = outbuf.as_mut_ptr();
+        unsafe {
+            check_packed_remaining_native(
+                inbuf.len(),
+                position
+                    .value_as()
+                    .expect("Packed buffer position must be non-negative"),
+                count,
+                &datatype,
+                self.as_raw(),
+                || {
+                    // Only needed to measure the last value of `inbuf`, which is packed from
+                    // `outbuf`.
+                    ptr.write_bytes(0, len);
+                    ptr as *const _
+                },
+            )?;
+        }
+
+        let mut position: Count = position;
+        unsafe {
+            ffi::MPI_Unpack(
+                inbuf.as_ptr() as *const _,
+                inbuf.count(),
+                &mut position,
+                outbuf.as_mut_ptr() as *mut _,
+                count,
+                datatype.as_raw(),
+                self.as_raw(),
+            );
+            outbuf.set_len(len);
+        }
+        Ok((outbuf, position))
+    }
+
     /// Returns the parent Communicator, if any
     ///
     /// # Standard Sections
@@ -1091,8 +1548,57 @@ unsafe impl AsRaw for UserGroup {
 
 impl Group for UserGroup {}
 
+/// A group owned by someone else, e.g. a C or Fortran library, that is valid for `'a`
+///
+/// The group is not freed when the `BorrowedGroup` is dropped.
+#[derive(Copy, Clone)]
+pub struct BorrowedGroup<'a>(MPI_Group, PhantomData<&'a ()>);
+
+impl<'a> BorrowedGroup<'a> {
+    /// Borrows the group `raw`, returns `None` if `raw` is `MPI_GROUP_NULL`.
+    ///
+    /// # Safety
+    /// - `raw` must be a live group handle or `MPI_GROUP_NULL`
+    /// - `raw` must not be freed during `'a`
+    pub unsafe fn from_raw_borrowed(raw: MPI_Group) -> Option<Self> {
+        (raw != ffi::RSMPI_GROUP_NULL).then_some(BorrowedGroup(raw, PhantomData))
+    }
+
+    /// Borrows the group with the Fortran handle `group`, returns `None` if `group` is
+    /// `MPI_GROUP_NULL`.
+    ///
+    /// # Safety
+    /// - `group` must be the handle of a live group or of `MPI_GROUP_NULL`
+    /// - the group must not be freed during `'a`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 17.2.4
+    pub unsafe fn from_fortran(group: Fint) -> Option<Self> {
+        Self::from_raw_borrowed(ffi::RSMPI_Group_f2c(group))
+    }
+}
+
+unsafe impl<'a> AsRaw for BorrowedGroup<'a> {
+    type Raw = MPI_Group;
+    fn as_raw(&self) -> Self::Raw {
+        self.0
+    }
+}
+
+impl<'a> Group for BorrowedGroup<'a> {}
+
 /// Groups are collections of parallel processes
 pub trait Group: AsRaw<Raw = MPI_Group> {
+    /// The handle of the group in the Fortran bindings of MPI
+    ///
+    /// # Standard section(s)
+    ///
+    /// 17.2.4
+    fn to_fortran(&self) -> Fint {
+        unsafe { ffi::RSMPI_Group_c2f(self.as_raw()) }
+    }
+
     /// Group union
     ///
     /// Constructs a new group that contains all members of the first group followed by all members
@@ -1200,6 +1706,98 @@ pub trait Group: AsRaw<Raw = MPI_Group> {
         }
     }
 
+    /// Subgroup including ranges of ranks
+    ///
+    /// Constructs a new group containing the processes whose ranks in the old group are listed by
+    /// `ranges`, in the order they are listed.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/group.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 6.3.2 (See: `MPI_Group_range_incl`)
+    fn include_ranges(&self, ranges: &[RankRange]) -> UserGroup {
+        let (count, mut raw) = RankRange::as_raw_ranges(ranges);
+        unsafe {
+            UserGroup(
+                with_uninitialized(|newgroup| {
+                    ffi::MPI_Group_range_incl(self.as_raw(), count, raw.as_mut_ptr(), newgroup)
+                })
+                .1,
+            )
+        }
+    }
+
+    /// Subgroup excluding ranges of ranks
+    ///
+    /// Constructs a new group containing those processes from the old group whose ranks are not
+    /// listed by `ranges`.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/group.rs`
+    ///
+    /// # Standard section(s)
+    ///
+    /// 6.3.2 (See: `MPI_Group_range_excl`)
+    fn exclude_ranges(&self, ranges: &[RankRange]) -> UserGroup {
+        let (count, mut raw) = RankRange::as_raw_ranges(ranges);
+        unsafe {
+            UserGroup(
+                with_uninitialized(|newgroup| {
+                    ffi::MPI_Group_range_excl(self.as_raw(), count, raw.as_mut_ptr(), newgroup)
+                })
+                .1,
+            )
+        }
+    }
+
+    /// Subgroup of the processes that satisfy a predicate
+    ///
+    /// `predicate` is called with the rank of each member in the world group, i.e. its rank in
+    /// `MPI_COMM_WORLD`. The members for which it returns `true` keep their relative order in the
+    /// new group.
+    ///
+    /// # Examples
+    ///
+    /// See `examples/group.rs`
+    fn filter<F>(&self, mut predicate: F) -> UserGroup
+    where
+        F: FnMut(Rank) -> bool,
+        Self: Sized,
+    {
+        let included: Vec<Rank> = self
+            .ranks()
+            .into_iter()
+            .enumerate()
+            .filter(|&(_, world_rank)| predicate(world_rank))
+            .map(|(rank, _)| {
+                rank.value_as()
+                    .expect("Group rank cannot be expressed as a Rank.")
+            })
+            .collect();
+        self.include(&included[..])
+    }
+
+    /// The ranks in the world group of all members of this group, in group order
+    ///
+    /// # Examples
+    ///
+    /// See `examples/group.rs`
+    fn ranks(&self) -> Vec<Rank>
+    where
+        Self: Sized,
+    {
+        let world_group = SimpleCommunicator::world().group();
+        let ranks: Vec<Rank> = (0..self.size()).collect();
+        self.translate_ranks(&ranks[..], &world_group)
+            .into_iter()
+            .map(|rank| rank.expect("Group member is not part of the world group"))
+            .collect()
+    }
+
     /// Number of processes in the group.
     ///
     /// # Standard section(s)
@@ -1287,6 +1885,71 @@ pub trait Group: AsRaw<Raw = MPI_Group> {
     }
 }
 
+/// The ranks `first`, `first + stride`, `first + 2 * stride`, ... that do not go past `last`
+///
+/// A negative `stride` describes a descending range. Ranges are used to construct groups with
+/// `Group::include_ranges()` and `Group::exclude_ranges()`. A `RangeInclusive<Rank>` converts to
+/// a range with stride 1.
+///
+/// # Standard section(s)
+///
+/// 6.3.2 (See: `MPI_Group_range_incl`)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RankRange {
+    first: Rank,
+    last: Rank,
+    stride: Rank,
+}
+
+impl RankRange {
+    /// The ranks in `range` that are `stride` apart, starting at `range.start()`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is 0.
+    pub fn with_stride(range: RangeInclusive<Rank>, stride: Rank) -> Self {
+        assert_ne!(stride, 0, "The stride of a rank range must not be 0");
+        RankRange {
+            first: *range.start(),
+            last: *range.end(),
+            stride,
+        }
+    }
+
+    /// The first rank of the range
+    pub fn first(&self) -> Rank {
+        self.first
+    }
+
+    /// The bound of the range, which is included if reached with whole strides
+    pub fn last(&self) -> Rank {
+        self.last
+    }
+
+    /// The distance between consecutive ranks
+    pub fn stride(&self) -> Rank {
+        self.stride
+    }
+
+    fn as_raw_ranges(ranges: &[RankRange]) -> (c_int, Vec<[c_int; 3]>) {
+        let count = ranges
+            .len()
+            .value_as()
+            .expect("Number of rank ranges cannot be expressed as an MPI int.");
+        let raw = ranges
+            .iter()
+            .map(|range| [range.first, range.last, range.stride])
+            .collect();
+        (count, raw)
+    }
+}
+
+impl From<RangeInclusive<Rank>> for RankRange {
+    fn from(range: RangeInclusive<Rank>) -> Self {
+        RankRange::with_stride(range, 1)
+    }
+}
+
 /// The relation between two groups.
 ///
 /// # Standard section(s)
@@ -0,0 +1,460 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, BinaryHeap},
+    os::raw::c_int,
+};
+
+use conv::ConvUtil;
+
+use super::{Color, Communicator, Group, Rank, SimpleCommunicator};
+use crate::{
+    collective::CommunicatorCollectives, environment::processor_name, ffi, with_uninitialized,
+    Count,
+};
+
+/// Describes which shared-memory node each rank of a communicator runs on.
+///
+/// Nodes are numbered from 0 in the order of their lowest rank.
+///
+/// # Examples
+/// See `examples/reorder.rs`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeMap {
+    node_of_rank: Vec<usize>,
+    names: Vec<String>,
+}
+
+impl NodeMap {
+    /// Collectively determines the node of every rank of `comm`.
+    ///
+    /// Nodes are the groups of processes found by
+    /// [`split_shared`](trait.Communicator.html#method.split_shared). Each node is named after the
+    /// [`processor_name`](../environment/fn.processor_name.html) of its lowest rank.
+    pub fn gather<C>(comm: &C) -> NodeMap
+    where
+        C: Communicator,
+    {
+        let shared = comm.split_shared(comm.rank());
+        let leader = shared
+            .group()
+            .translate_rank(0, &comm.group())
+            .expect("The node-local communicator is part of its parent communicator");
+        let leaders = comm.all_gather_vec(&[leader]);
+
+        let name =
+            processor_name().unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned());
+        let (name_bytes, name_counts) = comm.all_gather_varcount_vec(name.as_bytes());
+        let mut all_names = Vec::with_capacity(name_counts.len());
+        let mut rest = &name_bytes[..];
+        for count in name_counts {
+            let (name, tail) = rest.split_at(
+                count
+                    .value_as()
+                    .expect("Received a negative processor name length"),
+            );
+            all_names.push(String::from_utf8_lossy(name).into_owned());
+            rest = tail;
+        }
+
+        let mut node_leaders = leaders.clone();
+        node_leaders.sort_unstable();
+        node_leaders.dedup();
+
+        let node_of_rank = leaders
+            .iter()
+            .map(|leader| {
+                node_leaders
+                    .binary_search(leader)
+                    .expect("Every leader is in the list of leaders")
+            })
+            .collect();
+        let names = node_leaders
+            .iter()
+            .map(|&leader| all_names[Self::index(leader)].clone())
+            .collect();
+
+        NodeMap {
+            node_of_rank,
+            names,
+        }
+    }
+
+    /// A node map where rank `r` runs on node `nodes[r]`, e.g. to plan a reordering for a machine
+    /// other than the current one.
+    ///
+    /// Panics if a node index between 0 and the largest index in `nodes` does not occur in
+    /// `nodes`.
+    pub fn from_nodes(nodes: Vec<usize>) -> NodeMap {
+        let num_nodes = nodes.iter().max().map_or(0, |&max| max + 1);
+        let mut seen = vec![false; num_nodes];
+        for &node in &nodes {
+            seen[node] = true;
+        }
+        assert!(
+            seen.iter().all(|&s| s),
+            "Node indices must be contiguous, but {:?} are not",
+            nodes
+        );
+
+        NodeMap {
+            node_of_rank: nodes,
+            names: (0..num_nodes).map(|node| format!("node{}", node)).collect(),
+        }
+    }
+
+    /// The number of ranks
+    pub fn num_ranks(&self) -> usize {
+        self.node_of_rank.len()
+    }
+
+    /// The number of distinct nodes
+    pub fn num_nodes(&self) -> usize {
+        self.names.len()
+    }
+
+    /// The node that `rank` runs on
+    pub fn node_of(&self, rank: Rank) -> usize {
+        self.node_of_rank[Self::index(rank)]
+    }
+
+    /// The ranks that run on `node`, in increasing order
+    pub fn ranks_on(&self, node: usize) -> Vec<Rank> {
+        self.node_of_rank
+            .iter()
+            .enumerate()
+            .filter(|&(_, &n)| n == node)
+            .map(|(rank, _)| Self::rank(rank))
+            .collect()
+    }
+
+    /// The name of `node`
+    pub fn name(&self, node: usize) -> &str {
+        &self.names[node]
+    }
+
+    fn index(rank: Rank) -> usize {
+        rank.value_as().expect("Ranks must not be negative")
+    }
+
+    fn rank(index: usize) -> Rank {
+        index
+            .value_as()
+            .expect("Rank cannot be expressed as an MPI int")
+    }
+}
+
+/// An undirected graph of the communication volume between the logical ranks of an application
+///
+/// Vertices are the ranks the application uses in its algorithm, e.g. the row-major position of a
+/// process in a Cartesian grid. Edge weights are arbitrary, e.g. bytes per iteration.
+///
+/// # Examples
+/// See `examples/reorder.rs`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommGraph {
+    edges: Vec<BTreeMap<usize, u64>>,
+}
+
+impl CommGraph {
+    /// A graph of `num_vertices` vertices without edges
+    pub fn new(num_vertices: usize) -> CommGraph {
+        CommGraph {
+            edges: vec![BTreeMap::new(); num_vertices],
+        }
+    }
+
+    /// The graph of the face neighbors in a Cartesian grid with extents `dims` and periodicity
+    /// `periods`, where every pair of neighbors exchanges `weight`.
+    ///
+    /// Vertices are numbered in row-major order, which is the rank order of a communicator
+    /// returned by
+    /// [`create_cartesian_communicator`](trait.Communicator.html#method.create_cartesian_communicator)
+    /// with `reorder = false`.
+    pub fn cartesian(dims: &[Count], periods: &[bool], weight: u64) -> CommGraph {
+        assert_eq!(
+            dims.len(),
+            periods.len(),
+            "dims and periods must be the same length"
+        );
+        let dims: Vec<usize> = dims
+            .iter()
+            .map(|&d| d.value_as().expect("Extents must not be negative"))
+            .collect();
+        let num_vertices = dims.iter().product();
+        let mut graph = CommGraph::new(num_vertices);
+
+        let mut stride = 1;
+        for axis in (0..dims.len()).rev() {
+            let extent = dims[axis];
+            for vertex in 0..num_vertices {
+                let coord = (vertex / stride) % extent;
+                if coord + 1 < extent {
+                    graph.add_edge(vertex, vertex + stride, weight);
+                } else if periods[axis] && extent > 1 {
+                    graph.add_edge(vertex, vertex - coord * stride, weight);
+                }
+            }
+            stride *= extent;
+        }
+        graph
+    }
+
+    /// Adds `weight` to the edge between `a` and `b`. Self-loops are ignored.
+    pub fn add_edge(&mut self, a: usize, b: usize, weight: u64) -> &mut Self {
+        assert!(
+            a < self.num_vertices() && b < self.num_vertices(),
+            "Edge ({}, {}) is out of range for a graph of {} vertices",
+            a,
+            b,
+            self.num_vertices()
+        );
+        if a != b {
+            *self.edges[a].entry(b).or_insert(0) += weight;
+            *self.edges[b].entry(a).or_insert(0) += weight;
+        }
+        self
+    }
+
+    /// The number of vertices
+    pub fn num_vertices(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// The neighbors of `vertex` and the weights of the edges to them
+    pub fn neighbors(&self, vertex: usize) -> impl Iterator<Item = (usize, u64)> + '_ {
+        self.edges[vertex].iter().map(|(&v, &w)| (v, w))
+    }
+
+    /// The total weight of the edges whose vertices are placed on different nodes, given the
+    /// process each vertex is placed on
+    fn edge_cut(&self, nodes: &NodeMap, process_of_vertex: &[Rank]) -> u64 {
+        let node = |vertex: usize| nodes.node_of(process_of_vertex[vertex]);
+        (0..self.num_vertices())
+            .flat_map(|a| self.neighbors(a).map(move |(b, w)| (a, b, w)))
+            .filter(|&(a, b, _)| a < b && node(a) != node(b))
+            .map(|(_, _, w)| w)
+            .sum()
+    }
+}
+
+/// A placement of the vertices of a `CommGraph` onto the processes of a communicator that keeps
+/// heavily communicating vertices on the same node
+///
+/// The reordering is computed locally and deterministically, so all processes that pass the same
+/// node map and graph obtain the same result without communication. Vertices are assigned to one
+/// node at a time, each time adding the unassigned vertex with the heaviest edges into the node,
+/// until the node is full. Candidates are kept in a priority queue, so a reordering takes
+/// O((V + E) log V) time for a graph of V vertices and E edges. If this does not cut less weight than the identity placement, the
+/// identity placement is kept.
+///
+/// # Examples
+/// See `examples/reorder.rs`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reordering {
+    vertex_of_process: Vec<Rank>,
+    process_of_vertex: Vec<Rank>,
+    edge_cut: u64,
+    initial_edge_cut: u64,
+}
+
+impl Reordering {
+    /// Computes a reordering of the vertices of `graph` onto the ranks described by `nodes`.
+    ///
+    /// Panics if the graph does not have one vertex per rank.
+    pub fn new(nodes: &NodeMap, graph: &CommGraph) -> Reordering {
+        let n = nodes.num_ranks();
+        assert_eq!(
+            n,
+            graph.num_vertices(),
+            "The graph must have one vertex per rank of the node map"
+        );
+
+        let identity: Vec<Rank> = (0..n).map(NodeMap::rank).collect();
+        let initial_edge_cut = graph.edge_cut(nodes, &identity);
+
+        let mut process_of_vertex = vec![0; n];
+        let mut unassigned: BTreeSet<usize> = (0..n).collect();
+        let mut gain = vec![0u64; n];
+        let mut ranks_on_node = vec![Vec::new(); nodes.num_nodes()];
+        for process in identity.iter().copied() {
+            ranks_on_node[nodes.node_of(process)].push(process);
+        }
+        for ranks in ranks_on_node {
+            // Candidates ordered by gain, then by lowest vertex. Entries whose gain is out of date
+            // or whose vertex has been assigned are skipped when they surface.
+            let mut candidates = BinaryHeap::new();
+            let mut touched = Vec::new();
+            for process in ranks {
+                let vertex = loop {
+                    match candidates.pop() {
+                        Some((g, Reverse(v))) if unassigned.contains(&v) && gain[v] == g => {
+                            break v
+                        }
+                        Some(_) => continue,
+                        None => {
+                            break *unassigned
+                                .iter()
+                                .next()
+                                .expect("There is a vertex for every process")
+                        }
+                    }
+                };
+                unassigned.remove(&vertex);
+                process_of_vertex[vertex] = process;
+                for (neighbor, weight) in graph.neighbors(vertex) {
+                    if unassigned.contains(&neighbor) && weight > 0 {
+                        if gain[neighbor] == 0 {
+                            touched.push(neighbor);
+                        }
+                        gain[neighbor] += weight;
+                        candidates.push((gain[neighbor], Reverse(neighbor)));
+                    }
+                }
+            }
+            for vertex in touched {
+                gain[vertex] = 0;
+            }
+        }
+
+        let mut edge_cut = graph.edge_cut(nodes, &process_of_vertex);
+        if edge_cut >= initial_edge_cut {
+            process_of_vertex = identity;
+            edge_cut = initial_edge_cut;
+        }
+
+        let mut vertex_of_process = vec![0; n];
+        for (vertex, &process) in process_of_vertex.iter().enumerate() {
+            vertex_of_process[NodeMap::index(process)] = NodeMap::rank(vertex);
+        }
+
+        Reordering {
+            vertex_of_process,
+            process_of_vertex,
+            edge_cut,
+            initial_edge_cut,
+        }
+    }
+
+    /// The vertex placed on process `rank`, i.e. its rank in the reordered communicator
+    pub fn vertex_of(&self, rank: Rank) -> Rank {
+        self.vertex_of_process[NodeMap::index(rank)]
+    }
+
+    /// The process that `vertex` is placed on
+    pub fn process_of(&self, vertex: Rank) -> Rank {
+        self.process_of_vertex[NodeMap::index(vertex)]
+    }
+
+    /// The total weight of the edges between vertices on different nodes after reordering
+    pub fn edge_cut(&self) -> u64 {
+        self.edge_cut
+    }
+
+    /// The total weight of the edges between vertices on different nodes if vertex `i` were
+    /// placed on process `i`
+    pub fn initial_edge_cut(&self) -> u64 {
+        self.initial_edge_cut
+    }
+
+    /// Collectively creates a communicator from `comm` in which every process has the rank of
+    /// the vertex placed on it.
+    ///
+    /// Creating a Cartesian communicator from the result with `reorder = false` places grid
+    /// position `i` on the process that vertex `i` of
+    /// [`CommGraph::cartesian`](struct.CommGraph.html#method.cartesian) was assigned to.
+    ///
+    /// Panics if the size of `comm` differs from the number of vertices.
+    pub fn apply<C>(&self, comm: &C) -> SimpleCommunicator
+    where
+        C: Communicator,
+    {
+        assert_eq!(
+            self.vertex_of_process.len(),
+            NodeMap::index(comm.size()),
+            "The communicator must have one process per vertex"
+        );
+        comm.split_by_color_with_key(Color::with_value(0), self.vertex_of(comm.rank()))
+            .expect("Every process uses the same color")
+    }
+
+    /// The processes that the vertex placed on process `rank` communicates with, and the weights
+    /// of the edges to them, in increasing order of the neighboring vertex
+    ///
+    /// These are the sources and destinations that `rank` passes to
+    /// `MPI_Dist_graph_create_adjacent` on the original communicator, see
+    /// [`create_dist_graph`](#method.create_dist_graph).
+    ///
+    /// Panics if `graph` does not have one vertex per process.
+    pub fn neighbor_processes(&self, graph: &CommGraph, rank: Rank) -> Vec<(Rank, u64)> {
+        assert_eq!(
+            self.process_of_vertex.len(),
+            graph.num_vertices(),
+            "The graph must have one vertex per process"
+        );
+        graph
+            .neighbors(NodeMap::index(self.vertex_of(rank)))
+            .map(|(neighbor, weight)| (self.process_of_vertex[neighbor], weight))
+            .collect()
+    }
+
+    /// Collectively creates a distributed graph communicator from `comm` in which every process
+    /// keeps its rank and is adjacent to the processes its vertex of `graph` is connected to.
+    ///
+    /// Unlike [`apply`](#method.apply), ranks are not renumbered; use
+    /// [`vertex_of`](#method.vertex_of) to find the vertex placed on a process. Edge weights are
+    /// passed to MPI as communication weights and saturate at the largest MPI int.
+    ///
+    /// Panics if the size of `comm` or the number of vertices of `graph` differ from the number
+    /// of vertices of the reordering.
+    ///
+    /// # Standard section(s)
+    /// 7.5.4 (See: `MPI_Dist_graph_create_adjacent`)
+    pub fn create_dist_graph<C>(&self, comm: &C, graph: &CommGraph) -> SimpleCommunicator
+    where
+        C: Communicator,
+    {
+        assert_eq!(
+            self.vertex_of_process.len(),
+            NodeMap::index(comm.size()),
+            "The communicator must have one process per vertex"
+        );
+        let (neighbors, weights): (Vec<Rank>, Vec<c_int>) = self
+            .neighbor_processes(graph, comm.rank())
+            .into_iter()
+            .map(|(process, weight)| (process, weight.value_as().unwrap_or(c_int::MAX)))
+            .unzip();
+        let degree = neighbors
+            .len()
+            .value_as()
+            .expect("Degree cannot be expressed as an MPI int");
+        // `MPI_UNWEIGHTED` and `MPI_WEIGHTS_EMPTY` are sentinel pointers, so an empty weight list
+        // must still point to valid memory to not be mistaken for either of them.
+        let no_weights: [c_int; 1] = [0];
+        let weights = if weights.is_empty() {
+            no_weights.as_ptr()
+        } else {
+            weights.as_ptr()
+        };
+
+        unsafe {
+            SimpleCommunicator::try_from_raw(
+                with_uninitialized(|dist_graph| {
+                    ffi::MPI_Dist_graph_create_adjacent(
+                        comm.as_raw(),
+                        degree,
+                        neighbors.as_ptr(),
+                        weights,
+                        degree,
+                        neighbors.as_ptr(),
+                        weights,
+                        ffi::RSMPI_INFO_NULL,
+                        0,
+                        dist_graph,
+                    )
+                })
+                .1,
+            )
+            .expect("Every process is part of the distributed graph")
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Shared memory windows
+//!
+//! Processes that share a node can allocate memory that all of them can access directly with
+//! `SharedWindow`. A communicator suitable for this is returned by `Communicator::split_shared()`.
+//!
+//! # Unfinished features
+//!
+//! - **11.2**: Window creation other than `MPI_Win_allocate_shared()`, `MPI_Win_create()`,
+//! `MPI_Win_allocate()`, `MPI_Win_create_dynamic()`
+//! - **11.3**: Communication calls, `MPI_Put()`, `MPI_Get()`, `MPI_Accumulate()`, ...
+//! - **11.5**: Synchronization calls other than `MPI_Win_lock_all()` and `MPI_Win_sync()`
+
+use std::{
+    fmt,
+    marker::PhantomData,
+    mem,
+    os::raw::{c_int, c_void},
+    ptr, slice,
+};
+
+use conv::ConvUtil;
+
+use crate::{
+    collective::CommunicatorCollectives,
+    datatype::Equivalence,
+    ffi,
+    ffi::MPI_Win,
+    raw::traits::*,
+    topology::{Communicator, Rank, SimpleCommunicator},
+    with_uninitialized, with_uninitialized2, Address,
+};
+
+/// A window of node-local memory shared by all processes of a communicator
+///
+/// Each process contributes a segment of `local_len` elements of type `T`. A process can modify
+/// its own segment through `local_mut()` and read the segment of any process through
+/// `shared_query()` without any message passing.
+///
+/// Writes become visible to other processes only after synchronization, e.g. through `barrier()`.
+/// Since other processes can write to a segment at any time, the slices returned by `local()` and
+/// `shared_query()` are only valid while the processes agree not to write to it, so these methods
+/// are `unsafe`. Writes and reads are usually separated by `barrier()` calls.
+///
+/// The window holds a passive target access epoch on all processes (`MPI_Win_lock_all()`) for its
+/// whole lifetime, as is customary for shared memory windows.
+///
+/// # Examples
+///
+/// See `examples/shared_window.rs`
+///
+/// # Standard section(s)
+///
+/// 11.2.3
+pub struct SharedWindow<T> {
+    win: MPI_Win,
+    comm: SimpleCommunicator,
+    local: *mut T,
+    local_len: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<T> SharedWindow<T>
+where
+    T: Equivalence + Copy + Default,
+{
+    /// Collectively allocates a shared memory window in which this process owns `local_len`
+    /// elements, initialized to `T::default()`.
+    ///
+    /// All processes of `comm` must be able to share memory, which is guaranteed for
+    /// communicators returned by `Communicator::split_shared()`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.2.3, see MPI_Win_allocate_shared
+    pub fn allocate<C>(comm: &C, local_len: usize) -> Self
+    where
+        C: Communicator,
+    {
+        assert_ne!(
+            mem::size_of::<T>(),
+            0,
+            "Shared windows of zero-sized types are not supported"
+        );
+        let comm = comm.duplicate();
+
+        let size: Address = (local_len * mem::size_of::<T>())
+            .value_as()
+            .expect("Window size cannot be expressed as an MPI Address.");
+        let disp_unit: c_int = mem::size_of::<T>()
+            .value_as()
+            .expect("Element size cannot be expressed as an MPI displacement unit.");
+
+        let mut local: *mut T = ptr::null_mut();
+        let win = unsafe {
+            with_uninitialized(|win| {
+                ffi::MPI_Win_allocate_shared(
+                    size,
+                    disp_unit,
+                    ffi::RSMPI_INFO_NULL,
+                    comm.as_raw(),
+                    &mut local as *mut *mut T as *mut c_void,
+                    win,
+                )
+            })
+            .1
+        };
+        unsafe {
+            ffi::MPI_Win_lock_all(ffi::MPI_MODE_NOCHECK as c_int, win);
+        }
+
+        let mut window = SharedWindow {
+            win,
+            comm,
+            local,
+            local_len,
+            phantom: PhantomData,
+        };
+        window.local_mut().fill(T::default());
+        window.barrier();
+        window
+    }
+}
+
+impl<T> SharedWindow<T> {
+    /// The segment owned by this process
+    ///
+    /// # Safety
+    ///
+    /// No process may write to the segment while the returned slice is alive, e.g. because the
+    /// writes happen before a `barrier()` that precedes this call and after a `barrier()` that
+    /// follows the last use of the slice.
+    pub unsafe fn local(&self) -> &[T] {
+        if self.local_len == 0 {
+            return &[];
+        }
+        slice::from_raw_parts(self.local, self.local_len)
+    }
+
+    /// The segment owned by this process, for modification
+    pub fn local_mut(&mut self) -> &mut [T] {
+        if self.local_len == 0 {
+            return &mut [];
+        }
+        unsafe { slice::from_raw_parts_mut(self.local, self.local_len) }
+    }
+
+    /// The segment owned by process `rank` of the communicator the window was allocated on
+    ///
+    /// # Safety
+    ///
+    /// No process may write to the segment while the returned slice is alive, e.g. because the
+    /// writes happen before a `barrier()` that precedes this call and after a `barrier()` that
+    /// follows the last use of the slice.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.2.3, see MPI_Win_shared_query
+    pub unsafe fn shared_query(&self, rank: Rank) -> &[T] {
+        assert!(
+            0 <= rank && rank < self.comm.size(),
+            "Rank {} is not part of the window's communicator",
+            rank
+        );
+        let mut base: *mut T = ptr::null_mut();
+        let (_, size, _) = with_uninitialized2(|size: *mut Address, disp_unit: *mut c_int| {
+            ffi::MPI_Win_shared_query(
+                self.win,
+                rank,
+                size,
+                disp_unit,
+                &mut base as *mut *mut T as *mut c_void,
+            )
+        });
+        let len = size
+            .value_as::<usize>()
+            .expect("MPI_Win_shared_query returned a negative size!")
+            / mem::size_of::<T>();
+        if len == 0 || base.is_null() {
+            return &[];
+        }
+        slice::from_raw_parts(base, len)
+    }
+
+    /// Synchronizes the public and private copies of the window on this process, acting as a
+    /// memory barrier for the shared segments.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.5.4, see MPI_Win_sync
+    pub fn sync(&self) {
+        unsafe {
+            ffi::MPI_Win_sync(self.win);
+        }
+    }
+
+    /// Collectively makes all writes made before the call visible to all processes after the
+    /// call.
+    ///
+    /// This is a `sync()` followed by a barrier on the window's communicator and another `sync()`.
+    ///
+    /// # Standard section(s)
+    ///
+    /// 11.7, see MPI_Win_sync and MPI_Barrier
+    pub fn barrier(&self) {
+        self.sync();
+        self.comm.barrier();
+        self.sync();
+    }
+
+    /// The communicator the window was allocated on
+    pub fn communicator(&self) -> &SimpleCommunicator {
+        &self.comm
+    }
+}
+
+unsafe impl<T> AsRaw for SharedWindow<T> {
+    type Raw = MPI_Win;
+    fn as_raw(&self) -> Self::Raw {
+        self.win
+    }
+}
+
+impl<T> fmt::Debug for SharedWindow<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SharedWindow")
+            .field("win", &self.win)
+            .field("local_len", &self.local_len)
+            .finish()
+    }
+}
+
+impl<T> Drop for SharedWindow<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::MPI_Win_unlock_all(self.win);
+            ffi::MPI_Win_free(&mut self.win);
+        }
+    }
+}